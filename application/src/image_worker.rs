@@ -0,0 +1,3 @@
+fn main() {
+    workflow_visualizer::start_image_worker();
+}
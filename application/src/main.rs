@@ -46,6 +46,8 @@ fn main() {
         std::panic::set_hook(Box::new(console_error_panic_hook::hook));
         console_log::init().expect("could not initialize logger");
         visualizer.set_gfx_options(GfxOptions::limited_environment());
-        runner.web_run::<Engen>(visualizer, "./worker.js".to_string());
+        runner
+            .with_image_worker("./image_worker.js")
+            .web_run::<Engen>(visualizer, "./worker.js".to_string());
     }
 }
@@ -2,7 +2,7 @@ use workflow_visualizer::{
     Attach, BundleExtension, BundledIcon, Button, ButtonBorder, ButtonIcon, ButtonText, ButtonType,
     Color, IconRequest, SectionOutline, TextValue, Visualizer,
 };
-use workflow_visualizer::{GridUnit, Text, TextWrapStyle};
+use workflow_visualizer::{AutoScale, GridUnit, Text, TextWrapStyle};
 use workflow_visualizer::{ResponsiveGridLocation, ResponsiveGridRange, ResponsiveGridView};
 
 pub struct EntryAttachment;
@@ -17,6 +17,7 @@ impl Attach for EntryAttachment {
                 Color::CYAN_DARK,
                 TextWrapStyle::letter(),
             )
+            .extend(AutoScale::new(12, 122))
             .extend(ResponsiveGridView::new(
                 ResponsiveGridRange::new(
                     ResponsiveGridLocation::new(1.near()),
@@ -4,7 +4,7 @@ use std::marker::PhantomData;
 use bevy_ecs::prelude::{Changed, Component, Entity, Query, Res};
 use bevy_ecs::system::Commands;
 
-use crate::{TimeDelta, TimeTracker, Timer};
+use crate::{TimeDelta, TimeMarker, TimeTracker, Timer};
 #[derive(Copy, Clone, Default, Debug, Component)]
 pub struct Interpolation {
     remaining: f32,
@@ -97,6 +97,69 @@ impl<T: Animate> Animation<T> {
         self.timer.set_offset(offset);
         self
     }
+    /// true once the timer has passed its start offset
+    pub(crate) fn running(&self) -> bool {
+        self.timer.time_elapsed().is_some()
+    }
+    /// replaces both ends of an animation that is not yet running
+    pub(crate) fn retarget_pending(&mut self, start: T, end: T) {
+        self.interpolations = start.interpolations(&end);
+        self.start.replace(start);
+        self.end.replace(end);
+    }
+    /// aims a running animation at a new end from `current`, finishing in the remaining time
+    pub(crate) fn retarget(&mut self, current: &T, end: T) {
+        let remaining = match self.timer.time_elapsed() {
+            Some(elapsed) => (1f32 - self.timer.percent_elapsed(elapsed)).max(0f32),
+            None => 1f32,
+        };
+        self.interpolations = current
+            .interpolations(&end)
+            .into_iter()
+            .map(|interpolation| {
+                if remaining > f32::EPSILON {
+                    Interpolation {
+                        remaining: interpolation.remaining,
+                        total: interpolation.total / remaining,
+                        extraction: None,
+                    }
+                } else {
+                    interpolation
+                }
+            })
+            .collect();
+        self.end.replace(end);
+    }
+}
+
+impl<T: Animate> Animation<T> {
+    /// extracts the share of each interpolation covered by `frame_diff`
+    pub(crate) fn update(&mut self, mark: TimeMarker, frame_diff: TimeDelta) {
+        if self.timer.started() {
+            self.timer.mark(mark);
+            if let Some(_elapsed) = self.timer.time_elapsed() {
+                let percent = self.timer.percent_elapsed(frame_diff);
+                if self.timer.finished() {
+                    self.done = true;
+                }
+                let anim_done = self.done;
+                let mut all_finished = true;
+                for interpolation in self.interpolations.iter_mut() {
+                    if anim_done {
+                        let _extract = interpolation.finish();
+                    } else {
+                        let _extract = interpolation.extract(percent);
+                        if !interpolation.done() {
+                            all_finished = false;
+                        }
+                    }
+                }
+                if all_finished {
+                    self.done = true;
+                }
+            }
+        }
+    }
 }
 
 pub trait Animate
@@ -159,30 +222,7 @@ pub(crate) fn update_animations<T: Animate + Send + Sync + 'static>(
     time_tracker: Res<TimeTracker>,
 ) {
     for mut animation in animations.iter_mut() {
-        if animation.timer.started() {
-            animation.timer.mark(time_tracker.mark());
-            if let Some(_elapsed) = animation.timer.time_elapsed() {
-                let percent = animation.timer.percent_elapsed(time_tracker.frame_diff());
-                if animation.timer.finished() {
-                    animation.done = true;
-                }
-                let anim_done = animation.done;
-                let mut all_finished = true;
-                for interpolation in animation.interpolations.iter_mut() {
-                    if anim_done {
-                        let _extract = interpolation.finish();
-                    } else {
-                        let _extract = interpolation.extract(percent);
-                        if !interpolation.done() {
-                            all_finished = false;
-                        }
-                    }
-                }
-                if all_finished {
-                    animation.done = true;
-                }
-            }
-        }
+        animation.update(time_tracker.mark(), time_tracker.frame_diff());
     }
 }
 
@@ -196,3 +236,35 @@ pub(crate) fn end_animations<T: Animate + Send + Sync + 'static>(
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn retarget_mid_flight() {
+    use crate::{InterfaceContext, Position};
+    let start = Position::<InterfaceContext>::new(0f32, 0f32);
+    let mut animation = Animation::new(
+        Timer::new(1f32),
+        start.interpolations(&Position::new(100f32, 40f32)),
+        start,
+        Position::new(100f32, 40f32),
+    );
+    animation.timer.start(TimeMarker(0.0));
+    let mut current = start;
+    let mut step = |animation: &mut Animation<Position<InterfaceContext>>, mark: f64| {
+        animation.update(TimeMarker(mark), TimeDelta(0.25));
+        let extractions = animation.extractions();
+        current.x += extractions[0].map_or(0f32, |extraction| extraction.value());
+        current.y += extractions[1].map_or(0f32, |extraction| extraction.value());
+        current
+    };
+    step(&mut animation, 0.25);
+    let midway = step(&mut animation, 0.5);
+    assert!((midway.x - 50f32).abs() < 1e-3 && (midway.y - 20f32).abs() < 1e-3);
+    assert!(animation.running());
+    animation.retarget(&midway, Position::new(200f32, 0f32));
+    let quarter = step(&mut animation, 0.75);
+    assert!((quarter.x - 125f32).abs() < 1e-3 && (quarter.y - 10f32).abs() < 1e-3);
+    let end = step(&mut animation, 1.0);
+    assert!(animation.done());
+    assert!((end.x - 200f32).abs() < 1e-3 && end.y.abs() < 1e-3);
+}
@@ -9,9 +9,11 @@ impl Attach for ButtonAttachment {
         visualizer.job.task(Visualizer::TASK_MAIN).add_systems((
             system::spawn.in_set(SyncPoint::Spawn),
             system::place.in_set(SyncPoint::Spawn).after(system::spawn),
+            system::text_forward.in_set(SyncPoint::SecondaryEffects),
             system::scale_change
                 .in_set(SyncPoint::SecondaryEffects)
-                .after(crate::snap_grid::reapply),
+                .after(crate::snap_grid::reapply)
+                .after(system::text_forward),
             system::border_change.in_set(SyncPoint::Reconfigure),
             system::color_invert.in_set(SyncPoint::Reconfigure),
             system::secondary_despawn
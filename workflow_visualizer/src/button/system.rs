@@ -9,8 +9,8 @@ use crate::snap_grid::{FloatPlacementDescriptor, FloatPlacer, FloatRange, FloatV
 use crate::{
     ActiveInteraction, Area, BackgroundColor, BorderColor, BundleExtension, ButtonTag, ButtonType,
     Color, IconScale, InterfaceContext, Layer, MonoSpacedFont, Panel, PanelTag, PanelType,
-    Position, SectionOutline, Text, TextScale, TextSectionDescriptorKnown, TextValue,
-    TextWrapStyle, Toggled,
+    Position, SectionOutline, Text, TextAlignment, TextScale, TextSectionDescriptorKnown,
+    TextValue, TextWrapStyle, Toggled,
 };
 
 pub(crate) fn border_change(
@@ -87,7 +87,8 @@ pub(crate) fn spawn(
                         0,
                         *color,
                         TextWrapStyle::letter(),
-                    ), // .extend(SectionOutline::default()),
+                    )
+                    .extend(TextAlignment::centered()), // .extend(SectionOutline::default()),
                 )
                 .id(),
             );
@@ -154,7 +155,11 @@ pub(crate) fn scale_change(
         ),
         (
             With<ButtonTag>,
-            Or<(Changed<IconEntity>, Changed<TextEntity>)>,
+            Or<(
+                Changed<IconEntity>,
+                Changed<TextEntity>,
+                Changed<ButtonText>,
+            )>,
         ),
     >,
     mut listeners: Query<
@@ -194,7 +199,7 @@ pub(crate) fn scale_change(
             }
         }
         if let Some(entity) = text_entity.0 {
-            if let Ok((mut pos, mut area, scale, _, text_value)) = listeners.get_mut(entity) {
+            if let Ok((pos, area, scale, _, text_value)) = listeners.get_mut(entity) {
                 let new_scale = font
                     .text_section_descriptor(
                         *pos,
@@ -203,17 +208,6 @@ pub(crate) fn scale_change(
                     )
                     .scale;
                 *scale.unwrap() = new_scale;
-                // TODO integrate this into text_section_descriptor to get correct text for bounds
-                // usage with pos.y = text_section_desc.top(); from font.text_section_descriptor
-                let dims = font.character_dimensions(new_scale.px());
-                let expected = button_area.height * 0.95;
-                let actual = dims.height;
-                if actual < expected {
-                    let mut diff = expected - actual;
-                    diff /= 2f32;
-                    pos.y += diff;
-                    area.height -= diff;
-                }
             }
         }
     }
@@ -276,6 +270,20 @@ pub(crate) fn color_invert(
     }
 }
 
+pub(crate) fn text_forward(
+    buttons: Query<(&ButtonText, &TextEntity), (With<ButtonTag>, Changed<ButtonText>)>,
+    mut text_listeners: Query<&mut TextValue, Without<ButtonTag>>,
+) {
+    for (button_text, text_ent) in buttons.iter() {
+        if let (Some(ent), Some(desc)) = (text_ent.0, button_text.desc.as_ref()) {
+            if let Ok(mut text_value) = text_listeners.get_mut(ent) {
+                if text_value.0 != desc.0 {
+                    *text_value = desc.clone();
+                }
+            }
+        }
+    }
+}
 pub(crate) fn color_forward(
     mut color_listeners: Query<(&mut Color, Option<&mut BorderColor>), Without<ButtonTag>>,
     mut color_deciders: Query<
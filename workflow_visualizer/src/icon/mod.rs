@@ -1,7 +1,8 @@
 use crate::images::{Cache, Difference};
 use crate::{
-    Area, Color, EnableVisibility, ImageData, ImageFade, ImageRequest, ImageTag, InterfaceContext,
-    Layer, ResourceHandle, Section, Tag,
+    Area, Color, EnableVisibility, ImageData, ImageFade, ImageFit, ImageFocus, ImageFrame,
+    ImageLoadState, ImageMipmaps, ImagePlayback, ImageRegion, ImageRequest, ImageTag,
+    InterfaceContext, Layer, ResourceHandle, Section, Tag,
 };
 use bevy_ecs::bundle::Bundle;
 use bevy_ecs::component::Component;
@@ -25,6 +26,13 @@ pub struct Icon {
     image_icon_tag: IconTag,
     scale: IconScale,
     color: Color,
+    load_state: ImageLoadState,
+    fit: ImageFit,
+    focus: ImageFocus,
+    playback: ImagePlayback,
+    frame: ImageFrame,
+    mipmaps: ImageMipmaps,
+    region: ImageRegion,
 }
 
 impl Icon {
@@ -46,6 +54,13 @@ impl Icon {
             image_icon_tag: IconTag::new(),
             visibility: EnableVisibility::new(),
             section: Section::default(),
+            load_state: ImageLoadState::default(),
+            fit: ImageFit::default(),
+            focus: ImageFocus::default(),
+            playback: ImagePlayback::default(),
+            frame: ImageFrame::default(),
+            mipmaps: ImageMipmaps::default(),
+            region: ImageRegion::WHOLE,
         }
     }
     pub(crate) const INVALID_COLOR: Color = Color {
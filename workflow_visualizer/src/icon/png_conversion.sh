@@ -1,2 +0,0 @@
-# shellcheck disable=SC2231
-for file in bundled_icons/svg/*.svg; do inkscape "$file" -o "${file%svg}png"; done
\ No newline at end of file
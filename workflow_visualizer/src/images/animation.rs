@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::{
+    Component, DetectChanges, DetectChangesMut, Entity, Event, EventWriter, Query, Ref, Res,
+    Resource, With,
+};
+
+use crate::bundling::ResourceHandle;
+use crate::images::interface::ImageTag;
+use crate::TimeTracker;
+
+/// How an `Image` plays an animated handle (GIF, APNG or animated WebP).
+/// Still images ignore it.
+#[derive(Component, Copy, Clone, PartialEq, Debug)]
+pub struct ImagePlayback {
+    /// restart after the last frame instead of holding it
+    pub looping: bool,
+    /// multiplier of the encoded frame delays
+    pub speed: f32,
+    pub paused: bool,
+}
+impl ImagePlayback {
+    /// plays once and holds the last frame
+    pub fn once() -> Self {
+        Self {
+            looping: false,
+            ..Self::default()
+        }
+    }
+}
+impl Default for ImagePlayback {
+    fn default() -> Self {
+        Self {
+            looping: true,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+}
+/// Frame of its animation an `Image` displays; restarts when the handle changes
+#[derive(Component, Copy, Clone, PartialEq, Default, Debug)]
+pub struct ImageFrame {
+    index: usize,
+    /// seconds the current frame has been shown
+    elapsed: f32,
+    finished: bool,
+}
+impl ImageFrame {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// back to the first frame, replaying a finished non-looping playback
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+    /// advances by `delta` seconds through frames shown for `delays` seconds each.
+    /// Returns true when a non-looping `playback` reaches its last frame.
+    pub(crate) fn advance(&mut self, delays: &[f32], delta: f32, playback: &ImagePlayback) -> bool {
+        self.finished &= !playback.looping;
+        if self.finished || playback.paused || delays.is_empty() {
+            return false;
+        }
+        self.index = self.index.min(delays.len() - 1);
+        self.elapsed += delta * playback.speed.max(0.0);
+        if playback.looping {
+            let duration = delays.iter().sum::<f32>();
+            let remaining = delays[self.index..].iter().sum::<f32>();
+            if self.elapsed >= remaining {
+                self.elapsed = (self.elapsed - remaining) % duration;
+                self.index = 0;
+            }
+        }
+        while self.elapsed >= delays[self.index] {
+            if self.index + 1 == delays.len() {
+                self.elapsed = 0.0;
+                self.finished = true;
+                return true;
+            }
+            self.elapsed -= delays[self.index];
+            self.index += 1;
+        }
+        false
+    }
+}
+/// Sent when an `Image` with a non-looping `ImagePlayback` or `SpriteAnimation`
+/// reaches its last frame
+#[derive(Event, Copy, Clone, Debug)]
+pub struct PlaybackFinished {
+    pub entity: Entity,
+    pub handle: ResourceHandle,
+}
+/// Seconds each frame of the loaded animated handles is shown
+#[derive(Resource, Default)]
+pub struct ImageAnimations(pub(crate) HashMap<ResourceHandle, Vec<f32>>);
+impl ImageAnimations {
+    /// number of frames; 1 for still or unloaded images
+    pub fn frames<IN: Into<ResourceHandle>>(&self, handle: IN) -> usize {
+        self.0.get(&handle.into()).map_or(1, |delays| delays.len())
+    }
+    /// seconds of one pass through the animation
+    pub fn duration<IN: Into<ResourceHandle>>(&self, handle: IN) -> Option<f32> {
+        self.0.get(&handle.into()).map(|delays| delays.iter().sum())
+    }
+}
+pub(crate) fn advance_playback(
+    mut images: Query<
+        (Entity, Ref<ResourceHandle>, &ImagePlayback, &mut ImageFrame),
+        With<ImageTag>,
+    >,
+    animations: Res<ImageAnimations>,
+    time_tracker: Res<TimeTracker>,
+    mut finished: EventWriter<PlaybackFinished>,
+) {
+    let delta = time_tracker.frame_diff().as_f32();
+    for (entity, handle, playback, mut frame) in images.iter_mut() {
+        if handle.is_changed() && !handle.is_added() && *frame != ImageFrame::default() {
+            *frame = ImageFrame::default();
+        }
+        let Some(delays) = animations.0.get(&handle) else {
+            continue;
+        };
+        let index = frame.index;
+        let done = frame
+            .bypass_change_detection()
+            .advance(delays, delta, playback);
+        if frame.index != index {
+            frame.set_changed();
+        }
+        if done {
+            finished.send(PlaybackFinished {
+                entity,
+                handle: *handle,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn playback() {
+    let delays = [0.1, 0.2, 0.1];
+    let mut frame = ImageFrame::default();
+    let looping = ImagePlayback::default();
+    assert!(!frame.advance(&delays, 0.15, &looping));
+    assert_eq!(frame.index(), 1);
+    frame.advance(&delays, 0.2, &looping);
+    assert_eq!(frame.index(), 2);
+    frame.advance(&delays, 0.3, &looping);
+    assert_eq!(frame.index(), 1);
+    let paused = ImagePlayback {
+        paused: true,
+        ..looping
+    };
+    frame.advance(&delays, 1.0, &paused);
+    assert_eq!(frame.index(), 1);
+    let mut frame = ImageFrame::default();
+    let once = ImagePlayback {
+        speed: 2.0,
+        ..ImagePlayback::once()
+    };
+    assert!(!frame.advance(&delays, 0.1, &once));
+    assert_eq!(frame.index(), 1);
+    assert!(frame.advance(&delays, 0.2, &once));
+    assert_eq!(frame.index(), 2);
+    assert!(!frame.advance(&delays, 1.0, &once));
+    assert_eq!(frame.index(), 2);
+}
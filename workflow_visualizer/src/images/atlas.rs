@@ -0,0 +1,194 @@
+use crate::images::mipmap::MipChain;
+use crate::texture_atlas::AtlasShelves;
+use crate::{
+    Area, AtlasTexture, AtlasTextureDimensions, GfxSurface, NumericalContext, Position, Section,
+    TextureAtlas, TextureBindGroup, TextureCoordinates,
+};
+
+/// Texture shared by many images and drawn with a single bind group
+pub(crate) struct ImagePage {
+    pub(crate) texture: AtlasTexture,
+    pub(crate) bind_group: TextureBindGroup,
+    pub(crate) shelves: AtlasShelves,
+    /// holds one image too large to share a page
+    pub(crate) dedicated: bool,
+    pub(crate) mip_levels: u32,
+    /// images placed and not yet released
+    pub(crate) residents: usize,
+}
+
+impl ImagePage {
+    pub(crate) const DIMENSION: f32 = 1024f32;
+    /// images wider or taller than this get a dedicated page
+    pub(crate) const SHARED_MAX: f32 = Self::DIMENSION / 2f32;
+    /// levels down to 1/16 scale; images are aligned to 16 texels
+    pub(crate) const SHARED_MIP_LEVELS: u32 = 5;
+    /// levels down to 1/128 scale for large photos shown small
+    pub(crate) const DEDICATED_MIP_LEVELS: u32 = 8;
+    pub(crate) fn dedicated(area: Area<NumericalContext>) -> bool {
+        area.width > Self::SHARED_MAX || area.height > Self::SHARED_MAX
+    }
+    /// mip levels of the page an image of `area` is placed on
+    pub(crate) fn mip_levels(area: Area<NumericalContext>) -> u32 {
+        match Self::dedicated(area) {
+            true => Self::DEDICATED_MIP_LEVELS,
+            false => Self::SHARED_MIP_LEVELS,
+        }
+    }
+    fn new(
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        dimensions: Area<NumericalContext>,
+        dedicated: bool,
+        mip_levels: u32,
+    ) -> Self {
+        let texture = AtlasTexture::with_mip_levels(
+            gfx,
+            AtlasTextureDimensions { dimensions },
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            mip_levels,
+        );
+        let bind_group = TextureBindGroup::new(gfx, layout, &texture.view);
+        let alignment = (1 << (mip_levels - 1)) as f32;
+        Self {
+            texture,
+            bind_group,
+            shelves: AtlasShelves::aligned(dimensions, alignment),
+            dedicated,
+            mip_levels,
+            residents: 0,
+        }
+    }
+    pub(crate) fn bytes(&self) -> usize {
+        mip_bytes(self.shelves.dimensions, self.mip_levels)
+    }
+}
+/// bytes of an RGBA8 region of `area` and its mip levels
+fn mip_bytes(area: Area<NumericalContext>, levels: u32) -> usize {
+    (0..levels)
+        .map(|level| {
+            let scale = (1 << level) as f32;
+            ((area.width / scale).max(1.0) * (area.height / scale).max(1.0)) as usize * 4
+        })
+        .sum()
+}
+/// Where an uploaded image lives in the `ImagePages`
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ImageBackend {
+    pub(crate) page: usize,
+    pub(crate) slot: Section<NumericalContext>,
+    /// texels of the image itself
+    pub(crate) area: Area<NumericalContext>,
+    pub(crate) coordinates: TextureCoordinates,
+    /// edges of the image's texels, which sampling of every level stays within
+    pub(crate) bounds: TextureCoordinates,
+    pub(crate) mip_levels: u32,
+}
+impl ImageBackend {
+    /// texture memory reserved for the image, padding and mip levels included
+    pub(crate) fn bytes(&self) -> usize {
+        mip_bytes(self.slot.area, self.mip_levels)
+    }
+}
+/// Pages images are packed into; batches draw one page at a time.
+/// Pages left empty by `release` are dropped and their index reused.
+pub(crate) struct ImagePages {
+    pub(crate) layout: wgpu::BindGroupLayout,
+    pub(crate) pages: Vec<Option<ImagePage>>,
+}
+
+impl ImagePages {
+    pub(crate) fn new(layout: wgpu::BindGroupLayout) -> Self {
+        Self {
+            layout,
+            pages: vec![],
+        }
+    }
+    /// uploads every level of `chain` into the first shared page with room,
+    /// opening a new page when all are full
+    pub(crate) fn place(&mut self, gfx: &GfxSurface, chain: &MipChain) -> ImageBackend {
+        let area = chain.area;
+        let dedicated = ImagePage::dedicated(area);
+        let mip_levels = ImagePage::mip_levels(area);
+        let found = match dedicated {
+            true => None,
+            false => self
+                .pages
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(index, page)| page.as_mut().map(|page| (index, page)))
+                .filter(|(_, page)| !page.dedicated)
+                .find_map(|(index, page)| page.shelves.allocate(area).map(|slot| (index, slot))),
+        };
+        let (page, slot) = match found {
+            Some(found) => found,
+            None => {
+                let alignment = (1 << (mip_levels - 1)) as f32;
+                let align = |extent: f32| (extent / alignment).ceil() * alignment;
+                let dimensions = match dedicated {
+                    true => Area::new(
+                        align(area.width + TextureAtlas::ATLAS_PADDING),
+                        align(area.height + TextureAtlas::ATLAS_PADDING),
+                    ),
+                    false => Area::new(ImagePage::DIMENSION, ImagePage::DIMENSION),
+                };
+                let mut page = ImagePage::new(gfx, &self.layout, dimensions, dedicated, mip_levels);
+                let slot = page.shelves.allocate(area).expect("page fits image");
+                let index = match self.pages.iter().position(|page| page.is_none()) {
+                    Some(index) => index,
+                    None => {
+                        self.pages.push(None);
+                        self.pages.len() - 1
+                    }
+                };
+                self.pages[index].replace(page);
+                (index, slot)
+            }
+        };
+        let image_page = self.pages[page].as_mut().expect("page");
+        image_page.residents += 1;
+        for (level, data) in chain.levels.iter().enumerate() {
+            let scale = (1 << level) as f32;
+            image_page.texture.write_level::<[u8; 4]>(
+                Position::new(slot.position.x / scale, slot.position.y / scale),
+                data,
+                Area::new(chain.block.0 as f32 / scale, chain.block.1 as f32 / scale),
+                level as u32,
+                gfx,
+            );
+        }
+        // inset half a texel so filtering does not reach into neighbouring images
+        let region = Section::new(
+            (slot.position.x + 0.5, slot.position.y + 0.5),
+            (area.width - 1f32, area.height - 1f32),
+        );
+        let dimensions = AtlasTextureDimensions {
+            dimensions: image_page.shelves.dimensions,
+        };
+        let coordinates = TextureCoordinates::from_section(region, dimensions);
+        let bounds =
+            TextureCoordinates::from_section(Section::new(slot.position, area), dimensions);
+        ImageBackend {
+            page,
+            slot,
+            area,
+            coordinates,
+            bounds,
+            mip_levels,
+        }
+    }
+    /// frees the slot of `backend`, dropping its page once nothing else lives there
+    pub(crate) fn release(&mut self, backend: ImageBackend) {
+        let Some(page) = self.pages[backend.page].as_mut() else {
+            return;
+        };
+        page.shelves.free(backend.slot);
+        page.residents -= 1;
+        if page.residents == 0 {
+            self.pages[backend.page].take();
+        }
+    }
+    pub(crate) fn bytes(&self) -> usize {
+        self.pages.iter().flatten().map(|page| page.bytes()).sum()
+    }
+}
@@ -1,13 +1,21 @@
 use bevy_ecs::prelude::IntoSystemConfigs;
 
+use crate::images::animation::{advance_playback, ImageAnimations, PlaybackFinished};
+use crate::images::decode::{request_decoding, ImageDecoder};
 use crate::images::interface::{
     apply_aspect_animations, area_diff, aspect_ratio_aligned_dimension, extract, fade_diff,
-    icon_color_diff, layer_diff, management, name_diff, pos_diff, set_from_scale, Extraction,
+    fit_diff, focus_diff, frame_diff, icon_color_diff, layer_diff, management, mipmaps_diff,
+    name_diff, pos_diff, region_diff, set_from_scale, track_load_state, Extraction,
 };
 use crate::images::render_group::read_extraction;
 use crate::images::renderer::{
-    apply_animations, load_images, ImageLoaded, ImageOrientations, ImageRenderer,
+    apply_animations, load_images, ImageLoadFailed, ImageLoaded, ImageOrientations, ImageRenderer,
 };
+use crate::images::residency::{
+    count_references, manage_residency, rasterize_vectors, unload_images, ImageMemory,
+    ImageMemoryBudget, ImageReferences, ImageResidency,
+};
+use crate::images::sprite::step_sprites;
 use crate::{AspectRatioAlignedDimension, Attach, ImageFade, ImageSizes, SyncPoint, Visualizer};
 
 pub(crate) struct ImageAttachment;
@@ -29,13 +37,50 @@ impl Attach for ImageAttachment {
             .job
             .container
             .insert_resource(ImageSizes::default());
-        visualizer.add_event::<ImageLoaded>();
         visualizer
             .job
-            .task(Visualizer::TASK_STARTUP)
-            .add_systems((load_images.in_set(SyncPoint::Initialization),));
+            .container
+            .insert_resource(ImageDecoder::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ImageResidency::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ImageReferences::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ImageMemoryBudget::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ImageMemory::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ImageAnimations::default());
+        visualizer.add_event::<ImageLoaded>();
+        visualizer.add_event::<ImageLoadFailed>();
+        visualizer.add_event::<PlaybackFinished>();
+        visualizer.job.task(Visualizer::TASK_STARTUP).add_systems((
+            request_decoding.in_set(SyncPoint::Initialization),
+            load_images
+                .in_set(SyncPoint::Initialization)
+                .after(request_decoding),
+        ));
         visualizer.job.task(Visualizer::TASK_MAIN).add_systems((
-            // load_images.in_set(SyncPoint::Initialization),
+            unload_images
+                .in_set(SyncPoint::Initialization)
+                .before(request_decoding),
+            request_decoding.in_set(SyncPoint::Initialization),
+            load_images
+                .in_set(SyncPoint::Initialization)
+                .after(request_decoding),
+            track_load_state
+                .in_set(SyncPoint::Initialization)
+                .after(load_images),
             apply_animations.in_set(SyncPoint::Animation),
             apply_aspect_animations.in_set(SyncPoint::Animation),
             set_from_scale.in_set(SyncPoint::Reconfigure),
@@ -47,8 +92,24 @@ impl Attach for ImageAttachment {
             layer_diff.in_set(SyncPoint::PushDiff),
             name_diff.in_set(SyncPoint::PushDiff),
             fade_diff.in_set(SyncPoint::PushDiff),
+            fit_diff.in_set(SyncPoint::PushDiff),
+            focus_diff.in_set(SyncPoint::PushDiff),
             extract.in_set(SyncPoint::Finish),
         ));
+        visualizer.job.task(Visualizer::TASK_MAIN).add_systems((
+            advance_playback.in_set(SyncPoint::Animation),
+            frame_diff.in_set(SyncPoint::PushDiff),
+            mipmaps_diff.in_set(SyncPoint::PushDiff),
+            step_sprites.in_set(SyncPoint::Animation),
+            region_diff.in_set(SyncPoint::PushDiff),
+            count_references.in_set(SyncPoint::Finish),
+            rasterize_vectors
+                .in_set(SyncPoint::Finish)
+                .after(count_references),
+            manage_residency
+                .in_set(SyncPoint::Finish)
+                .after(rasterize_vectors),
+        ));
         visualizer
             .job
             .task(Visualizer::TASK_RENDER_MAIN)
@@ -0,0 +1,616 @@
+#[cfg(target_family = "wasm")]
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+#[cfg(not(target_family = "wasm"))]
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+
+use bevy_ecs::prelude::{Commands, Entity, Query, ResMut, Resource};
+#[cfg(target_family = "wasm")]
+use gloo_worker::WorkerBridge;
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+#[cfg(target_family = "wasm")]
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::bundling::ResourceHandle;
+use crate::images::fetch::{self, ImageSource};
+use crate::images::mipmap::MipChain;
+use crate::images::renderer::{ImageData, ImageRequest};
+use crate::images::residency::ImageResidency;
+use crate::images::svg;
+use crate::{Area, NumericalContext};
+
+/// Frames of a decoded `ImageRequest`
+pub(crate) struct DecodedImage {
+    pub(crate) handle: ResourceHandle,
+    generation: u64,
+    pub(crate) result: Result<DecodedFrames, String>,
+}
+/// Mip chains of each frame; stills have one frame and no delays
+pub(crate) struct DecodedFrames {
+    pub(crate) dimensions: (u32, u32),
+    pub(crate) frames: Vec<MipChain>,
+    /// seconds each frame is shown
+    pub(crate) delays: Vec<f32>,
+}
+impl DecodedFrames {
+    /// delays at most this long play at 0.1s, as browsers do
+    const MINIMUM_DELAY: f32 = 0.01;
+    fn still((pixels, dimensions): (Vec<u8>, (u32, u32))) -> Self {
+        Self {
+            dimensions,
+            frames: vec![MipChain::new(pixels, dimensions)],
+            delays: vec![],
+        }
+    }
+    /// animations with more frames fail to decode
+    const MAX_FRAMES: usize = 1024;
+    /// animations whose frames take more RGBA bytes fail to decode
+    const MAX_BYTES: usize = 256 << 20;
+    /// decodes frames until the animation ends or exceeds the frame or byte budget
+    fn animation(frames: image::Frames) -> Result<Self, String> {
+        let mut dimensions = None;
+        let mut delays = vec![];
+        let mut canvases = vec![];
+        for frame in frames {
+            let frame = frame.map_err(|error| error.to_string())?;
+            let dimensions = *dimensions.get_or_insert(frame.buffer().dimensions());
+            if canvases.len() == Self::MAX_FRAMES {
+                return Err(format!("animation exceeds {} frames", Self::MAX_FRAMES));
+            }
+            let bytes = (canvases.len() + 1) * dimensions.0 as usize * dimensions.1 as usize * 4;
+            if bytes > Self::MAX_BYTES {
+                return Err(format!(
+                    "animation exceeds {} bytes of frames",
+                    Self::MAX_BYTES
+                ));
+            }
+            let (numerator, denominator) = frame.delay().numer_denom_ms();
+            let delay = numerator as f32 / denominator.max(1) as f32 / 1000.0;
+            delays.push(match delay <= Self::MINIMUM_DELAY {
+                true => 0.1,
+                false => delay,
+            });
+            let (left, top) = (frame.left(), frame.top());
+            let buffer = frame.into_buffer();
+            if buffer.dimensions() == dimensions && left == 0 && top == 0 {
+                canvases.push(buffer.into_raw());
+                continue;
+            }
+            let mut canvas = image::RgbaImage::new(dimensions.0, dimensions.1);
+            image::imageops::replace(&mut canvas, &buffer, left as i64, top as i64);
+            canvases.push(canvas.into_raw());
+        }
+        let dimensions = dimensions.ok_or_else(|| "no frames".to_string())?;
+        Ok(match canvases.len() {
+            1 => Self::still((canvases.into_iter().next().expect("frame"), dimensions)),
+            _ => Self {
+                dimensions,
+                frames: canvases
+                    .into_iter()
+                    .map(|pixels| MipChain::new(pixels, dimensions))
+                    .collect(),
+                delays,
+            },
+        })
+    }
+}
+/// decodes every frame of GIF, APNG and animated WebP data; `None` for other data
+fn decode_animation(data: &[u8]) -> Option<Result<DecodedFrames, String>> {
+    use image::codecs::{gif::GifDecoder, png::PngDecoder, webp::WebPDecoder};
+    use image::AnimationDecoder;
+    let cursor = || std::io::Cursor::new(data);
+    let frames = match image::guess_format(data).ok()? {
+        image::ImageFormat::Gif => GifDecoder::new(cursor()).map(|decoder| decoder.into_frames()),
+        image::ImageFormat::Png => match PngDecoder::new(cursor()) {
+            Ok(decoder) if decoder.is_apng() => Ok(decoder.apng().into_frames()),
+            Ok(_) => return None,
+            Err(error) => Err(error),
+        },
+        image::ImageFormat::WebP => match WebPDecoder::new(cursor()) {
+            Ok(decoder) if decoder.has_animation() => Ok(decoder.into_frames()),
+            Ok(_) => return None,
+            Err(error) => Err(error),
+        },
+        _ => return None,
+    };
+    Some(
+        frames
+            .map_err(|error| error.to_string())
+            .and_then(DecodedFrames::animation),
+    )
+}
+/// decodes `data` the way it is tagged; SVG data is rasterized at `size`
+fn decode_data(data: &[u8], size: Option<(u32, u32)>) -> Result<DecodedFrames, String> {
+    match svg::is_svg(data) {
+        true => svg::decode(data, size).map(DecodedFrames::still),
+        false => decode_animation(data).unwrap_or_else(|| {
+            image::load_from_memory(data)
+                .map(|image| {
+                    let rgba = image.to_rgba8();
+                    let dimensions = rgba.dimensions();
+                    DecodedFrames::still((rgba.into_raw(), dimensions))
+                })
+                .map_err(|error| error.to_string())
+        }),
+    }
+}
+/// Decode sent to the `DecodeWorker`
+#[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
+#[derive(Serialize, Deserialize)]
+pub(crate) struct DecodeJob {
+    handle: ResourceHandle,
+    generation: u64,
+    data: ImageData,
+    size: Option<(u32, u32)>,
+}
+/// `DecodedImage` as sent back from the `DecodeWorker`
+#[derive(Serialize, Deserialize)]
+pub(crate) struct TransferredImage {
+    handle: ResourceHandle,
+    generation: u64,
+    result: Result<TransferredFrames, String>,
+}
+#[derive(Serialize, Deserialize)]
+struct TransferredFrames {
+    dimensions: (u32, u32),
+    frames: Vec<TransferredChain>,
+    delays: Vec<f32>,
+}
+/// `MipChain` without its area, which follows from the dimensions
+#[derive(Serialize, Deserialize)]
+struct TransferredChain {
+    block: (u32, u32),
+    levels: Vec<Vec<u8>>,
+}
+impl From<DecodedFrames> for TransferredFrames {
+    fn from(decoded: DecodedFrames) -> Self {
+        Self {
+            dimensions: decoded.dimensions,
+            frames: decoded
+                .frames
+                .into_iter()
+                .map(|chain| TransferredChain {
+                    block: chain.block,
+                    levels: chain.levels,
+                })
+                .collect(),
+            delays: decoded.delays,
+        }
+    }
+}
+impl From<TransferredImage> for DecodedImage {
+    fn from(transferred: TransferredImage) -> Self {
+        Self {
+            handle: transferred.handle,
+            generation: transferred.generation,
+            result: transferred.result.map(|frames| DecodedFrames {
+                dimensions: frames.dimensions,
+                frames: frames
+                    .frames
+                    .into_iter()
+                    .map(|chain| MipChain {
+                        area: Area::<NumericalContext>::from(frames.dimensions),
+                        block: chain.block,
+                        levels: chain.levels,
+                    })
+                    .collect(),
+                delays: frames.delays,
+            }),
+        }
+    }
+}
+/// Web worker decoding raster, animated and SVG data, mip chains included,
+/// so none of it blocks the frame on wasm. Registered by `start_image_worker`
+/// in its own worker script.
+#[cfg_attr(not(target_family = "wasm"), allow(dead_code))]
+pub(crate) struct DecodeWorker;
+impl Worker for DecodeWorker {
+    type Message = ();
+    type Input = DecodeJob;
+    type Output = TransferredImage;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, job: Self::Input, id: HandlerId) {
+        let result = decode_data(&job.data, job.size).map(TransferredFrames::from);
+        scope.respond(
+            id,
+            TransferredImage {
+                handle: job.handle,
+                generation: job.generation,
+                result,
+            },
+        );
+    }
+}
+/// registers the image decode worker; call from the worker script passed
+/// to `Runner::with_image_worker`
+pub fn start_image_worker() {
+    #[cfg(target_family = "wasm")]
+    {
+        use gloo_worker::Registrable;
+        console_error_panic_hook::set_once();
+        DecodeWorker::registrar().register();
+    }
+}
+#[cfg(target_family = "wasm")]
+thread_local! {
+    static DECODE_WORKER_PATH: RefCell<Option<String>> = RefCell::new(None);
+    static DECODE_WORKER: RefCell<Option<WorkerBridge<DecodeWorker>>> = RefCell::new(None);
+}
+/// decodes are sent to the worker script at `path` once one is requested
+#[cfg(target_family = "wasm")]
+pub(crate) fn use_decode_worker(path: String) {
+    DECODE_WORKER_PATH.with(|worker_path| worker_path.replace(Some(path)));
+}
+/// Fixed set of threads running jobs off the frame; jobs queue while
+/// every thread is busy
+#[cfg(not(target_family = "wasm"))]
+struct WorkerPool {
+    jobs: mpsc::Sender<Job>,
+}
+#[cfg(not(target_family = "wasm"))]
+type Job = Box<dyn FnOnce() + Send>;
+#[cfg(not(target_family = "wasm"))]
+impl WorkerPool {
+    const MAX_THREADS: usize = 4;
+    fn new() -> Self {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let queue = Arc::new(Mutex::new(queue));
+        let threads = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1)
+            .min(Self::MAX_THREADS);
+        for _ in 0..threads {
+            let queue = queue.clone();
+            std::thread::spawn(move || loop {
+                let job = queue.lock().expect("worker pool").recv();
+                match job {
+                    Ok(job) => job(),
+                    Err(_) => break,
+                }
+            });
+        }
+        Self { jobs }
+    }
+    fn run<F: FnOnce() + Send + 'static>(&self, job: F) {
+        self.jobs.send(Box::new(job)).expect("worker pool");
+    }
+}
+/// Bytes read for an `ImageSource`
+pub(crate) struct FetchedImage {
+    pub(crate) source: ImageSource,
+    pub(crate) result: Result<ImageData, String>,
+}
+/// Decodes `ImageRequest`s off the frame; on a bounded pool of threads
+/// natively and in the `DecodeWorker` on wasm, where data the worker cannot
+/// decode goes to the browser's image decoder (`createImageBitmap`).
+/// SVG data is rasterized at the requested size instead, and every frame
+/// of animated GIF, APNG and WebP data is decoded.
+#[derive(Resource, Default)]
+pub(crate) struct ImageDecoder {
+    finished: Arc<Mutex<Vec<DecodedImage>>>,
+    fetched: Arc<Mutex<Vec<FetchedImage>>>,
+    /// each pool starts with its first job so idle apps spawn no threads
+    #[cfg(not(target_family = "wasm"))]
+    decoders: Option<WorkerPool>,
+    #[cfg(not(target_family = "wasm"))]
+    fetchers: Option<WorkerPool>,
+    /// generation of the latest decode per handle; older results are dropped
+    pending: HashMap<ResourceHandle, u64>,
+    generation: u64,
+    pub(crate) failed: HashSet<ResourceHandle>,
+}
+
+impl ImageDecoder {
+    /// `size` is the raster size for SVG data; raster data keeps its own size
+    pub(crate) fn decode(
+        &mut self,
+        handle: ResourceHandle,
+        data: ImageData,
+        size: Option<(u32, u32)>,
+    ) {
+        self.failed.remove(&handle);
+        self.generation += 1;
+        let generation = self.generation;
+        self.pending.insert(handle, generation);
+        let finished = self.finished.clone();
+        #[cfg(not(target_family = "wasm"))]
+        self.decoders
+            .get_or_insert_with(WorkerPool::new)
+            .run(move || {
+                let result = decode_data(&data, size);
+                finished.lock().expect("image decoder").push(DecodedImage {
+                    handle,
+                    generation,
+                    result,
+                });
+            });
+        #[cfg(target_family = "wasm")]
+        {
+            // everything `decode_data` reads, animations included, decodes
+            // in the worker; the browser only takes formats it can't read
+            let decodable = svg::is_svg(&data)
+                || image::guess_format(&data).is_ok_and(|format| format.reading_enabled());
+            if let Some(worker) = decodable.then(|| self.worker()).flatten() {
+                worker.send(DecodeJob {
+                    handle,
+                    generation,
+                    data,
+                    size,
+                });
+                return;
+            }
+        }
+        #[cfg(target_family = "wasm")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = match svg::is_svg(&data) {
+                true => svg::decode(&data, size).map(DecodedFrames::still),
+                false => match decode_animation(&data) {
+                    Some(result) => result,
+                    None => decode_in_browser(data).await.map(DecodedFrames::still),
+                },
+            };
+            finished.lock().expect("image decoder").push(DecodedImage {
+                handle,
+                generation,
+                result,
+            });
+        });
+    }
+    /// reads `source` off the frame; collected by `fetched`
+    pub(crate) fn fetch(&mut self, source: ImageSource) {
+        let fetched = self.fetched.clone();
+        #[cfg(not(target_family = "wasm"))]
+        self.fetchers
+            .get_or_insert_with(WorkerPool::new)
+            .run(move || {
+                let result = fetch::read(&source);
+                fetched
+                    .lock()
+                    .expect("image fetcher")
+                    .push(FetchedImage { source, result });
+            });
+        #[cfg(target_family = "wasm")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = fetch::fetch(source.clone()).await;
+            fetched
+                .lock()
+                .expect("image fetcher")
+                .push(FetchedImage { source, result });
+        });
+    }
+    /// bridge to the `DecodeWorker`, spawned on first use; `None` without
+    /// a worker script
+    #[cfg(target_family = "wasm")]
+    fn worker(&self) -> Option<WorkerBridge<DecodeWorker>> {
+        use gloo_worker::Spawnable;
+        DECODE_WORKER.with(|worker| {
+            if worker.borrow().is_none() {
+                let path = DECODE_WORKER_PATH.with(|path| path.borrow().clone())?;
+                let finished = self.finished.clone();
+                let bridge = DecodeWorker::spawner()
+                    .callback(move |transferred: TransferredImage| {
+                        finished
+                            .lock()
+                            .expect("image decoder")
+                            .push(DecodedImage::from(transferred));
+                    })
+                    .spawn(path.as_str());
+                worker.replace(Some(bridge));
+            }
+            worker.borrow().clone()
+        })
+    }
+    pub(crate) fn fetched(&mut self) -> Vec<FetchedImage> {
+        self.fetched
+            .lock()
+            .expect("image fetcher")
+            .drain(..)
+            .collect()
+    }
+    /// reports `handle` as failed alongside finished decodes
+    pub(crate) fn fail(&mut self, handle: ResourceHandle, error: String) {
+        self.generation += 1;
+        let generation = self.generation;
+        self.pending.insert(handle, generation);
+        self.finished
+            .lock()
+            .expect("image decoder")
+            .push(DecodedImage {
+                handle,
+                generation,
+                result: Err(error),
+            });
+    }
+    pub(crate) fn decoding(&self, handle: ResourceHandle) -> bool {
+        self.pending.contains_key(&handle)
+    }
+    /// forgets `handle`; a decode still in flight is dropped when it finishes
+    pub(crate) fn cancel(&mut self, handle: ResourceHandle) {
+        self.pending.remove(&handle);
+        self.failed.remove(&handle);
+    }
+    /// decodes completed since the last call
+    pub(crate) fn finished(&mut self) -> Vec<DecodedImage> {
+        let finished = self
+            .finished
+            .lock()
+            .expect("image decoder")
+            .drain(..)
+            .filter(|decoded| self.pending.get(&decoded.handle) == Some(&decoded.generation))
+            .collect::<Vec<DecodedImage>>();
+        for decoded in finished.iter() {
+            self.pending.remove(&decoded.handle);
+        }
+        for decoded in finished.iter().filter(|decoded| decoded.result.is_err()) {
+            self.failed.insert(decoded.handle);
+        }
+        finished
+    }
+}
+#[cfg(target_family = "wasm")]
+async fn decode_in_browser(data: ImageData) -> Result<(Vec<u8>, (u32, u32)), String> {
+    let describe = |error: JsValue| format!("{:?}", error);
+    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(data.as_slice()));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).map_err(describe)?;
+    let promise = window
+        .create_image_bitmap_with_blob(&blob)
+        .map_err(describe)?;
+    let bitmap: web_sys::ImageBitmap = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(describe)?
+        .unchecked_into();
+    let (width, height) = (bitmap.width(), bitmap.height());
+    let canvas = web_sys::OffscreenCanvas::new(width, height).map_err(describe)?;
+    let context: web_sys::OffscreenCanvasRenderingContext2d = canvas
+        .get_context("2d")
+        .map_err(describe)?
+        .ok_or_else(|| "no 2d context".to_string())?
+        .unchecked_into();
+    context
+        .draw_image_with_image_bitmap(&bitmap, 0.0, 0.0)
+        .map_err(describe)?;
+    bitmap.close();
+    let pixels = context
+        .get_image_data(0.0, 0.0, width as f64, height as f64)
+        .map_err(describe)?
+        .data()
+        .0;
+    Ok((pixels, (width, height)))
+}
+/// keeps `data` as the source of `handle` and starts decoding it
+fn ingest(
+    handle: ResourceHandle,
+    data: ImageData,
+    decoder: &mut ImageDecoder,
+    residency: &mut ImageResidency,
+) {
+    match svg::is_svg(&data) {
+        true => residency.vectors.insert(handle),
+        false => residency.vectors.remove(&handle),
+    };
+    residency.rasterized.remove(&handle);
+    residency.by_source.retain(|_, cached| *cached != handle);
+    residency.sources.insert(handle, data.clone());
+    decoder.decode(handle, data, None);
+}
+pub(crate) fn request_decoding(
+    mut requests: Query<(Entity, &mut ImageRequest)>,
+    mut decoder: ResMut<ImageDecoder>,
+    mut residency: ResMut<ImageResidency>,
+    mut cmd: Commands,
+) {
+    for fetched in decoder.fetched() {
+        let waiting = residency
+            .fetching
+            .remove(&fetched.source)
+            .unwrap_or_default();
+        match fetched.result {
+            Ok(data) => {
+                for handle in waiting.iter() {
+                    ingest(*handle, data.clone(), &mut decoder, &mut residency);
+                }
+                if let Some(handle) = waiting.first() {
+                    residency.by_source.insert(fetched.source, *handle);
+                }
+            }
+            Err(error) => {
+                for handle in waiting {
+                    decoder.fail(handle, error.clone());
+                }
+            }
+        }
+    }
+    for (entity, mut request) in requests.iter_mut() {
+        let data = match request.source.take() {
+            Some(source) => {
+                let cached = residency
+                    .by_source
+                    .get(&source)
+                    .and_then(|handle| residency.sources.get(handle))
+                    .cloned();
+                match cached {
+                    Some(data) => data,
+                    None => {
+                        let waiting = residency.fetching.entry(source.clone()).or_default();
+                        if waiting.is_empty() {
+                            decoder.fetch(source);
+                        }
+                        waiting.push(request.handle);
+                        cmd.entity(entity).despawn();
+                        continue;
+                    }
+                }
+            }
+            None => std::mem::take(&mut request.data),
+        };
+        ingest(request.handle, data, &mut decoder, &mut residency);
+        cmd.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn animated_gif() {
+    use image::codecs::gif::GifEncoder;
+    let frames = [[255u8, 0, 0, 255], [0, 0, 255, 255]]
+        .into_iter()
+        .map(|color| {
+            let buffer = image::RgbaImage::from_pixel(2, 2, image::Rgba(color));
+            image::Frame::from_parts(buffer, 0, 0, image::Delay::from_numer_denom_ms(250, 1))
+        });
+    let mut data = vec![];
+    GifEncoder::new(&mut data).encode_frames(frames).unwrap();
+    let decoded = decode_animation(&data).expect("animated").unwrap();
+    assert_eq!(decoded.dimensions, (2, 2));
+    assert_eq!(decoded.delays, vec![0.25, 0.25]);
+    assert_eq!(&decoded.frames[1].levels[0][..4], &[0, 0, 255, 255]);
+    assert!(decode_animation(include_bytes!("../icon/bundled_icons/svg/activity.svg")).is_none());
+    let frames = (0..=DecodedFrames::MAX_FRAMES).map(|_| {
+        let buffer = image::RgbaImage::from_pixel(1, 1, image::Rgba([0, 0, 0, 255]));
+        image::Frame::from_parts(buffer, 0, 0, image::Delay::from_numer_denom_ms(20, 1))
+    });
+    let mut data = vec![];
+    GifEncoder::new(&mut data).encode_frames(frames).unwrap();
+    let exceeded = decode_animation(&data).expect("animated");
+    assert!(exceeded.is_err_and(|error| error.contains("frames")));
+}
+#[cfg(test)]
+#[test]
+fn pooled_decoding() {
+    let mut data = vec![];
+    image::RgbaImage::from_pixel(2, 2, image::Rgba([0, 255, 0, 255]))
+        .write_to(
+            &mut std::io::Cursor::new(&mut data),
+            image::ImageOutputFormat::Png,
+        )
+        .unwrap();
+    let mut decoder = ImageDecoder::default();
+    for handle in 0..16 {
+        decoder.decode(ResourceHandle(handle), data.clone(), None);
+    }
+    let mut decoded = vec![];
+    let start = std::time::Instant::now();
+    while decoded.len() < 16 && start.elapsed().as_secs() < 10 {
+        decoded.extend(decoder.finished());
+        std::thread::yield_now();
+    }
+    assert_eq!(decoded.len(), 16);
+    assert!(decoded.iter().all(|image| image.result.is_ok()));
+    let transferred = TransferredImage {
+        handle: ResourceHandle(0),
+        generation: 0,
+        result: decoded.pop().unwrap().result.map(TransferredFrames::from),
+    };
+    let frames = DecodedImage::from(transferred).result.unwrap();
+    assert_eq!(frames.frames[0].area, Area::from((2, 2)));
+    assert_eq!(&frames.frames[0].levels[0][..4], &[0, 255, 0, 255]);
+}
@@ -0,0 +1,140 @@
+#[cfg(not(target_family = "wasm"))]
+use std::io::Read;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+#[cfg(target_family = "wasm")]
+use wasm_bindgen::{JsCast, JsValue};
+
+use crate::images::renderer::ImageData;
+
+/// Where an `ImageRequest` reads its bytes from when not given them.
+/// Requests for the same source share one read.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub enum ImageSource {
+    /// file read off the frame; native only
+    Path(PathBuf),
+    /// fetched with the browser's `fetch` on web; natively over `http://` or `https://`
+    Url(String),
+}
+/// reads `source` on the calling thread
+#[cfg(not(target_family = "wasm"))]
+pub(crate) fn read(source: &ImageSource) -> Result<ImageData, String> {
+    match source {
+        ImageSource::Path(path) => {
+            std::fs::read(path).map_err(|error| format!("{}: {}", path.display(), error))
+        }
+        ImageSource::Url(url) => http_get(url, MAX_BODY),
+    }
+}
+/// bodies past this many bytes fail instead of being read into memory
+#[cfg(not(target_family = "wasm"))]
+const MAX_BODY: u64 = 64 * 1024 * 1024;
+/// shared so connections are kept alive between images of one host
+#[cfg(not(target_family = "wasm"))]
+fn agent() -> &'static ureq::Agent {
+    static AGENT: std::sync::OnceLock<ureq::Agent> = std::sync::OnceLock::new();
+    AGENT.get_or_init(|| {
+        ureq::AgentBuilder::new()
+            .timeout_connect(std::time::Duration::from_secs(10))
+            .timeout_read(std::time::Duration::from_secs(30))
+            .redirects(5)
+            .build()
+    })
+}
+/// `http://` and `https://` (rustls with the webpki roots) GET of at most `limit` bytes
+#[cfg(not(target_family = "wasm"))]
+fn http_get(url: &str, limit: u64) -> Result<ImageData, String> {
+    let response =
+        agent()
+            .get(url)
+            .set("Accept", "image/*")
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(status, _) => format!("{}: HTTP {}", url, status),
+                ureq::Error::Transport(transport) => format!("{}: {}", url, transport),
+            })?;
+    let length = response
+        .header("content-length")
+        .and_then(|length| length.parse::<u64>().ok());
+    let too_large = || format!("{}: body larger than {} bytes", url, limit);
+    if length.is_some_and(|length| length > limit) {
+        return Err(too_large());
+    }
+    let mut data = Vec::with_capacity(length.unwrap_or_default() as usize);
+    response
+        .into_reader()
+        .take(limit + 1)
+        .read_to_end(&mut data)
+        .map_err(|error| format!("{}: {}", url, error))?;
+    match data.len() as u64 > limit {
+        true => Err(too_large()),
+        false => Ok(data),
+    }
+}
+#[cfg(target_family = "wasm")]
+pub(crate) async fn fetch(source: ImageSource) -> Result<ImageData, String> {
+    let describe = |error: JsValue| format!("{:?}", error);
+    let url = match source {
+        ImageSource::Url(url) => url,
+        ImageSource::Path(path) => {
+            return Err(format!(
+                "{}: file paths are not readable on web",
+                path.display()
+            ))
+        }
+    };
+    let window = web_sys::window().ok_or_else(|| "no window".to_string())?;
+    let response: web_sys::Response =
+        wasm_bindgen_futures::JsFuture::from(window.fetch_with_str(&url))
+            .await
+            .map_err(describe)?
+            .unchecked_into();
+    if !response.ok() {
+        return Err(format!("{}: HTTP {}", url, response.status()));
+    }
+    let buffer = wasm_bindgen_futures::JsFuture::from(response.array_buffer().map_err(describe)?)
+        .await
+        .map_err(describe)?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+#[test]
+fn local_server() {
+    use std::io::Write;
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        // every connection is closed after its response, so none is pooled
+        let responses: [&[u8]; 5] = [
+            b"HTTP/1.1 302 Found\r\nConnection: close\r\nLocation: /icon.png\r\nContent-Length: 0\r\n\r\n",
+            b"HTTP/1.1 200 OK\r\nConnection: close\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nicon\r\n5\r\n-data\r\n0\r\n\r\n",
+            b"HTTP/1.1 404 Not Found\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+            b"HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 9\r\n\r\nicon-data",
+            b"HTTP/1.1 200 OK\r\nConnection: close\r\nTransfer-Encoding: chunked\r\n\r\n9\r\nicon-data\r\n0\r\n\r\n",
+        ];
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut byte = [0u8];
+            while !request.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap() == 1 {
+                request.push(byte[0]);
+            }
+            stream.write_all(response).unwrap();
+        }
+    });
+    let url = |path: &str| ImageSource::Url(format!("http://{}{}", address, path));
+    assert_eq!(read(&url("/moved")).unwrap(), b"icon-data".to_vec());
+    assert!(read(&url("/missing")).unwrap_err().ends_with("HTTP 404"));
+    let bounded = |path: &str| http_get(&format!("http://{}{}", address, path), 4);
+    assert!(bounded("/sized.png")
+        .unwrap_err()
+        .contains("larger than 4 bytes"));
+    assert!(bounded("/streamed.png")
+        .unwrap_err()
+        .contains("larger than 4 bytes"));
+    server.join().unwrap();
+    assert!(read(&ImageSource::Url("ftp://example.com/a.png".into())).is_err());
+    assert!(read(&ImageSource::Path("/nonexistent/image.png".into())).is_err());
+}
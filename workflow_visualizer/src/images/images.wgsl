@@ -39,29 +39,32 @@ fn adjust_coords_of(vertex_index: u32, tex_coords: vec4<f32>) -> vec2<f32> {
 struct VertexInput {
     @builtin(vertex_index) vertex_index: u32,
     @location(0) vertex_position: vec2<f32>,
+    @location(1) placement: vec4<f32>,
+    @location(2) fade_and_layer: vec4<f32>,
+    @location(3) texture_coordinates: vec4<f32>,
+    @location(4) icon_color: vec4<f32>,
+    @location(5) bounds: vec4<f32>,
 };
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) sample_coords: vec2<f32>,
     @location(1) fade: f32,
+    @location(2) icon_color: vec4<f32>,
+    @location(3) bounds: vec4<f32>,
+    @location(4) max_lod: f32,
 };
-@group(3)
-@binding(0)
-var<uniform> fade_and_layer: vec4<f32>;
-@group(3)
-@binding(1)
-var<uniform> texture_coordinates: vec4<f32>;
-@group(3)
-@binding(2)
-var<uniform> placement: vec4<f32>;
 @vertex
 fn vertex_entry(vertex_input: VertexInput) -> VertexOutput {
-    let coordinates = vec4<f32>(placement.xy - viewport_offset.xy + vertex_input.vertex_position.xy * placement.zw, fade_and_layer.g, 1.0);
-    let sample_coordinates = adjust_coords_of(vertex_input.vertex_index, texture_coordinates);
+    let placement = vertex_input.placement;
+    let coordinates = vec4<f32>(placement.xy - viewport_offset.xy + vertex_input.vertex_position.xy * placement.zw, vertex_input.fade_and_layer.g, 1.0);
+    let sample_coordinates = adjust_coords_of(vertex_input.vertex_index, vertex_input.texture_coordinates);
     let output = VertexOutput(
         viewport.view_matrix * coordinates,
         sample_coordinates,
-        fade_and_layer.r,
+        vertex_input.fade_and_layer.r,
+        vertex_input.icon_color,
+        vertex_input.bounds,
+        vertex_input.fade_and_layer.b,
     );
     return output;
 }
@@ -71,13 +74,20 @@ var image_sampler: sampler;
 @group(2)
 @binding(0)
 var image_texture: texture_2d<f32>;
-@group(3)
-@binding(3)
-var<uniform> icon_color: vec4<f32>;
 @fragment
 fn fragment_entry(vertex_output: VertexOutput) -> @location(0) vec4<f32> {
-    let image_data = textureSample(image_texture, image_sampler, vertex_output.sample_coords);
+    // level of detail from the texel footprint of the pixel, kept within the
+    // image's levels and, at that level, within the image's texels
+    let texture_size = vec2<f32>(textureDimensions(image_texture));
+    let texel_coords = vertex_output.sample_coords * texture_size;
+    let footprint = max(length(dpdx(texel_coords)), length(dpdy(texel_coords)));
+    let lod = clamp(log2(footprint), 0.0, vertex_output.max_lod);
+    let half_texel = 0.5 * exp2(ceil(lod)) / texture_size;
+    let bounds = vertex_output.bounds;
+    let sample_coords = clamp(vertex_output.sample_coords, bounds.rg + half_texel, bounds.ba - half_texel);
+    let image_data = textureSampleLevel(image_texture, image_sampler, sample_coords, lod);
     var color = vec4<f32>(image_data.rgba);
+    let icon_color = vertex_output.icon_color;
     if (icon_color.a <= 0.0) {
         let alpha = image_data.a * vertex_output.fade;
         if (alpha <= 0.0) {
@@ -2,17 +2,23 @@ use std::collections::{HashMap, HashSet};
 
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::{
-    Bundle, Changed, Component, Or, Query, RemovedComponents, Res, Resource, Without,
+    Bundle, Changed, Component, DetectChanges, EventReader, Or, Query, Ref, RemovedComponents, Res,
+    Resource, Without,
 };
 use bevy_ecs::query::With;
 use bevy_ecs::system::ResMut;
 
 use crate::bundling::ResourceHandle;
 use crate::icon::{Icon, IconScale, IconTag};
-use crate::images::renderer::{ImageFade, ImageOrientations};
+use crate::images::animation::{ImageFrame, ImagePlayback};
+use crate::images::atlas::ImageBackend;
+use crate::images::decode::ImageDecoder;
+use crate::images::renderer::{
+    ImageFade, ImageLoadFailed, ImageLoaded, ImageOrientations, ImageSizes,
+};
 use crate::{
     Animate, Animation, Area, Color, Disabled, EnableVisibility, InterfaceContext, Interpolation,
-    Layer, Orientation, Position, Section, Tag, Visibility,
+    Layer, NumericalContext, Position, Section, Tag, TextureCoordinates, Visibility,
 };
 
 pub type ImageTag = Tag<Image>;
@@ -26,6 +32,13 @@ pub struct Image {
     cache: Cache,
     difference: Difference,
     color: Color,
+    load_state: ImageLoadState,
+    fit: ImageFit,
+    focus: ImageFocus,
+    playback: ImagePlayback,
+    frame: ImageFrame,
+    mipmaps: ImageMipmaps,
+    region: ImageRegion,
     tag: ImageTag,
 }
 impl Image {
@@ -43,9 +56,223 @@ impl Image {
             cache: Cache::default(),
             difference: Difference::default(),
             color: Icon::INVALID_COLOR,
+            load_state: ImageLoadState::default(),
+            fit: ImageFit::default(),
+            focus: ImageFocus::default(),
+            playback: ImagePlayback::default(),
+            frame: ImageFrame::default(),
+            mipmaps: ImageMipmaps::default(),
+            region: ImageRegion::WHOLE,
             tag: ImageTag::new(),
         }
     }
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+    pub fn with_focus<F: Into<ImageFocus>>(mut self, focus: F) -> Self {
+        self.focus = focus.into();
+        self
+    }
+    pub fn with_playback(mut self, playback: ImagePlayback) -> Self {
+        self.playback = playback;
+        self
+    }
+    pub fn with_mipmaps(mut self, mipmaps: bool) -> Self {
+        self.mipmaps = ImageMipmaps(mipmaps);
+        self
+    }
+    pub fn with_region(mut self, region: ImageRegion) -> Self {
+        self.region = region;
+        self
+    }
+}
+/// Part of an image drawn, in image pixels; a sprite of a sheet for example.
+/// A region without area draws the whole image.
+#[derive(Component, Copy, Clone, PartialEq, Default, Debug)]
+pub struct ImageRegion {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+impl ImageRegion {
+    pub const WHOLE: ImageRegion = ImageRegion {
+        x: 0.0,
+        y: 0.0,
+        w: 0.0,
+        h: 0.0,
+    };
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self { x, y, w, h }
+    }
+    pub fn is_whole(&self) -> bool {
+        self.w <= 0.0 || self.h <= 0.0
+    }
+    /// texture coordinates, sampling bounds and texels of the region, kept
+    /// within the image placed at `backend`
+    pub(crate) fn within(
+        &self,
+        backend: &ImageBackend,
+    ) -> (
+        TextureCoordinates,
+        TextureCoordinates,
+        Area<NumericalContext>,
+    ) {
+        if self.is_whole() {
+            return (backend.coordinates, backend.bounds, backend.area);
+        }
+        let image = backend.area;
+        let x = self.x.clamp(0.0, image.width - 1.0);
+        let y = self.y.clamp(0.0, image.height - 1.0);
+        let area = Area::new(self.w.min(image.width - x), self.h.min(image.height - y));
+        let [u0, v0, u1, v1] = backend.bounds.data;
+        let texel = ((u1 - u0) / image.width, (v1 - v0) / image.height);
+        let bounds = TextureCoordinates::new(
+            u0 + x * texel.0,
+            v0 + y * texel.1,
+            u0 + (x + area.width) * texel.0,
+            v0 + (y + area.height) * texel.1,
+        );
+        // inset half a texel so filtering does not reach into neighbouring regions
+        let [left, top, right, bottom] = bounds.data;
+        let coordinates = TextureCoordinates::new(
+            left + texel.0 * 0.5,
+            top + texel.1 * 0.5,
+            right - texel.0 * 0.5,
+            bottom - texel.1 * 0.5,
+        );
+        (coordinates, bounds, area)
+    }
+}
+/// Whether an image is sampled from smaller mip levels when drawn below its
+/// texel size, keeping downscaled photos from shimmering. Disable for pixel
+/// art that should stay sharp at any scale.
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ImageMipmaps(pub bool);
+impl Default for ImageMipmaps {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+/// How an image is sized within its section.
+/// Cropping (`Cover`, or `None`/`ScaleDown` of an image larger than the section)
+/// keeps the part around the `ImageFocus`.
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ImageFit {
+    /// stretched to the section, ignoring aspect ratio
+    #[default]
+    Fill,
+    /// as large as fits entirely, leaving uncovered space
+    Contain,
+    /// as small as covers the section, cropping the overflow
+    Cover,
+    /// one image pixel per device pixel
+    None,
+    /// `None` or `Contain`, whichever is smaller
+    ScaleDown,
+}
+impl ImageFit {
+    /// placement and texture coordinates drawn for an image of `image` texels
+    /// fitted to `placement` (`[x, y, width, height]` in device pixels)
+    pub(crate) fn fitted(
+        &self,
+        placement: [f32; 4],
+        coordinates: TextureCoordinates,
+        image: Area<NumericalContext>,
+        focus: ImageFocus,
+    ) -> ([f32; 4], TextureCoordinates) {
+        if *self == ImageFit::Fill || image.width <= 0.0 || image.height <= 0.0 {
+            return (placement, coordinates);
+        }
+        let contain = (placement[2] / image.width).min(placement[3] / image.height);
+        let scale = match self {
+            ImageFit::Cover => (placement[2] / image.width).max(placement[3] / image.height),
+            ImageFit::None => 1.0,
+            ImageFit::ScaleDown => contain.min(1.0),
+            _ => contain,
+        };
+        // offset and extent of the drawn quad, and the visible span of the texture
+        let axis = |extent: f32, displayed: f32, focus: f32| match displayed <= extent {
+            true => ((extent - displayed) * focus, displayed, 0.0, 1.0),
+            false => {
+                let visible = extent / displayed;
+                let start = (1.0 - visible) * focus;
+                (0.0, extent, start, start + visible)
+            }
+        };
+        let (x, width, left, right) = axis(placement[2], image.width * scale, focus.x);
+        let (y, height, top, bottom) = axis(placement[3], image.height * scale, focus.y);
+        let [u0, v0, u1, v1] = coordinates.data;
+        (
+            [placement[0] + x, placement[1] + y, width, height],
+            TextureCoordinates::new(
+                u0 + (u1 - u0) * left,
+                v0 + (v1 - v0) * top,
+                u0 + (u1 - u0) * right,
+                v0 + (v1 - v0) * bottom,
+            ),
+        )
+    }
+}
+/// Point of an image, as fractions of its width and height, kept in view when
+/// `ImageFit` crops and toward which uncovered space is distributed.
+#[derive(Component, Copy, Clone, PartialEq, Debug)]
+pub struct ImageFocus {
+    pub x: f32,
+    pub y: f32,
+}
+impl ImageFocus {
+    pub const CENTER: ImageFocus = ImageFocus { x: 0.5, y: 0.5 };
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            x: x.clamp(0.0, 1.0),
+            y: y.clamp(0.0, 1.0),
+        }
+    }
+}
+impl Default for ImageFocus {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+impl From<(f32, f32)> for ImageFocus {
+    fn from(value: (f32, f32)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+/// Whether an `Image`'s handle has been decoded and uploaded.
+/// A placeholder is drawn until it is `Loaded`.
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ImageLoadState {
+    #[default]
+    Loading,
+    Loaded,
+    Failed,
+}
+pub(crate) fn track_load_state(
+    mut images: Query<(Ref<ResourceHandle>, &mut ImageLoadState)>,
+    mut loaded: EventReader<ImageLoaded>,
+    mut failed: EventReader<ImageLoadFailed>,
+    sizes: Res<ImageSizes>,
+    decoder: Res<ImageDecoder>,
+) {
+    let settled = loaded.iter().count() + failed.iter().count() > 0 || sizes.is_changed();
+    for (handle, mut state) in images.iter_mut() {
+        if !settled && !handle.is_changed() {
+            continue;
+        }
+        let current = if sizes.0.contains_key(&handle) {
+            ImageLoadState::Loaded
+        } else if decoder.failed.contains(&handle) {
+            ImageLoadState::Failed
+        } else {
+            ImageLoadState::Loading
+        };
+        if *state != current {
+            *state = current;
+        }
+    }
 }
 #[derive(Component, Copy, Clone)]
 pub struct AspectRatioAlignedDimension {
@@ -88,25 +315,31 @@ pub(crate) fn aspect_ratio_aligned_dimension(
             &ResourceHandle,
             &AspectRatioAlignedDimension,
             &mut Area<InterfaceContext>,
+            Option<&ImageRegion>,
         ),
         Or<(
             Changed<AspectRatioAlignedDimension>,
             Changed<Area<InterfaceContext>>,
             Changed<ResourceHandle>,
+            Changed<ImageLoadState>,
+            Changed<ImageRegion>,
         )>,
     >,
     orientations: Res<ImageOrientations>,
 ) {
-    for (name, max_dim, mut area) in bound.iter_mut() {
-        let orientation = orientations.get(*name);
-        let _dimensions_orientation = Orientation::new(max_dim.dimension.as_numerical());
-        let mut attempted_width = max_dim.dimension.width;
-        let mut attempted_height = attempted_width * orientation.value().reciprocal();
-        while attempted_height > max_dim.dimension.height {
-            attempted_width -= 1f32;
-            attempted_height = attempted_width * orientation.value().reciprocal();
-        }
-        *area = Area::new(attempted_width, attempted_height);
+    for (name, max_dim, mut area, region) in bound.iter_mut() {
+        let Some(orientation) = orientations.0.get(name).copied() else {
+            continue;
+        };
+        let ratio = match region.filter(|region| !region.is_whole()) {
+            Some(region) => region.w / region.h,
+            None => orientation.value().0,
+        };
+        let width = max_dim
+            .dimension
+            .width
+            .min(max_dim.dimension.height * ratio);
+        *area = Area::new(width, width / ratio);
     }
 }
 
@@ -118,6 +351,11 @@ pub(crate) struct Cache {
     pub(crate) area: Option<Area<InterfaceContext>>,
     pub(crate) layer: Option<Layer>,
     pub(crate) icon_color: Option<Color>,
+    pub(crate) fit: Option<ImageFit>,
+    pub(crate) focus: Option<ImageFocus>,
+    pub(crate) frame: Option<usize>,
+    pub(crate) mipmaps: Option<ImageMipmaps>,
+    pub(crate) region: Option<ImageRegion>,
 }
 
 #[derive(Component, Clone, Default)]
@@ -128,6 +366,11 @@ pub(crate) struct Difference {
     pub(crate) area: Option<Area<InterfaceContext>>,
     pub(crate) layer: Option<Layer>,
     pub(crate) icon_color: Option<Color>,
+    pub(crate) fit: Option<ImageFit>,
+    pub(crate) focus: Option<ImageFocus>,
+    pub(crate) frame: Option<usize>,
+    pub(crate) mipmaps: Option<ImageMipmaps>,
+    pub(crate) region: Option<ImageRegion>,
 }
 pub(crate) fn set_from_scale(
     mut image_icons: Query<(&IconScale, &mut Area<InterfaceContext>), Changed<IconScale>>,
@@ -161,6 +404,66 @@ pub(crate) fn name_diff(
         cache.name.replace(*name);
     }
 }
+pub(crate) fn fit_diff(
+    mut images: Query<(&ImageFit, &mut Cache, &mut Difference), Changed<ImageFit>>,
+) {
+    for (fit, mut cache, mut difference) in images.iter_mut() {
+        if let Some(cached) = cache.fit.as_ref() {
+            if *cached != *fit {
+                difference.fit.replace(*fit);
+            }
+        }
+        cache.fit.replace(*fit);
+    }
+}
+pub(crate) fn focus_diff(
+    mut images: Query<(&ImageFocus, &mut Cache, &mut Difference), Changed<ImageFocus>>,
+) {
+    for (focus, mut cache, mut difference) in images.iter_mut() {
+        if let Some(cached) = cache.focus.as_ref() {
+            if *cached != *focus {
+                difference.focus.replace(*focus);
+            }
+        }
+        cache.focus.replace(*focus);
+    }
+}
+pub(crate) fn mipmaps_diff(
+    mut images: Query<(&ImageMipmaps, &mut Cache, &mut Difference), Changed<ImageMipmaps>>,
+) {
+    for (mipmaps, mut cache, mut difference) in images.iter_mut() {
+        if let Some(cached) = cache.mipmaps.as_ref() {
+            if *cached != *mipmaps {
+                difference.mipmaps.replace(*mipmaps);
+            }
+        }
+        cache.mipmaps.replace(*mipmaps);
+    }
+}
+pub(crate) fn region_diff(
+    mut images: Query<(&ImageRegion, &mut Cache, &mut Difference), Changed<ImageRegion>>,
+) {
+    for (region, mut cache, mut difference) in images.iter_mut() {
+        if let Some(cached) = cache.region.as_ref() {
+            if *cached != *region {
+                difference.region.replace(*region);
+            }
+        }
+        cache.region.replace(*region);
+    }
+}
+pub(crate) fn frame_diff(
+    mut images: Query<(&ImageFrame, &mut Cache, &mut Difference), Changed<ImageFrame>>,
+) {
+    for (frame, mut cache, mut difference) in images.iter_mut() {
+        if let Some(cached) = cache.frame.as_ref() {
+            if *cached != frame.index() {
+                difference.frame.replace(frame.index());
+            }
+        }
+        cache.frame.replace(frame.index());
+    }
+}
 pub(crate) fn fade_diff(
     mut images: Query<(&ImageFade, &mut Cache, &mut Difference), Changed<ImageFade>>,
 ) {
@@ -238,6 +541,13 @@ pub(crate) fn management(
             &mut Difference,
             &Color,
             Option<&IconTag>,
+            (
+                &ImageFit,
+                &ImageFocus,
+                &ImageFrame,
+                &ImageMipmaps,
+                &ImageRegion,
+            ),
         ),
         Changed<Visibility>,
     >,
@@ -256,6 +566,7 @@ pub(crate) fn management(
         mut difference,
         icon_color,
         image_icon,
+        (fit, focus, frame, mipmaps, region),
     ) in images.iter_mut()
     {
         if visibility.visible() {
@@ -269,6 +580,16 @@ pub(crate) fn management(
             difference.layer.replace(cache.layer.unwrap());
             difference.fade.replace(cache.fade.unwrap());
             difference.name.replace(cache.name.unwrap());
+            cache.fit.replace(*fit);
+            cache.focus.replace(*focus);
+            difference.fit.replace(*fit);
+            difference.focus.replace(*focus);
+            cache.frame.replace(frame.index());
+            difference.frame.replace(frame.index());
+            cache.mipmaps.replace(*mipmaps);
+            difference.mipmaps.replace(*mipmaps);
+            cache.region.replace(*region);
+            difference.region.replace(*region);
             if image_icon.is_some() {
                 cache.icon_color.replace(*icon_color);
                 difference.icon_color.replace(*icon_color);
@@ -295,3 +616,25 @@ pub(crate) fn extract(
         *diff = Difference::default();
     }
 }
+
+#[cfg(test)]
+#[test]
+fn fitting() {
+    let coordinates = TextureCoordinates::new(0.0, 0.0, 1.0, 1.0);
+    let image = Area::<NumericalContext>::new(200.0, 100.0);
+    let cell = [10.0, 10.0, 100.0, 100.0];
+    let (placement, _) = ImageFit::Contain.fitted(cell, coordinates, image, ImageFocus::CENTER);
+    assert_eq!(placement, [10.0, 35.0, 100.0, 50.0]);
+    let (placement, cropped) = ImageFit::Cover.fitted(cell, coordinates, image, ImageFocus::CENTER);
+    assert_eq!(placement, cell);
+    assert_eq!(cropped.data, [0.25, 0.0, 0.75, 1.0]);
+    let (_, cropped) = ImageFit::Cover.fitted(cell, coordinates, image, ImageFocus::new(0.0, 0.5));
+    assert_eq!(cropped.data, [0.0, 0.0, 0.5, 1.0]);
+    let (placement, _) = ImageFit::ScaleDown.fitted(
+        [0.0, 0.0, 400.0, 400.0],
+        coordinates,
+        image,
+        ImageFocus::new(1.0, 0.0),
+    );
+    assert_eq!(placement, [200.0, 0.0, 200.0, 100.0]);
+}
@@ -0,0 +1,109 @@
+use crate::images::atlas::ImagePage;
+use crate::{Area, NumericalContext};
+
+/// RGBA8 image extended to an aligned block and downsampled once per mip
+/// level of the page it is placed on. Texels past the image repeat its edge
+/// so coarse levels do not blend in whatever lies beside it.
+pub(crate) struct MipChain {
+    /// texels of the image itself
+    pub(crate) area: Area<NumericalContext>,
+    /// extent of level 0; halves exactly at every following level
+    pub(crate) block: (u32, u32),
+    pub(crate) levels: Vec<Vec<u8>>,
+}
+
+impl MipChain {
+    pub(crate) fn new(pixels: Vec<u8>, (width, height): (u32, u32)) -> Self {
+        let area = Area::<NumericalContext>::from((width, height));
+        let level_count = ImagePage::mip_levels(area);
+        let alignment = 1 << (level_count - 1);
+        let align = |extent: u32| extent.div_ceil(alignment) * alignment;
+        let block = (align(width), align(height));
+        let row = width as usize * 4;
+        let mut base = Vec::with_capacity(block.0 as usize * block.1 as usize * 4);
+        for y in 0..block.1 {
+            let start = y.min(height - 1) as usize * row;
+            let source = &pixels[start..start + row];
+            base.extend_from_slice(source);
+            for _ in width..block.0 {
+                base.extend_from_slice(&source[row - 4..]);
+            }
+        }
+        let mut levels = vec![base];
+        let mut extent = block;
+        for _ in 1..level_count {
+            let next = downsample(levels.last().expect("level"), extent);
+            extent = (extent.0 / 2, extent.1 / 2);
+            levels.push(next);
+        }
+        Self {
+            area,
+            block,
+            levels,
+        }
+    }
+}
+fn to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+    match value <= 0.04045 {
+        true => value / 12.92,
+        false => ((value + 0.055) / 1.055).powf(2.4),
+    }
+}
+fn to_srgb(value: f32) -> u8 {
+    let value = match value <= 0.0031308 {
+        true => value * 12.92,
+        false => 1.055 * value.powf(1.0 / 2.4) - 0.055,
+    };
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+/// halves `extent` averaging each 2x2 texel square in linear light, weighted
+/// by alpha so transparent texels do not darken their neighbours
+fn downsample(texels: &[u8], extent: (u32, u32)) -> Vec<u8> {
+    let linear = (0..=255u8).map(to_linear).collect::<Vec<f32>>();
+    let (width, height) = (extent.0 / 2, extent.1 / 2);
+    let mut halved = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let mut color = [0f32; 3];
+            let mut alpha = 0f32;
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                let index = (((y * 2 + dy) * extent.0 + x * 2 + dx) * 4) as usize;
+                let texel = &texels[index..index + 4];
+                let weight = texel[3] as f32 / 255.0;
+                for (channel, value) in color.iter_mut().zip(texel) {
+                    *channel += linear[*value as usize] * weight;
+                }
+                alpha += weight;
+            }
+            for channel in color {
+                halved.push(match alpha > 0.0 {
+                    true => to_srgb(channel / alpha),
+                    false => 0,
+                });
+            }
+            halved.push((alpha / 4.0 * 255.0).round() as u8);
+        }
+    }
+    halved
+}
+
+#[cfg(test)]
+#[test]
+fn mip_chain() {
+    let mut pixels = vec![];
+    for x in 0..3u8 {
+        pixels.extend_from_slice(&[x * 100, 0, 0, 255]);
+    }
+    let chain = MipChain::new(pixels, (3, 1));
+    assert_eq!(chain.block, (16, 16));
+    assert_eq!(chain.levels.len(), ImagePage::SHARED_MIP_LEVELS as usize);
+    assert_eq!(&chain.levels[0][12..16], &[200, 0, 0, 255]);
+    assert_eq!(&chain.levels[0][60..64], &[200, 0, 0, 255]);
+    assert_eq!(chain.levels[4].len(), 4);
+    let blend = downsample(
+        &[255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 0, 0],
+        (2, 2),
+    );
+    assert_eq!(blend, vec![188, 188, 188, 128]);
+}
@@ -1,15 +1,31 @@
+mod animation;
+mod atlas;
 mod attachment;
+pub(crate) mod decode;
+mod fetch;
 mod interface;
+mod mipmap;
 mod render_group;
 mod renderer;
+mod residency;
+mod sprite;
+mod svg;
 pub use crate::bundling::ResourceHandle;
 pub use crate::icon::BundledIcon;
 pub use crate::icon::Icon;
 pub use crate::icon::IconData;
 pub use crate::icon::IconTag;
+pub use animation::{ImageAnimations, ImageFrame, ImagePlayback, PlaybackFinished};
 pub(crate) use attachment::ImageAttachment;
-pub use interface::{AspectRatioAlignedDimension, Image, ImageTag};
+pub use decode::start_image_worker;
+pub use fetch::ImageSource;
+pub use interface::{
+    AspectRatioAlignedDimension, Image, ImageFit, ImageFocus, ImageLoadState, ImageMipmaps,
+    ImageRegion, ImageTag,
+};
 pub(crate) use interface::{Cache, Difference};
 pub use renderer::{
-    ImageData, ImageFade, ImageLoaded, ImageOrientations, ImageRequest, ImageSizes,
+    ImageData, ImageFade, ImageLoadFailed, ImageLoaded, ImageOrientations, ImageRequest, ImageSizes,
 };
+pub use residency::{ImageMemory, ImageMemoryBudget, ImageReferences, ImageUnload};
+pub use sprite::{SpriteAnimation, SpriteSheet};
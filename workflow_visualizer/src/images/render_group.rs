@@ -1,36 +1,71 @@
 use crate::bundling::ResourceHandle;
 use crate::icon::Icon;
-use crate::images::interface::Extraction;
+use crate::images::atlas::ImageBackend;
+use crate::images::interface::{Extraction, ImageFit, ImageFocus, ImageRegion};
 use crate::images::renderer::ImageRenderer;
-use crate::{AlignedUniform, Color, GfxSurface, ScaleFactor, TextureCoordinates, Uniform};
+use crate::{Color, GfxSurface, ScaleFactor, TextureCoordinates};
 #[cfg(target_family = "wasm")]
 use bevy_ecs::prelude::{NonSend, NonSendMut};
 use bevy_ecs::prelude::{Res, ResMut};
 
+/// Per-image attributes of an instanced draw
+#[repr(C)]
+#[derive(bytemuck::Pod, bytemuck::Zeroable, Copy, Clone)]
+pub(crate) struct ImageInstance {
+    pub(crate) placement: [f32; 4],
+    /// fade, layer and the highest mip level sampled
+    pub(crate) fade_layer_and_lod: [f32; 4],
+    pub(crate) texture_coordinates: TextureCoordinates,
+    pub(crate) icon_color: Color,
+    pub(crate) bounds: TextureCoordinates,
+}
 pub(crate) struct ImageRenderGroup {
     pub(crate) image_name: ResourceHandle,
-    pub(crate) render_group_bind_group: wgpu::BindGroup,
-    pub(crate) fade_and_layer: AlignedUniform<f32>,
-    pub(crate) texture_coordinates: Uniform<TextureCoordinates>,
-    pub(crate) placement: AlignedUniform<f32>,
-    pub(crate) icon_color: Uniform<Color>,
+    pub(crate) placement: [f32; 4],
+    pub(crate) fade_and_layer: [f32; 4],
+    pub(crate) icon_color: Color,
+    pub(crate) fit: ImageFit,
+    pub(crate) focus: ImageFocus,
+    pub(crate) frame: usize,
+    pub(crate) mipmaps: bool,
+    pub(crate) region: ImageRegion,
 }
 impl ImageRenderGroup {
-    pub(crate) fn new(
-        name: ResourceHandle,
-        uniforms_bind_group: wgpu::BindGroup,
-        fade_uniform: AlignedUniform<f32>,
-        texture_coordinates: Uniform<TextureCoordinates>,
-        placement: AlignedUniform<f32>,
-        icon_color_uniform: Uniform<Color>,
-    ) -> Self {
+    pub(crate) fn new(name: ResourceHandle) -> Self {
         Self {
             image_name: name,
-            render_group_bind_group: uniforms_bind_group,
-            fade_and_layer: fade_uniform,
-            texture_coordinates,
+            placement: [0.0; 4],
+            fade_and_layer: [0.0; 4],
+            icon_color: Icon::INVALID_COLOR,
+            fit: ImageFit::default(),
+            focus: ImageFocus::default(),
+            frame: 0,
+            mipmaps: true,
+            region: ImageRegion::WHOLE,
+        }
+    }
+    /// `fit` is false for the placeholder, which always fills and has no regions
+    pub(crate) fn instance(&self, backend: &ImageBackend, fit: bool) -> ImageInstance {
+        let (placement, texture_coordinates, bounds) = match fit {
+            true => {
+                let (coordinates, bounds, area) = self.region.within(backend);
+                let (placement, coordinates) =
+                    self.fit
+                        .fitted(self.placement, coordinates, area, self.focus);
+                (placement, coordinates, bounds)
+            }
+            false => (self.placement, backend.coordinates, backend.bounds),
+        };
+        let mut fade_layer_and_lod = self.fade_and_layer;
+        if self.mipmaps {
+            fade_layer_and_lod[2] = (backend.mip_levels - 1) as f32;
+        }
+        ImageInstance {
             placement,
-            icon_color: icon_color_uniform,
+            fade_layer_and_lod,
+            texture_coordinates,
+            icon_color: self.icon_color,
+            bounds,
         }
     }
 }
@@ -43,99 +78,55 @@ pub(crate) fn read_extraction(
     scale_factor: Res<ScaleFactor>,
 ) {
     for entity in extraction.queued_remove.drain() {
-        image_renderer.render_groups.remove(&entity);
+        if image_renderer.render_groups.remove(&entity).is_some() {
+            image_renderer.dirty = true;
+        }
     }
     for (entity, diff) in extraction.differences.drain() {
-        if image_renderer.render_groups.get(&entity).is_none() {
-            let fade_and_layer = AlignedUniform::new(
-                &gfx.device,
-                Some([
-                    diff.fade.expect("fade").0,
-                    diff.layer.expect("layer").z,
-                    0.0,
-                    0.0,
-                ]),
-            );
-            let coordinates_uniform = Uniform::new(
-                &gfx.device,
-                image_renderer
-                    .images
-                    .get(&diff.name.expect("name"))
-                    .expect("images")
-                    .coordinates,
-            );
-            let position = diff.pos.expect("pos");
-            let area = diff.area.expect("area");
-            let placement_uniform = AlignedUniform::new(
-                &gfx.device,
-                Some([position.x, position.y, area.width, area.height]),
-            );
-            let color_uniform =
-                Uniform::new(&gfx.device, diff.icon_color.unwrap_or(Icon::INVALID_COLOR));
-            let bind_group = gfx.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("images-uniform-bind-group"),
-                layout: &image_renderer.render_group_uniforms_layout,
-                entries: &[
-                    fade_and_layer.uniform.bind_group_entry(0),
-                    coordinates_uniform.bind_group_entry(1),
-                    placement_uniform.uniform.bind_group_entry(2),
-                    color_uniform.bind_group_entry(3),
-                ],
-            });
-            let render_group = ImageRenderGroup::new(
-                diff.name.expect("name"),
-                bind_group,
-                fade_and_layer,
-                coordinates_uniform,
-                placement_uniform,
-                color_uniform,
-            );
-            image_renderer.render_groups.insert(entity, render_group);
+        let render_group = image_renderer
+            .render_groups
+            .entry(entity)
+            .or_insert_with(|| ImageRenderGroup::new(diff.name.expect("name")));
+        if let Some(pos) = diff.pos {
+            let pos = pos.to_device(scale_factor.factor());
+            render_group.placement[0] = pos.x;
+            render_group.placement[1] = pos.y;
         }
-        if let Some(mut render_group) = image_renderer.render_groups.remove(&entity) {
-            let mut placement_changed = false;
-            if let Some(pos) = diff.pos {
-                let pos = pos.to_device(scale_factor.factor());
-                render_group.placement.set_aspect(0, pos.x);
-                render_group.placement.set_aspect(1, pos.y);
-                placement_changed = true;
-            }
-            if let Some(area) = diff.area {
-                let area = area.to_device(scale_factor.factor());
-                render_group.placement.set_aspect(2, area.width);
-                render_group.placement.set_aspect(3, area.height);
-                placement_changed = true;
-            }
-            if placement_changed {
-                render_group.placement.update(&gfx.queue);
-            }
-            let mut fade_layer_changed = false;
-            if let Some(fade) = diff.fade {
-                render_group.fade_and_layer.set_aspect(0, fade.0);
-                fade_layer_changed = true;
-            }
-            if let Some(layer) = diff.layer {
-                render_group.fade_and_layer.set_aspect(1, layer.z);
-                fade_layer_changed = true;
-            }
-            if fade_layer_changed {
-                render_group.fade_and_layer.update(&gfx.queue);
-            }
-            if let Some(name) = diff.name {
-                render_group.texture_coordinates.update(
-                    &gfx.queue,
-                    image_renderer
-                        .images
-                        .get(&name)
-                        .expect("images")
-                        .coordinates,
-                );
-                render_group.image_name = name;
-            }
-            if let Some(icon_color) = diff.icon_color {
-                render_group.icon_color.update(&gfx.queue, icon_color);
-            }
-            image_renderer.render_groups.insert(entity, render_group);
+        if let Some(area) = diff.area {
+            let area = area.to_device(scale_factor.factor());
+            render_group.placement[2] = area.width;
+            render_group.placement[3] = area.height;
+        }
+        if let Some(fade) = diff.fade {
+            render_group.fade_and_layer[0] = fade.0;
+        }
+        if let Some(layer) = diff.layer {
+            render_group.fade_and_layer[1] = layer.z;
+        }
+        if let Some(name) = diff.name {
+            render_group.image_name = name;
+        }
+        if let Some(icon_color) = diff.icon_color {
+            render_group.icon_color = icon_color;
         }
+        if let Some(fit) = diff.fit {
+            render_group.fit = fit;
+        }
+        if let Some(focus) = diff.focus {
+            render_group.focus = focus;
+        }
+        if let Some(frame) = diff.frame {
+            render_group.frame = frame;
+        }
+        if let Some(mipmaps) = diff.mipmaps {
+            render_group.mipmaps = mipmaps.0;
+        }
+        if let Some(region) = diff.region {
+            render_group.region = region;
+        }
+        image_renderer.dirty = true;
+    }
+    if image_renderer.dirty {
+        image_renderer.write_instances(&gfx);
     }
 }
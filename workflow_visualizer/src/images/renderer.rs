@@ -1,24 +1,30 @@
 use bevy_ecs::event::EventWriter;
 use std::collections::HashMap;
+use std::ops::Range;
+#[cfg(not(target_family = "wasm"))]
+use std::path::PathBuf;
 
 #[cfg(not(target_family = "wasm"))]
 use bevy_ecs::prelude::Res;
-use bevy_ecs::prelude::{Commands, Component, Entity, Event, Query, ResMut, Resource};
+use bevy_ecs::prelude::{Component, Entity, Event, Query, ResMut, Resource};
 #[cfg(target_family = "wasm")]
 use bevy_ecs::prelude::{NonSend, NonSendMut};
-use image::{EncodableLayout, GenericImageView};
 use serde::{Deserialize, Serialize};
 use wgpu::util::DeviceExt;
 
 use crate::bundling::ResourceHandle;
-use crate::images::render_group::ImageRenderGroup;
+use crate::images::animation::ImageAnimations;
+use crate::images::atlas::{ImageBackend, ImagePages};
+use crate::images::decode::ImageDecoder;
+use crate::images::fetch::ImageSource;
+use crate::images::mipmap::MipChain;
+use crate::images::render_group::{ImageInstance, ImageRenderGroup};
 use crate::orientation::Orientation;
-use crate::texture_atlas::{AtlasLocation, TextureSampler};
+use crate::texture_atlas::TextureSampler;
 use crate::{
-    Animate, Animation, Area, AtlasBlock, AtlasDimension, AtlasTextureDimensions, GfxSurface,
-    GfxSurfaceConfiguration, Interpolation, MsaaRenderAdapter, NumericalContext, RawPosition,
-    Render, RenderPassHandle, RenderPhase, ScaleFactor, TextureAtlas, TextureBindGroup,
-    TextureCoordinates, Viewport, Visualizer,
+    Animate, Animation, Area, GfxSurface, GfxSurfaceConfiguration, Interpolation,
+    MsaaRenderAdapter, NumericalContext, RawPosition, Render, RenderPassHandle, RenderPhase,
+    ScaleFactor, TextureBindGroup, Viewport, Visualizer,
 };
 
 #[repr(C)]
@@ -92,6 +98,9 @@ impl From<u32> for ImageFade {
 pub struct ImageRequest {
     pub handle: ResourceHandle,
     pub data: ImageData,
+    /// read instead of `data` when set
+    #[serde(default)]
+    pub source: Option<ImageSource>,
 }
 pub type ImageData = Vec<u8>;
 impl ImageRequest {
@@ -99,26 +108,23 @@ impl ImageRequest {
         Self {
             handle: handle.into(),
             data: data.into(),
+            source: None,
         }
     }
-}
-
-pub(crate) struct ImageBackend {
-    #[allow(unused)]
-    pub(crate) atlas: TextureAtlas,
-    pub(crate) bind_group: TextureBindGroup,
-    pub(crate) coordinates: TextureCoordinates,
-}
-impl ImageBackend {
-    pub(crate) fn new(
-        atlas: TextureAtlas,
-        bind_group: TextureBindGroup,
-        coordinates: TextureCoordinates,
-    ) -> Self {
+    /// reads the file off the frame; failures send `ImageLoadFailed`
+    #[cfg(not(target_family = "wasm"))]
+    pub fn from_path<IN: Into<ResourceHandle>, P: Into<PathBuf>>(handle: IN, path: P) -> Self {
+        Self::from_source(handle, ImageSource::Path(path.into()))
+    }
+    /// fetches the url off the frame; failures send `ImageLoadFailed`
+    pub fn from_url<IN: Into<ResourceHandle>, U: Into<String>>(handle: IN, url: U) -> Self {
+        Self::from_source(handle, ImageSource::Url(url.into()))
+    }
+    fn from_source<IN: Into<ResourceHandle>>(handle: IN, source: ImageSource) -> Self {
         Self {
-            atlas,
-            bind_group,
-            coordinates,
+            handle: handle.into(),
+            data: vec![],
+            source: Some(source),
         }
     }
 }
@@ -130,8 +136,17 @@ pub(crate) struct ImageRenderer {
     pub(crate) vertex_buffer: wgpu::Buffer,
     pub(crate) sampler_bind_group: wgpu::BindGroup,
     pub(crate) images: HashMap<ResourceHandle, ImageBackend>,
-    pub(crate) render_group_layout: wgpu::BindGroupLayout,
-    pub(crate) render_group_uniforms_layout: wgpu::BindGroupLayout,
+    /// frames after the first of animated images, which is in `images`
+    pub(crate) frames: HashMap<ResourceHandle, Vec<ImageBackend>>,
+    pub(crate) pages: ImagePages,
+    /// drawn for handles that are still loading or failed to load
+    pub(crate) placeholder: ImageBackend,
+    pub(crate) instances: wgpu::Buffer,
+    pub(crate) instance_capacity: usize,
+    /// page and instance range of each draw
+    pub(crate) batches: Vec<(usize, Range<u32>)>,
+    /// render groups or backends changed since the instances were written
+    pub(crate) dirty: bool,
 }
 #[derive(Resource, Default)]
 pub struct ImageOrientations(pub(crate) HashMap<ResourceHandle, Orientation>);
@@ -149,48 +164,141 @@ impl ImageSizes {
 }
 #[derive(Event, Copy, Clone)]
 pub struct ImageLoaded(pub ResourceHandle);
+/// An `ImageRequest` whose data could not be decoded
+#[derive(Event, Clone, Debug)]
+pub struct ImageLoadFailed {
+    pub handle: ResourceHandle,
+    pub error: String,
+}
+impl ImageRenderer {
+    /// translucent grey pixel shown in place of an image that has not loaded
+    pub(crate) const PLACEHOLDER: [u8; 4] = [128, 128, 128, 64];
+    pub(crate) const INITIAL_INSTANCES: usize = 64;
+    /// backend drawn for `handle`; the placeholder while it is loading or failed
+    pub(crate) fn backend(&self, handle: ResourceHandle) -> &ImageBackend {
+        self.images.get(&handle).unwrap_or(&self.placeholder)
+    }
+    /// backend of `frame` of `handle`; the first frame when it is not animated
+    pub(crate) fn frame_backend(&self, handle: ResourceHandle, frame: usize) -> &ImageBackend {
+        match frame.checked_sub(1) {
+            Some(index) => self
+                .frames
+                .get(&handle)
+                .and_then(|frames| frames.get(index))
+                .unwrap_or_else(|| self.backend(handle)),
+            None => self.backend(handle),
+        }
+    }
+    /// texture memory reserved for every frame of `handle`
+    pub(crate) fn image_bytes(&self, handle: ResourceHandle) -> usize {
+        let frames = self.frames.get(&handle).into_iter().flatten();
+        self.images
+            .get(&handle)
+            .into_iter()
+            .chain(frames)
+            .map(|backend| backend.bytes())
+            .sum()
+    }
+    /// releases the texture space of `handle`; entities using it draw the placeholder
+    pub(crate) fn unload(&mut self, handle: ResourceHandle) {
+        if let Some(backend) = self.images.remove(&handle) {
+            self.pages.release(backend);
+            self.dirty = true;
+        }
+        for backend in self.frames.remove(&handle).unwrap_or_default() {
+            self.pages.release(backend);
+        }
+    }
+    fn instance_buffer(gfx: &GfxSurface, capacity: usize) -> wgpu::Buffer {
+        gfx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("images instance buffer"),
+            size: (std::mem::size_of::<ImageInstance>() * capacity) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+    /// rewrites the instances ordered by page so each page is drawn once
+    pub(crate) fn write_instances(&mut self, gfx: &GfxSurface) {
+        let mut ordered = self
+            .render_groups
+            .values()
+            .map(|group| {
+                let backend = self.frame_backend(group.image_name, group.frame);
+                let loaded = self.images.contains_key(&group.image_name);
+                (backend.page, group.instance(backend, loaded))
+            })
+            .collect::<Vec<(usize, ImageInstance)>>();
+        ordered.sort_by_key(|(page, _)| *page);
+        self.batches.clear();
+        for (index, (page, _)) in ordered.iter().enumerate() {
+            let index = index as u32;
+            match self.batches.last_mut() {
+                Some((last, range)) if last == page => range.end = index + 1,
+                _ => self.batches.push((*page, index..index + 1)),
+            }
+        }
+        let instances = ordered
+            .into_iter()
+            .map(|(_, instance)| instance)
+            .collect::<Vec<ImageInstance>>();
+        if instances.len() > self.instance_capacity {
+            self.instance_capacity = instances.len().next_power_of_two();
+            self.instances = Self::instance_buffer(gfx, self.instance_capacity);
+        }
+        gfx.queue
+            .write_buffer(&self.instances, 0, bytemuck::cast_slice(&instances));
+        self.dirty = false;
+    }
+}
+/// uploads images the `ImageDecoder` has finished
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn load_images(
     #[cfg(not(target_family = "wasm"))] mut image_renderer: ResMut<ImageRenderer>,
     #[cfg(target_family = "wasm")] mut image_renderer: NonSendMut<ImageRenderer>,
-    requests: Query<(Entity, &ImageRequest)>,
-    mut cmd: Commands,
+    mut decoder: ResMut<ImageDecoder>,
     #[cfg(not(target_family = "wasm"))] gfx: Res<GfxSurface>,
     #[cfg(target_family = "wasm")] gfx: NonSend<GfxSurface>,
     mut orientations: ResMut<ImageOrientations>,
     mut sizes: ResMut<ImageSizes>,
-    mut event_writer: EventWriter<ImageLoaded>,
+    mut animations: ResMut<ImageAnimations>,
+    mut loaded: EventWriter<ImageLoaded>,
+    mut failed: EventWriter<ImageLoadFailed>,
 ) {
-    for (entity, request) in requests.iter() {
-        let image = image::load_from_memory(request.data.as_slice()).expect("images-load");
-        let texture_data = image.to_rgba8();
-        let dimensions = image.dimensions();
-        sizes.0.insert(request.handle, Area::from(dimensions));
-        let block = AtlasBlock::new((dimensions.0, dimensions.1));
-        let atlas_dimension = AtlasDimension::new(1);
-        let dimensions = AtlasTextureDimensions::new(block, atlas_dimension);
-        let atlas = TextureAtlas::new(
-            &gfx,
-            block,
-            atlas_dimension,
-            wgpu::TextureFormat::Rgba8UnormSrgb,
-        );
-        let coordinates = atlas.write::<[u8; 4]>(
-            AtlasLocation::new(0, 0),
-            texture_data.as_bytes(),
-            block.block,
-            &gfx,
-        );
-        let bind_group =
-            TextureBindGroup::new(&gfx, &image_renderer.render_group_layout, atlas.view());
+    for decoded in decoder.finished() {
+        let decoded_frames = match decoded.result {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                failed.send(ImageLoadFailed {
+                    handle: decoded.handle,
+                    error,
+                });
+                continue;
+            }
+        };
+        let area = Area::<NumericalContext>::from(decoded_frames.dimensions);
+        sizes.0.insert(decoded.handle, area);
         orientations
             .0
-            .insert(request.handle, Orientation::new(dimensions.dimensions));
-        image_renderer.images.insert(
-            request.handle,
-            ImageBackend::new(atlas, bind_group, coordinates),
-        );
-        event_writer.send(ImageLoaded(request.handle));
-        cmd.entity(entity).despawn();
+            .insert(decoded.handle, Orientation::new(area));
+        image_renderer.unload(decoded.handle);
+        let mut backends = decoded_frames
+            .frames
+            .iter()
+            .map(|frame| image_renderer.pages.place(&gfx, frame))
+            .collect::<Vec<ImageBackend>>();
+        let first = backends.remove(0);
+        image_renderer.images.insert(decoded.handle, first);
+        match decoded_frames.delays.is_empty() {
+            true => {
+                animations.0.remove(&decoded.handle);
+            }
+            false => {
+                image_renderer.frames.insert(decoded.handle, backends);
+                animations.0.insert(decoded.handle, decoded_frames.delays);
+            }
+        }
+        image_renderer.dirty = true;
+        loaded.send(ImageLoaded(decoded.handle));
     }
 }
 
@@ -223,60 +331,12 @@ impl Render for ImageRenderer {
                     label: Some("images-render-group-layout"),
                     entries: &[TextureBindGroup::entry(0)],
                 });
-        let render_group_uniforms_layout =
-            gfx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("render-group"),
-                    entries: &[
-                        wgpu::BindGroupLayoutEntry {
-                            binding: 0,
-                            visibility: wgpu::ShaderStages::VERTEX,
-                            ty: wgpu::BindingType::Buffer {
-                                ty: wgpu::BufferBindingType::Uniform,
-                                has_dynamic_offset: false,
-                                min_binding_size: None,
-                            },
-                            count: None,
-                        },
-                        wgpu::BindGroupLayoutEntry {
-                            binding: 1,
-                            visibility: wgpu::ShaderStages::VERTEX,
-                            ty: wgpu::BindingType::Buffer {
-                                ty: wgpu::BufferBindingType::Uniform,
-                                has_dynamic_offset: false,
-                                min_binding_size: None,
-                            },
-                            count: None,
-                        },
-                        wgpu::BindGroupLayoutEntry {
-                            binding: 2,
-                            visibility: wgpu::ShaderStages::VERTEX,
-                            ty: wgpu::BindingType::Buffer {
-                                ty: wgpu::BufferBindingType::Uniform,
-                                has_dynamic_offset: false,
-                                min_binding_size: None,
-                            },
-                            count: None,
-                        },
-                        wgpu::BindGroupLayoutEntry {
-                            binding: 3,
-                            visibility: wgpu::ShaderStages::FRAGMENT,
-                            ty: wgpu::BindingType::Buffer {
-                                ty: wgpu::BufferBindingType::Uniform,
-                                has_dynamic_offset: false,
-                                min_binding_size: None,
-                            },
-                            count: None,
-                        },
-                    ],
-                });
         let pipeline_layout_descriptor = wgpu::PipelineLayoutDescriptor {
             label: Some("images-render-pipeline-layout"),
             bind_group_layouts: &[
                 viewport.bind_group_layout(),
                 &sampler_bind_group_layout,
                 &texture_bind_group_layout,
-                &render_group_uniforms_layout,
             ],
             push_constant_ranges: &[],
         };
@@ -293,11 +353,24 @@ impl Render for ImageRenderer {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: "vertex_entry",
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
-                }],
+                buffers: &[
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Vertex,
+                        attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                    },
+                    wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<ImageInstance>() as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            1 => Float32x4,
+                            2 => Float32x4,
+                            3 => Float32x4,
+                            4 => Float32x4,
+                            5 => Float32x4
+                        ],
+                    },
+                ],
             },
             primitive: gfx.triangle_primitive(),
             depth_stencil: Some(viewport.depth_stencil_state()),
@@ -310,15 +383,21 @@ impl Render for ImageRenderer {
             multiview: None,
         };
         let pipeline = gfx.device.create_render_pipeline(&pipeline_descriptor);
-
+        let mut pages = ImagePages::new(texture_bind_group_layout);
+        let placeholder = pages.place(gfx, &MipChain::new(Self::PLACEHOLDER.to_vec(), (1, 1)));
         ImageRenderer {
             pipeline,
             render_groups: HashMap::new(),
             vertex_buffer: aabb_vertex_buffer(gfx),
             sampler_bind_group,
             images: HashMap::new(),
-            render_group_layout: texture_bind_group_layout,
-            render_group_uniforms_layout,
+            frames: HashMap::new(),
+            pages,
+            placeholder,
+            instances: Self::instance_buffer(gfx, Self::INITIAL_INSTANCES),
+            instance_capacity: Self::INITIAL_INSTANCES,
+            batches: vec![],
+            dirty: false,
         }
     }
 
@@ -337,21 +416,19 @@ impl Render for ImageRenderer {
         render_pass_handle
             .0
             .set_bind_group(1, &self.sampler_bind_group, &[]);
-        for (_, group) in self.render_groups.iter() {
-            render_pass_handle.0.set_bind_group(
-                2,
-                &self
-                    .images
-                    .get(&group.image_name)
-                    .expect("no images")
-                    .bind_group
-                    .bind_group,
-                &[],
-            );
+        render_pass_handle
+            .0
+            .set_vertex_buffer(1, self.instances.slice(..));
+        for (page, instances) in self.batches.iter() {
+            let Some(page) = self.pages.pages[*page].as_ref() else {
+                continue;
+            };
+            render_pass_handle
+                .0
+                .set_bind_group(2, &page.bind_group.bind_group, &[]);
             render_pass_handle
                 .0
-                .set_bind_group(3, &group.render_group_bind_group, &[]);
-            render_pass_handle.0.draw(0..AABB.len() as u32, 0..1);
+                .draw(0..AABB.len() as u32, instances.clone());
         }
     }
 }
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+#[cfg(target_family = "wasm")]
+use bevy_ecs::prelude::NonSendMut;
+use bevy_ecs::prelude::{
+    Commands, Component, DetectChanges, Entity, Query, Ref, RemovedComponents, Res, ResMut,
+    Resource, With,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::bundling::ResourceHandle;
+use crate::images::animation::ImageAnimations;
+use crate::images::decode::ImageDecoder;
+use crate::images::fetch::ImageSource;
+use crate::images::interface::ImageTag;
+use crate::images::renderer::{ImageData, ImageOrientations, ImageRenderer, ImageSizes};
+use crate::{Area, GfxOptions, InterfaceContext, Record, ScaleFactor};
+
+/// Request to forget an image: its texture, size, orientation and source data.
+/// Entities still using the handle draw the loading placeholder.
+#[derive(Component, Copy, Clone, Serialize, Deserialize, Debug)]
+pub struct ImageUnload(pub ResourceHandle);
+/// Number of `Image`/`Icon` entities using each handle
+#[derive(Resource, Default)]
+pub struct ImageReferences(pub(crate) HashMap<ResourceHandle, usize>);
+impl ImageReferences {
+    pub fn count<IN: Into<ResourceHandle>>(&self, handle: IN) -> usize {
+        self.0.get(&handle.into()).copied().unwrap_or_default()
+    }
+}
+/// Bytes of image texture space to keep resident. Beyond it, images no entity
+/// references are evicted least recently used first; they are decoded again
+/// from their source when referenced. `None` (the default) never evicts.
+#[derive(Resource, Copy, Clone, Default, Debug)]
+pub struct ImageMemoryBudget(pub Option<usize>);
+/// Readout of the texture memory held by images
+#[derive(Resource, Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct ImageMemory {
+    /// bytes of every allocated texture page
+    pub texture_bytes: usize,
+    /// bytes reserved by resident images; what `ImageMemoryBudget` limits
+    pub image_bytes: usize,
+    pub resident: usize,
+    pub pages: usize,
+}
+impl Record for ImageMemory {
+    fn record(&self, core_record: String) -> String {
+        format!(
+            "{} @images.texture_bytes:{:?} @images.image_bytes:{:?} @images.resident:{:?} @images.pages:{:?}",
+            core_record, self.texture_bytes, self.image_bytes, self.resident, self.pages
+        )
+    }
+}
+/// Encoded sources to reload evicted images from, and when each was last used
+#[derive(Resource, Default)]
+pub(crate) struct ImageResidency {
+    pub(crate) sources: HashMap<ResourceHandle, ImageData>,
+    /// handles whose source is SVG
+    pub(crate) vectors: HashSet<ResourceHandle>,
+    /// device size each vector was last rasterized at for display
+    pub(crate) rasterized: HashMap<ResourceHandle, (u32, u32)>,
+    /// handle holding the bytes read for each source
+    pub(crate) by_source: HashMap<ImageSource, ResourceHandle>,
+    /// handles waiting on each source being read
+    pub(crate) fetching: HashMap<ImageSource, Vec<ResourceHandle>>,
+    last_used: HashMap<ResourceHandle, u64>,
+    frame: u64,
+}
+pub(crate) fn count_references(
+    handles: Query<Ref<ResourceHandle>, With<ImageTag>>,
+    mut removed: RemovedComponents<ImageTag>,
+    mut references: ResMut<ImageReferences>,
+) {
+    let removals = removed.iter().count() > 0;
+    if !removals && !handles.iter().any(|handle| handle.is_changed()) {
+        return;
+    }
+    references.0.clear();
+    for handle in handles.iter() {
+        *references.0.entry(*handle).or_default() += 1;
+    }
+}
+/// smallest side vectors are rasterized at; each larger step is `RASTER_STEP` times it
+const SMALLEST_RASTER: f32 = 16.0;
+const RASTER_STEP: f32 = 1.25;
+/// raster size covering `size`: its larger side rounds up to the next step
+/// and the other keeps the aspect ratio, so resizing only rasterizes again
+/// when a step is crossed
+fn raster_step((width, height): (u32, u32), limit: u32) -> (u32, u32) {
+    let larger = width.max(height) as f32;
+    let mut step = SMALLEST_RASTER;
+    while step < larger {
+        step *= RASTER_STEP;
+    }
+    let step = step.ceil();
+    let scaled = |extent: u32| match extent as f32 == larger {
+        true => (step as u32).min(limit),
+        false => ((extent as f32 * step / larger).ceil() as u32).clamp(1, limit),
+    };
+    (scaled(width), scaled(height))
+}
+/// re-rasterizes SVG images at the step covering the largest device size they
+/// are displayed at, following changes of their areas (e.g. `IconScale`) and
+/// the `ScaleFactor`
+pub(crate) fn rasterize_vectors(
+    images: Query<(Ref<ResourceHandle>, Ref<Area<InterfaceContext>>), With<ImageTag>>,
+    scale_factor: Res<ScaleFactor>,
+    references: Res<ImageReferences>,
+    mut residency: ResMut<ImageResidency>,
+    mut decoder: ResMut<ImageDecoder>,
+) {
+    if residency.vectors.is_empty() {
+        return;
+    }
+    let unrasterized = residency
+        .vectors
+        .iter()
+        .any(|handle| references.count(*handle) > 0 && !residency.rasterized.contains_key(handle));
+    let changed = scale_factor.is_changed()
+        || images
+            .iter()
+            .any(|(handle, area)| handle.is_changed() || area.is_changed());
+    if !unrasterized && !changed {
+        return;
+    }
+    let limit = GfxOptions::limited_environment()
+        .limits
+        .max_texture_dimension_2d
+        - 1;
+    let mut sizes = HashMap::<ResourceHandle, (u32, u32)>::new();
+    for (handle, area) in images.iter() {
+        if !residency.vectors.contains(&handle) {
+            continue;
+        }
+        let area = area.to_device(scale_factor.factor());
+        let size = (
+            (area.width.ceil() as u32).min(limit),
+            (area.height.ceil() as u32).min(limit),
+        );
+        if size.0 == 0 || size.1 == 0 {
+            continue;
+        }
+        let largest = sizes.entry(*handle).or_insert(size);
+        *largest = (largest.0.max(size.0), largest.1.max(size.1));
+    }
+    for (handle, size) in sizes {
+        let size = raster_step(size, limit);
+        if residency.rasterized.get(&handle) == Some(&size) {
+            continue;
+        }
+        residency.rasterized.insert(handle, size);
+        if let Some(source) = residency.sources.get(&handle) {
+            decoder.decode(handle, source.clone(), Some(size));
+        }
+    }
+}
+fn forget(
+    handle: ResourceHandle,
+    sizes: &mut ImageSizes,
+    orientations: &mut ImageOrientations,
+    animations: &mut ImageAnimations,
+) {
+    sizes.0.remove(&handle);
+    orientations.0.remove(&handle);
+    animations.0.remove(&handle);
+}
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn unload_images(
+    unloads: Query<(Entity, &ImageUnload)>,
+    #[cfg(not(target_family = "wasm"))] mut image_renderer: ResMut<ImageRenderer>,
+    #[cfg(target_family = "wasm")] mut image_renderer: NonSendMut<ImageRenderer>,
+    mut decoder: ResMut<ImageDecoder>,
+    mut residency: ResMut<ImageResidency>,
+    mut sizes: ResMut<ImageSizes>,
+    mut orientations: ResMut<ImageOrientations>,
+    mut animations: ResMut<ImageAnimations>,
+    mut cmd: Commands,
+) {
+    for (entity, unload) in unloads.iter() {
+        image_renderer.unload(unload.0);
+        decoder.cancel(unload.0);
+        residency.sources.remove(&unload.0);
+        residency.vectors.remove(&unload.0);
+        residency.rasterized.remove(&unload.0);
+        residency.last_used.remove(&unload.0);
+        residency.by_source.retain(|_, handle| *handle != unload.0);
+        for waiting in residency.fetching.values_mut() {
+            waiting.retain(|handle| *handle != unload.0);
+        }
+        forget(unload.0, &mut sizes, &mut orientations, &mut animations);
+        cmd.entity(entity).despawn();
+    }
+}
+/// reloads referenced images that were evicted, then evicts unreferenced
+/// images least recently used first while over the budget
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn manage_residency(
+    #[cfg(not(target_family = "wasm"))] mut image_renderer: ResMut<ImageRenderer>,
+    #[cfg(target_family = "wasm")] mut image_renderer: NonSendMut<ImageRenderer>,
+    mut decoder: ResMut<ImageDecoder>,
+    mut residency: ResMut<ImageResidency>,
+    references: Res<ImageReferences>,
+    budget: Res<ImageMemoryBudget>,
+    mut memory: ResMut<ImageMemory>,
+    mut sizes: ResMut<ImageSizes>,
+    mut orientations: ResMut<ImageOrientations>,
+    mut animations: ResMut<ImageAnimations>,
+) {
+    residency.frame += 1;
+    let frame = residency.frame;
+    for (handle, _) in references.0.iter().filter(|(_, count)| **count > 0) {
+        residency.last_used.insert(*handle, frame);
+        let resident = image_renderer.images.contains_key(handle);
+        if resident || decoder.decoding(*handle) || decoder.failed.contains(handle) {
+            continue;
+        }
+        if let Some(source) = residency.sources.get(handle) {
+            let size = residency.rasterized.get(handle).copied();
+            decoder.decode(*handle, source.clone(), size);
+        }
+    }
+    let image_bytes = |renderer: &ImageRenderer| {
+        renderer
+            .images
+            .keys()
+            .map(|handle| renderer.image_bytes(*handle))
+            .sum::<usize>()
+    };
+    if let Some(budget) = budget.0 {
+        let mut used = image_bytes(&image_renderer);
+        if used > budget {
+            let mut evictable = image_renderer
+                .images
+                .keys()
+                .filter(|handle| references.count(**handle) == 0)
+                .filter(|handle| residency.sources.contains_key(handle))
+                .map(|handle| {
+                    let last_used = residency.last_used.get(handle).copied();
+                    (*handle, last_used.unwrap_or_default())
+                })
+                .collect::<Vec<(ResourceHandle, u64)>>();
+            evictable.sort_by_key(|(_, last_used)| *last_used);
+            for (handle, _) in evictable {
+                if used <= budget {
+                    break;
+                }
+                used -= image_renderer.image_bytes(handle);
+                image_renderer.unload(handle);
+                forget(handle, &mut sizes, &mut orientations, &mut animations);
+            }
+        }
+    }
+    let readout = ImageMemory {
+        texture_bytes: image_renderer.pages.bytes(),
+        image_bytes: image_bytes(&image_renderer),
+        resident: image_renderer.images.len(),
+        pages: image_renderer.pages.pages.iter().flatten().count(),
+    };
+    if *memory != readout {
+        *memory = readout;
+    }
+}
+#[cfg(test)]
+#[test]
+fn raster_steps() {
+    assert_eq!(raster_step((10, 5), 8191), (16, 8));
+    assert_eq!(raster_step((16, 16), 8191), (16, 16));
+    assert_eq!(raster_step((17, 17), 8191), (20, 20));
+    // sizes within a step share its raster
+    let step = raster_step((100, 50), 8191);
+    assert!((96..=100).all(|width| raster_step((width, width / 2), 8191).0 == step.0));
+    assert_eq!(step, (120, 60));
+    assert_eq!(raster_step((9000, 10), 8191).0, 8191);
+}
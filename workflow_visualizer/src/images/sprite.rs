@@ -0,0 +1,186 @@
+use std::ops::Range;
+
+use bevy_ecs::prelude::{Component, Entity, EventWriter, Query, Res};
+
+use crate::bundling::ResourceHandle;
+use crate::images::animation::PlaybackFinished;
+use crate::images::interface::ImageRegion;
+use crate::{TimeDelta, TimeTracker, Timer};
+
+/// Grid of equally sized frames within one image, numbered row by row
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SpriteSheet {
+    /// pixels of each frame
+    pub frame_width: f32,
+    pub frame_height: f32,
+    pub columns: u32,
+    pub rows: u32,
+    /// pixels between neighbouring frames
+    pub spacing: f32,
+    /// pixels before the first row and column
+    pub margin: f32,
+}
+impl SpriteSheet {
+    pub fn new(frame_width: f32, frame_height: f32, columns: u32, rows: u32) -> Self {
+        Self {
+            frame_width,
+            frame_height,
+            columns,
+            rows,
+            spacing: 0.0,
+            margin: 0.0,
+        }
+    }
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+    pub fn frames(&self) -> u32 {
+        self.columns * self.rows
+    }
+    /// region of frame `index`; the last frame past the end
+    pub fn region(&self, index: u32) -> ImageRegion {
+        let index = index.min(self.frames().max(1) - 1);
+        let (column, row) = (index % self.columns.max(1), index / self.columns.max(1));
+        ImageRegion::new(
+            self.margin + column as f32 * (self.frame_width + self.spacing),
+            self.margin + row as f32 * (self.frame_height + self.spacing),
+            self.frame_width,
+            self.frame_height,
+        )
+    }
+}
+/// Steps the `ImageRegion` of an `Image` through frames of a `SpriteSheet`,
+/// showing each for the interval of its `Timer`
+#[derive(Component, Clone, Debug)]
+pub struct SpriteAnimation {
+    pub sheet: SpriteSheet,
+    /// frames played, in order
+    pub frames: Range<u32>,
+    /// restart after the last frame instead of holding it
+    pub looping: bool,
+    pub paused: bool,
+    timer: Timer,
+    current: u32,
+    finished: bool,
+}
+impl SpriteAnimation {
+    /// loops every frame of `sheet`, showing each for `frame_time` seconds
+    pub fn new<TD: Into<TimeDelta>>(sheet: SpriteSheet, frame_time: TD) -> Self {
+        Self {
+            sheet,
+            frames: 0..sheet.frames(),
+            looping: true,
+            paused: false,
+            timer: Timer::new(frame_time),
+            current: 0,
+            finished: false,
+        }
+    }
+    pub fn with_frames(mut self, frames: Range<u32>) -> Self {
+        self.frames = frames;
+        self
+    }
+    /// plays once and holds the last frame
+    pub fn once(mut self) -> Self {
+        self.looping = false;
+        self
+    }
+    /// index into `frames` of the frame shown
+    pub fn frame(&self) -> u32 {
+        self.current
+    }
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+    /// back to the first frame, replaying a finished animation
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.finished = false;
+        self.timer.reset();
+    }
+    pub(crate) fn region(&self) -> ImageRegion {
+        self.sheet.region(self.frames.start + self.current)
+    }
+    /// moves forward `steps` frames; returns true when a non-looping
+    /// animation reaches its last frame
+    pub(crate) fn step(&mut self, steps: u32) -> bool {
+        let count = self.frames.len() as u32;
+        if count == 0 || self.finished {
+            return false;
+        }
+        let next = self.current + steps;
+        if self.looping {
+            self.current = next % count;
+            return false;
+        }
+        self.current = next.min(count - 1);
+        self.finished = next >= count - 1;
+        self.finished
+    }
+}
+pub(crate) fn step_sprites(
+    mut sprites: Query<(
+        Entity,
+        &ResourceHandle,
+        &mut SpriteAnimation,
+        &mut ImageRegion,
+    )>,
+    time_tracker: Res<TimeTracker>,
+    mut finished: EventWriter<PlaybackFinished>,
+) {
+    for (entity, handle, mut animation, mut region) in sprites.iter_mut() {
+        if animation.paused || animation.finished {
+            if animation.timer.started() {
+                animation.timer.reset();
+            }
+        } else if animation.timer.not_started() {
+            animation.timer.start(time_tracker.mark());
+        } else {
+            animation.timer.mark(time_tracker.mark());
+            let interval = animation.timer.interval.0;
+            let elapsed = animation.timer.time_elapsed().unwrap_or_default().0;
+            let steps = match interval > 0.0 {
+                true => (elapsed / interval).floor() as u32,
+                false => 1,
+            };
+            if steps > 0 {
+                let start = animation.timer.start.expect("started");
+                animation.timer.start(start.offset(interval * steps as f64));
+                if animation.step(steps) {
+                    finished.send(PlaybackFinished {
+                        entity,
+                        handle: *handle,
+                    });
+                }
+            }
+        }
+        let current = animation.region();
+        if *region != current {
+            *region = current;
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn sprite_sheet() {
+    let sheet = SpriteSheet::new(16.0, 16.0, 4, 2)
+        .with_spacing(2.0)
+        .with_margin(1.0);
+    assert_eq!(sheet.region(5), ImageRegion::new(19.0, 19.0, 16.0, 16.0));
+    assert_eq!(sheet.region(20), sheet.region(7));
+    let mut animation = SpriteAnimation::new(sheet, 0.1).with_frames(2..6);
+    assert_eq!(animation.region(), sheet.region(2));
+    animation.step(5);
+    assert_eq!(animation.frame(), 1);
+    let mut once = animation.clone().once();
+    assert!(!once.step(1));
+    assert!(once.step(3));
+    assert_eq!(once.region(), sheet.region(5));
+    assert!(!once.step(1));
+}
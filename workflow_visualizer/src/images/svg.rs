@@ -0,0 +1,98 @@
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+
+/// gives `currentColor` white so icon colors tint it like raster icons
+const CURRENT_COLOR: &str = "svg { color: white }";
+
+/// whether `data` looks like an SVG document rather than raster bytes
+pub(crate) fn is_svg(data: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&data[..data.len().min(1024)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<svg")
+        || ((head.starts_with("<?xml") || head.starts_with("<!")) && head.contains("<svg"))
+}
+/// rasterizes SVG `data` at `size`, or at its intrinsic size, to RGBA8 pixels.
+/// The document is drawn centered and scaled to fit `size`. Parsing and
+/// rendering are `usvg`/`resvg`'s; text and embedded raster images are not
+/// drawn, as neither fonts nor raster decoders are given to them.
+pub(crate) fn decode(
+    data: &[u8],
+    size: Option<(u32, u32)>,
+) -> Result<(Vec<u8>, (u32, u32)), String> {
+    let options = Options {
+        style_sheet: Some(CURRENT_COLOR.to_string()),
+        ..Options::default()
+    };
+    let tree = Tree::from_data(data, &options).map_err(|error| error.to_string())?;
+    let intrinsic = tree.size();
+    let size = size.unwrap_or_else(|| {
+        let size = intrinsic.to_int_size();
+        (size.width(), size.height())
+    });
+    let mut pixmap = Pixmap::new(size.0, size.1).ok_or_else(|| "empty raster".to_string())?;
+    let scale = (size.0 as f32 / intrinsic.width()).min(size.1 as f32 / intrinsic.height());
+    let transform = Transform::from_translate(
+        (size.0 as f32 - intrinsic.width() * scale) / 2.0,
+        (size.1 as f32 - intrinsic.height() * scale) / 2.0,
+    )
+    .pre_scale(scale, scale);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Ok((pixels, size))
+}
+
+#[cfg(test)]
+#[test]
+fn rasterization() {
+    let source = br#"<?xml version="1.0"?>
+<!-- feather home -->
+<svg xmlns="http://www.w3.org/2000/svg" width="48" height="48" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2"><path d="M3 9l9-7 9 7v11a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2z"></path><circle cx="12" cy="12" r="3"/></svg>"#;
+    assert!(is_svg(source));
+    assert!(!is_svg(b"\x89PNG"));
+    let (pixels, size) = decode(source, None).unwrap();
+    assert_eq!(size, (48, 48));
+    assert_eq!(pixels.len(), 48 * 48 * 4);
+    let (pixels, size) = decode(source, Some((24, 24))).unwrap();
+    assert_eq!(size, (24, 24));
+    let pixel = |x: usize, y: usize| &pixels[(y * 24 + x) * 4..(y * 24 + x) * 4 + 4];
+    // stroked left wall in white, unfilled interior
+    assert!(pixel(3, 14)[3] > 0);
+    assert_eq!(&pixel(3, 14)[..3], &[255, 255, 255]);
+    assert_eq!(pixel(6, 17)[3], 0);
+}
+
+#[cfg(test)]
+#[test]
+fn painting() {
+    let source = br##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="60" height="10">
+<style>.blue { fill: blue }</style>
+<defs>
+<linearGradient id="fade"><stop offset="0" stop-color="red"/><stop offset="1" stop-color="red"/></linearGradient>
+<rect id="cell" width="10" height="10"/>
+</defs>
+<rect x="0" width="10" height="10" fill="teal"/>
+<rect x="10" width="10" height="10" fill="url(#fade)"/>
+<rect x="20" width="10" height="10" fill="rgba(0, 255, 0, 0.5)"/>
+<rect x="30" width="10" height="10" fill="#00ff0080"/>
+<rect x="40" width="10" height="10" class="blue" opacity="0.5"/>
+<use xlink:href="#cell" x="50" fill="yellow"/>
+</svg>"##;
+    let (pixels, _) = decode(source, None).unwrap();
+    let pixel = |x: usize| &pixels[(5 * 60 + x) * 4..(5 * 60 + x) * 4 + 4];
+    assert_eq!(pixel(5), &[0, 128, 128, 255]);
+    assert_eq!(pixel(15), &[255, 0, 0, 255]);
+    assert_eq!(pixel(25)[1], 255);
+    assert!((127..=128).contains(&pixel(25)[3]));
+    assert_eq!(pixel(35)[1], 255);
+    assert!((127..=128).contains(&pixel(35)[3]));
+    assert_eq!(pixel(45)[2], 255);
+    assert!((127..=128).contains(&pixel(45)[3]));
+    assert_eq!(pixel(55), &[255, 255, 0, 255]);
+}
@@ -0,0 +1,34 @@
+use crate::{Attach, Visualizer};
+use bevy_ecs::prelude::{Event, Resource};
+use winit::event::ElementState;
+use winit::keyboard::{Key, ModifiersState};
+
+/// Logical key press/release forwarded from the window
+#[derive(Event, Clone, Debug)]
+pub struct KeyboardInput {
+    pub key: Key,
+    pub state: ElementState,
+    pub repeat: bool,
+    pub modifiers: ModifiersState,
+}
+
+impl KeyboardInput {
+    pub fn pressed(&self, key: Key) -> bool {
+        self.state == ElementState::Pressed && !self.repeat && self.key == key
+    }
+}
+
+/// Currently held modifier keys
+#[derive(Resource, Copy, Clone, Default, Debug)]
+pub struct KeyboardModifiers(pub ModifiersState);
+
+pub(crate) struct KeyboardAttachment;
+impl Attach for KeyboardAttachment {
+    fn attach(visualizer: &mut Visualizer) {
+        visualizer.add_event::<KeyboardInput>();
+        visualizer
+            .job
+            .container
+            .insert_resource(KeyboardModifiers::default());
+    }
+}
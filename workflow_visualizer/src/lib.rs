@@ -15,8 +15,11 @@ pub use button::{
 };
 pub use icon::{BundledIcon, Icon, IconRequest, IconScale, IconTag};
 pub use images::{
-    AspectRatioAlignedDimension, Image, ImageData, ImageFade, ImageLoaded, ImageOrientations,
-    ImageRequest, ImageSizes, ImageTag,
+    start_image_worker, AspectRatioAlignedDimension, Image, ImageAnimations, ImageData, ImageFade,
+    ImageFit, ImageFocus, ImageFrame, ImageLoadFailed, ImageLoadState, ImageLoaded, ImageMemory,
+    ImageMemoryBudget, ImageMipmaps, ImageOrientations, ImagePlayback, ImageReferences,
+    ImageRegion, ImageRequest, ImageSizes, ImageSource, ImageTag, ImageUnload, PlaybackFinished,
+    SpriteAnimation, SpriteSheet,
 };
 pub use instance::{
     AttributeWrite, CpuAttributeBuffer, GpuAttributeBuffer, Index, Indexer,
@@ -34,8 +37,8 @@ pub use scene::Scene;
 pub use snap_grid::{
     FloatArrangement, FloatLocation, FloatPlacementDescriptor, FloatPlacer, FloatRange, FloatView,
     GridBias, GridDirection, GridLocation, GridMarker, GridPoint, GridRange, GridUnit, GridView,
-    ResponsiveGridLocation, ResponsiveGridPoint, ResponsiveGridRange, ResponsiveGridView,
-    ResponsiveUnit,
+    IntrinsicArea, ResponsiveGridLocation, ResponsiveGridPoint, ResponsiveGridRange,
+    ResponsiveGridView, ResponsiveUnit, Stack, StackAlignment, StackItem, StackLayout,
 };
 pub use viewport::{Viewport, ViewportHandle};
 pub use visualizer::{Attach, Attachment, Visualizer};
@@ -55,28 +58,38 @@ pub use crate::focus::{Focus, FocusInputListener};
 pub use crate::gfx::{GfxOptions, GfxSurface};
 pub use crate::gfx::{GfxSurfaceConfiguration, MsaaRenderAdapter};
 pub use crate::job::Job;
+pub use crate::keyboard::{KeyboardInput, KeyboardModifiers};
 pub use crate::layer_compositor::{LayerArrangement, LayerCompositor};
 pub use crate::line::{Line, LineRender, LineTag};
+pub use crate::localization::{Catalog, CatalogError, Locale, LocalizedText, Translations};
 pub use crate::media::Media;
 pub use crate::panel::{BorderColor, Panel, PanelContentArea, PanelTag, PanelType};
 pub use crate::path::{Path, PathView};
 pub use crate::scale_factor::{ScaleFactor, WindowAppearanceFactor};
-pub use crate::snap_grid::{Column, Row, SnapGrid};
+pub use crate::snap_grid::{
+    solve_constraints, Breakpoint, BreakpointDescriptor, Column, ConstraintAnchor,
+    ConstraintAttribute, ConstraintDiagnostic, ConstraintDiagnostics, ConstraintIssue,
+    ConstraintRelation, ConstraintStrength, Constraints, LayoutConstraint, Row, SnapGrid,
+    SnapGridConfig,
+};
 pub use crate::sync::SyncPoint;
 pub use crate::text::{
-    KnownTextDimension, MonoSpacedFont, Text, TextGridLocation, TextGridPlacement,
-    TextLetterDimensions, TextLineStructure, TextScale, TextSectionDescriptor,
-    TextSectionDescriptorKnown, TextTag, TextValue, TextWrapStyle,
+    AutoScale, FontRegistry, KnownTextDimension, MeasuredGlyph, MeasuredLine, MonoSpacedFont,
+    RichText, SdfText, Selectable, Text, TextAlignment, TextDirection, TextFont, TextGridLocation,
+    TextGridPlacement, TextHorizontalAlignment, TextLetterDimensions, TextLineStructure,
+    TextMeasurement, TextMeasurer, TextOutline, TextOverflow, TextOverflowMode, TextScale,
+    TextSectionDescriptor, TextSectionDescriptorKnown, TextShadow, TextSpan, TextTag, TextValue,
+    TextVerticalAlignment, TextWrapStyle,
 };
 pub use crate::texture_atlas::{
-    AtlasBlock, AtlasDimension, AtlasFreeLocations, AtlasPosition, AtlasTexture,
+    AtlasBlock, AtlasDimension, AtlasFreeLocations, AtlasPosition, AtlasShelves, AtlasTexture,
     AtlasTextureDimensions, TextureAtlas, TextureBindGroup, TextureCoordinates,
 };
 pub use crate::theme::{Theme, ThemeDescriptor};
 pub use crate::time::{TimeDelta, TimeMarker, TimeTracker, Timer};
 pub use crate::uniform::{AlignedUniform, Uniform};
 pub use crate::visibility::{EnableVisibility, Visibility, VisibleSection};
-pub use crate::visual_debug::SectionOutline;
+pub use crate::visual_debug::{DebugOverlay, DebugOverlayTag, SectionOutline};
 pub use crate::window::WindowResize;
 #[cfg(target_os = "android")]
 pub use crate::workflow::AndroidInterface;
@@ -95,8 +108,10 @@ mod images;
 mod instance;
 mod interaction;
 mod job;
+mod keyboard;
 mod layer_compositor;
 mod line;
+mod localization;
 mod media;
 mod orientation;
 mod panel;
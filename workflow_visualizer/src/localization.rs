@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::{Component, DetectChanges, IntoSystemConfigs, Query, Res, Resource};
+
+use crate::button::ButtonText;
+use crate::{Attach, SyncPoint, TextValue, Visualizer};
+
+/// Language tag (BCP 47, e.g. "en-US") that `LocalizedText` resolves against.
+/// Changing it re-resolves every localized Text and button label.
+#[derive(Resource, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Locale(pub String);
+
+impl Locale {
+    pub const FALLBACK: &'static str = "en-US";
+    pub fn new<S: Into<String>>(tag: S) -> Self {
+        Self(tag.into())
+    }
+    /// primary language subtag; "en" for "en-US"
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+    /// locale the user prefers: the platform language setting on Windows, macOS, iOS
+    /// and Android, LANGUAGE/LC_ALL/LC_MESSAGES/LANG on Linux, `navigator.language` on web
+    pub fn system() -> Self {
+        sys_locale::get_locale()
+            .and_then(|tag| Self::from_posix(&tag))
+            .unwrap_or_else(|| Self::new(Self::FALLBACK))
+    }
+    /// "en_US.UTF-8" => "en-US"; None for unset or the "C"/"POSIX" locales
+    fn from_posix(value: &str) -> Option<Self> {
+        let tag = value.split(['.', '@']).next().unwrap_or_default();
+        if tag.is_empty() || tag == "C" || tag == "POSIX" {
+            return None;
+        }
+        Some(Self::new(tag.replace('_', "-")))
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+/// Line of a catalog source that is not `key = value`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CatalogError {
+    pub line: usize,
+}
+/// Translations of one locale.
+/// Values may reference `LocalizedText` args as `{$name}`.
+#[derive(Clone, Default, Debug)]
+pub struct Catalog {
+    entries: HashMap<String, String>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.entries.insert(key.into(), value.into());
+        self
+    }
+    /// parses `key = value` lines; blank lines and lines starting with `#` are skipped
+    pub fn parse(source: &str) -> Result<Self, CatalogError> {
+        let mut catalog = Self::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(CatalogError { line: index + 1 })?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(CatalogError { line: index + 1 });
+            }
+            catalog
+                .entries
+                .insert(key.to_string(), value.trim().to_string());
+        }
+        Ok(catalog)
+    }
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|value| value.as_str())
+    }
+}
+/// Catalogs per locale tag.
+/// Lookups try the exact tag, then its language, then `Locale::FALLBACK`.
+#[derive(Resource, Default)]
+pub struct Translations {
+    catalogs: HashMap<String, Catalog>,
+}
+
+impl Translations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn add(&mut self, locale: Locale, catalog: Catalog) {
+        self.catalogs.insert(locale.0, catalog);
+    }
+    pub fn load(&mut self, locale: Locale, source: &str) -> Result<(), CatalogError> {
+        self.add(locale, Catalog::parse(source)?);
+        Ok(())
+    }
+    /// translation of `key` with `args` substituted; None when no catalog has it
+    pub fn resolve(&self, locale: &Locale, key: &str, args: &[(String, String)]) -> Option<String> {
+        let fallback = Locale::new(Locale::FALLBACK);
+        let value = [
+            locale.0.as_str(),
+            locale.language(),
+            fallback.0.as_str(),
+            fallback.language(),
+        ]
+        .iter()
+        .filter_map(|tag| self.catalogs.get(*tag))
+        .find_map(|catalog| catalog.get(key))?;
+        let mut resolved = value.to_string();
+        for (name, arg) in args.iter() {
+            resolved = resolved.replace(&format!("{{${}}}", name), arg);
+        }
+        Some(resolved)
+    }
+}
+/// Text (or button label) whose `TextValue` is looked up in `Translations` for the `Locale`.
+/// Unknown keys display the key itself.
+#[derive(Component, Clone, PartialEq, Debug)]
+pub struct LocalizedText {
+    pub key: String,
+    pub args: Vec<(String, String)>,
+}
+
+impl LocalizedText {
+    pub fn new<S: Into<String>>(key: S) -> Self {
+        Self {
+            key: key.into(),
+            args: vec![],
+        }
+    }
+    pub fn with_arg<N: Into<String>, V: ToString>(mut self, name: N, value: V) -> Self {
+        self.args.push((name.into(), value.to_string()));
+        self
+    }
+    pub(crate) fn resolve(&self, translations: &Translations, locale: &Locale) -> String {
+        translations
+            .resolve(locale, &self.key, &self.args)
+            .unwrap_or_else(|| self.key.clone())
+    }
+}
+pub(crate) fn localize(
+    mut texts: Query<(
+        bevy_ecs::change_detection::Ref<LocalizedText>,
+        Option<&mut TextValue>,
+        Option<&mut ButtonText>,
+    )>,
+    translations: Res<Translations>,
+    locale: Res<Locale>,
+) {
+    let all = translations.is_changed() || locale.is_changed();
+    for (localized, text_value, button_text) in texts.iter_mut() {
+        if !all && !localized.is_changed() {
+            continue;
+        }
+        let resolved = localized.resolve(&translations, &locale);
+        if let Some(mut text_value) = text_value {
+            if text_value.0 != resolved {
+                text_value.0 = resolved.clone();
+            }
+        }
+        if let Some(mut button_text) = button_text {
+            if button_text.desc.as_ref().map(|desc| &desc.0) != Some(&resolved) {
+                button_text.desc.replace(TextValue(resolved));
+            }
+        }
+    }
+}
+pub(crate) struct LocalizationAttachment;
+impl Attach for LocalizationAttachment {
+    fn attach(visualizer: &mut Visualizer) {
+        visualizer
+            .job
+            .task(Visualizer::TASK_MAIN)
+            .add_systems((localize.in_set(SyncPoint::Preparation),));
+        visualizer.job.container.insert_resource(Locale::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(Translations::new());
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn resolution() {
+    let mut translations = Translations::new();
+    translations
+        .load(
+            Locale::new("en-US"),
+            "# greetings\ngreeting = Hello, {$name}!\nfarewell = Goodbye",
+        )
+        .unwrap();
+    translations
+        .load(Locale::new("fr"), "greeting = Bonjour, {$name} !")
+        .unwrap();
+    assert_eq!(
+        Catalog::parse("no separator").unwrap_err(),
+        CatalogError { line: 1 }
+    );
+    let greeting = LocalizedText::new("greeting").with_arg("name", "Ada");
+    let french = Locale::new("fr-CA");
+    assert_eq!(greeting.resolve(&translations, &french), "Bonjour, Ada !");
+    assert_eq!(
+        LocalizedText::new("farewell").resolve(&translations, &french),
+        "Goodbye"
+    );
+    assert_eq!(
+        LocalizedText::new("missing").resolve(&translations, &french),
+        "missing"
+    );
+    assert_eq!(
+        Locale::from_posix("pt_BR.UTF-8"),
+        Some(Locale::new("pt-BR"))
+    );
+}
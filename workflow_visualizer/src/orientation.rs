@@ -7,7 +7,7 @@ use crate::visualizer::{Attach, Visualizer};
 use crate::window::WindowResize;
 use crate::{Area, NumericalContext, ScaleFactor, SyncPoint};
 
-#[derive(Component, Resource, Copy, Clone)]
+#[derive(Component, Resource, Copy, Clone, PartialEq, Debug)]
 pub enum Orientation {
     Portrait(AspectRatio),
     Landscape(AspectRatio),
@@ -58,7 +58,7 @@ impl Attach for OrientationAttachment {
         engen
             .job
             .task(Visualizer::TASK_STARTUP)
-            .add_systems((setup_orientation,));
+            .add_systems((setup_orientation.in_set(SyncPoint::Event),));
         engen
             .job
             .task(Visualizer::TASK_MAIN)
@@ -66,7 +66,7 @@ impl Attach for OrientationAttachment {
     }
 }
 
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
 pub struct AspectRatio(pub f32);
 
 impl AspectRatio {
@@ -1,43 +1,167 @@
 use crate::{CoordinateUnit, SnapGrid};
+use bevy_ecs::prelude::Resource;
+use std::sync::OnceLock;
 
-#[repr(i32)]
-#[derive(Copy, Clone)]
-pub enum Breakpoint {
-    Mobile = 650,
-    Tablet = 950,
-    Desktop = 1250,
-    Workstation = 1550,
-}
+/// Index of a breakpoint within the active `SnapGridConfig`, ordered by threshold.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Debug, Default)]
+pub struct Breakpoint(pub usize);
 
 impl Breakpoint {
-    pub fn gutter(&self) -> CoordinateUnit {
-        match self {
-            Breakpoint::Mobile => SnapGrid::GUTTER_BASE,
-            Breakpoint::Tablet => SnapGrid::GUTTER_BASE * 1.25f32,
-            Breakpoint::Desktop => SnapGrid::GUTTER_BASE * 1.75f32,
-            Breakpoint::Workstation => SnapGrid::GUTTER_BASE * 2f32,
+    pub const MOBILE: Breakpoint = Breakpoint(0);
+    pub const TABLET: Breakpoint = Breakpoint(1);
+    pub const DESKTOP: Breakpoint = Breakpoint(2);
+    pub const WORKSTATION: Breakpoint = Breakpoint(3);
+}
+
+/// Describes the grid segmentation used up to a width threshold.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BreakpointDescriptor {
+    pub name: String,
+    pub threshold: CoordinateUnit,
+    pub columns: i32,
+    pub rows: i32,
+    pub gutter: CoordinateUnit,
+}
+
+impl BreakpointDescriptor {
+    pub fn new<S: Into<String>>(
+        name: S,
+        threshold: CoordinateUnit,
+        columns: i32,
+        rows: i32,
+        gutter: CoordinateUnit,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            threshold,
+            columns: columns.max(1),
+            rows: rows.max(1),
+            gutter,
+        }
+    }
+}
+
+/// Application defined breakpoints for the `SnapGrid`.
+/// Replace the resource to change the grid at runtime.
+/// Vertical breakpoints are optional; without them rows follow the horizontal breakpoint.
+/// A config without horizontal breakpoints uses those of `SnapGridConfig::default()`.
+#[derive(Resource, Clone, PartialEq, Debug)]
+pub struct SnapGridConfig {
+    breakpoints: Vec<BreakpointDescriptor>,
+    vertical_breakpoints: Vec<BreakpointDescriptor>,
+    pub max_content_aspect: f32,
+}
+
+impl SnapGridConfig {
+    pub const DEFAULT_MAX_CONTENT_ASPECT: f32 = 1.778;
+    pub fn new(max_content_aspect: f32) -> Self {
+        Self {
+            breakpoints: vec![],
+            vertical_breakpoints: vec![],
+            max_content_aspect,
+        }
+    }
+    /// adds a breakpoint keeping them ordered by threshold
+    pub fn with_breakpoint(mut self, descriptor: BreakpointDescriptor) -> Self {
+        self.breakpoints.push(descriptor);
+        self.breakpoints
+            .sort_by(|a, b| a.threshold.total_cmp(&b.threshold));
+        self
+    }
+    /// adds a breakpoint selected by height instead of width
+    pub fn with_vertical_breakpoint(mut self, descriptor: BreakpointDescriptor) -> Self {
+        self.vertical_breakpoints.push(descriptor);
+        self.vertical_breakpoints
+            .sort_by(|a, b| a.threshold.total_cmp(&b.threshold));
+        self
+    }
+    pub fn breakpoints(&self) -> &[BreakpointDescriptor] {
+        if self.breakpoints.is_empty() {
+            static DEFAULTS: OnceLock<SnapGridConfig> = OnceLock::new();
+            return DEFAULTS
+                .get_or_init(SnapGridConfig::default)
+                .breakpoints
+                .as_slice();
+        }
+        self.breakpoints.as_slice()
+    }
+    pub fn vertical_breakpoints(&self) -> &[BreakpointDescriptor] {
+        self.vertical_breakpoints.as_slice()
+    }
+    pub fn descriptor(&self, breakpoint: Breakpoint) -> &BreakpointDescriptor {
+        let breakpoints = self.breakpoints();
+        &breakpoints[breakpoint.0.min(breakpoints.len() - 1)]
+    }
+    /// lookup a breakpoint by the name it was configured with
+    pub fn breakpoint(&self, name: &str) -> Option<Breakpoint> {
+        self.breakpoints()
+            .iter()
+            .position(|desc| desc.name == name)
+            .map(Breakpoint)
+    }
+    pub fn vertical_descriptor(&self, breakpoint: Breakpoint) -> &BreakpointDescriptor {
+        if self.vertical_breakpoints.is_empty() {
+            return self.descriptor(breakpoint);
         }
+        let index = breakpoint.0.min(self.vertical_breakpoints.len() - 1);
+        &self.vertical_breakpoints[index]
+    }
+    pub fn establish(&self, dimension: CoordinateUnit) -> Breakpoint {
+        Self::establish_from(self.breakpoints(), dimension)
     }
-    pub fn segments(&self) -> i32 {
-        match self {
-            Breakpoint::Mobile => 15,
-            Breakpoint::Tablet => 17,
-            Breakpoint::Desktop => 19,
-            Breakpoint::Workstation => 21,
+    /// returns None when no vertical breakpoints are configured
+    pub fn establish_vertical(&self, dimension: CoordinateUnit) -> Option<Breakpoint> {
+        if self.vertical_breakpoints.is_empty() {
+            return None;
         }
+        Some(Self::establish_from(
+            self.vertical_breakpoints.as_slice(),
+            dimension,
+        ))
     }
-    pub fn value(&self) -> CoordinateUnit {
-        (*self as i32) as f32
-    }
-    pub fn establish(dimension: CoordinateUnit) -> Self {
-        if dimension <= Self::Mobile.value() {
-            Self::Mobile
-        } else if dimension <= Self::Tablet.value() {
-            Self::Tablet
-        } else if dimension <= Self::Desktop.value() {
-            Self::Desktop
-        } else {
-            Self::Workstation
+    fn establish_from(
+        breakpoints: &[BreakpointDescriptor],
+        dimension: CoordinateUnit,
+    ) -> Breakpoint {
+        for (index, desc) in breakpoints.iter().enumerate() {
+            if dimension <= desc.threshold {
+                return Breakpoint(index);
+            }
         }
+        Breakpoint(breakpoints.len().saturating_sub(1))
+    }
+}
+
+impl Default for SnapGridConfig {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_CONTENT_ASPECT)
+            .with_breakpoint(BreakpointDescriptor::new(
+                "mobile",
+                650f32,
+                15,
+                15,
+                SnapGrid::GUTTER_BASE,
+            ))
+            .with_breakpoint(BreakpointDescriptor::new(
+                "tablet",
+                950f32,
+                17,
+                17,
+                SnapGrid::GUTTER_BASE * 1.25f32,
+            ))
+            .with_breakpoint(BreakpointDescriptor::new(
+                "desktop",
+                1250f32,
+                19,
+                19,
+                SnapGrid::GUTTER_BASE * 1.75f32,
+            ))
+            .with_breakpoint(BreakpointDescriptor::new(
+                "workstation",
+                1550f32,
+                21,
+                21,
+                SnapGrid::GUTTER_BASE * 2f32,
+            ))
     }
 }
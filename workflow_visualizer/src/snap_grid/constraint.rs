@@ -0,0 +1,415 @@
+use crate::{Area, CoordinateUnit, InterfaceContext, Position, Section, ViewportHandle};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::prelude::{ParamSet, Query, Ref, Res, ResMut, Resource, With, Without};
+use cassowary::strength::{MEDIUM, REQUIRED, STRONG, WEAK};
+use cassowary::{AddConstraintError, Expression, RelationalOperator, Solver, Variable};
+use std::collections::HashMap;
+
+/// Edge or dimension of a section that a constraint can relate.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConstraintAttribute {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Width,
+    Height,
+    CenterX,
+    CenterY,
+}
+
+/// What a constraint is measured against.
+/// Entities without `Constraints` (e.g. grid placed) act as fixed anchors.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ConstraintAnchor {
+    Viewport,
+    Entity(Entity),
+}
+
+impl From<Entity> for ConstraintAnchor {
+    fn from(value: Entity) -> Self {
+        ConstraintAnchor::Entity(value)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConstraintRelation {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum ConstraintStrength {
+    #[default]
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl ConstraintStrength {
+    fn value(&self) -> f64 {
+        match self {
+            ConstraintStrength::Required => REQUIRED,
+            ConstraintStrength::Strong => STRONG,
+            ConstraintStrength::Medium => MEDIUM,
+            ConstraintStrength::Weak => WEAK,
+        }
+    }
+}
+
+/// `attribute relation anchor.attribute * multiplier + constant`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LayoutConstraint {
+    pub attribute: ConstraintAttribute,
+    pub relation: ConstraintRelation,
+    pub anchor: Option<(ConstraintAnchor, ConstraintAttribute)>,
+    pub multiplier: CoordinateUnit,
+    pub constant: CoordinateUnit,
+    pub strength: ConstraintStrength,
+}
+
+impl LayoutConstraint {
+    pub fn new(attribute: ConstraintAttribute) -> Self {
+        Self {
+            attribute,
+            relation: ConstraintRelation::Equal,
+            anchor: None,
+            multiplier: 1f32,
+            constant: 0f32,
+            strength: ConstraintStrength::default(),
+        }
+    }
+    fn relate<A: Into<ConstraintAnchor>>(
+        mut self,
+        relation: ConstraintRelation,
+        anchor: A,
+        attribute: ConstraintAttribute,
+    ) -> Self {
+        self.relation = relation;
+        self.anchor.replace((anchor.into(), attribute));
+        self
+    }
+    pub fn equal_to<A: Into<ConstraintAnchor>>(
+        self,
+        anchor: A,
+        attribute: ConstraintAttribute,
+    ) -> Self {
+        self.relate(ConstraintRelation::Equal, anchor, attribute)
+    }
+    pub fn at_most<A: Into<ConstraintAnchor>>(
+        self,
+        anchor: A,
+        attribute: ConstraintAttribute,
+    ) -> Self {
+        self.relate(ConstraintRelation::LessOrEqual, anchor, attribute)
+    }
+    pub fn at_least<A: Into<ConstraintAnchor>>(
+        self,
+        anchor: A,
+        attribute: ConstraintAttribute,
+    ) -> Self {
+        self.relate(ConstraintRelation::GreaterOrEqual, anchor, attribute)
+    }
+    /// relates the attribute to a constant only
+    pub fn constant(mut self, relation: ConstraintRelation, value: CoordinateUnit) -> Self {
+        self.relation = relation;
+        self.anchor.take();
+        self.constant = value;
+        self
+    }
+    pub fn times(mut self, multiplier: CoordinateUnit) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+    pub fn plus(mut self, constant: CoordinateUnit) -> Self {
+        self.constant = constant;
+        self
+    }
+    pub fn with_strength(mut self, strength: ConstraintStrength) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+/// Constraint placement for an entity. The solver writes `Position`/`Area`
+/// whenever the viewport, these constraints, or an anchor change.
+#[derive(Component, Clone, Default)]
+pub struct Constraints(pub Vec<LayoutConstraint>);
+
+impl Constraints {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+    pub fn with(mut self, constraint: LayoutConstraint) -> Self {
+        self.0.push(constraint);
+        self
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ConstraintIssue {
+    /// the anchor entity has no `Position`/`Area` (or does not exist)
+    MissingAnchor(ConstraintAnchor),
+    /// a required constraint conflicts with those added before it
+    Unsatisfiable,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ConstraintDiagnostic {
+    pub entity: Entity,
+    /// index into the entity's `Constraints`
+    pub index: usize,
+    pub issue: ConstraintIssue,
+}
+
+/// Issues found during the last solve; offending constraints are skipped.
+#[derive(Resource, Default, Debug)]
+pub struct ConstraintDiagnostics(pub Vec<ConstraintDiagnostic>);
+
+#[derive(Copy, Clone)]
+struct SectionVariables {
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable,
+}
+
+impl SectionVariables {
+    fn new() -> Self {
+        Self {
+            left: Variable::new(),
+            top: Variable::new(),
+            width: Variable::new(),
+            height: Variable::new(),
+        }
+    }
+    fn expression(&self, attribute: ConstraintAttribute) -> Expression {
+        match attribute {
+            ConstraintAttribute::Left => self.left.into(),
+            ConstraintAttribute::Right => self.left + self.width,
+            ConstraintAttribute::Top => self.top.into(),
+            ConstraintAttribute::Bottom => self.top + self.height,
+            ConstraintAttribute::Width => self.width.into(),
+            ConstraintAttribute::Height => self.height.into(),
+            ConstraintAttribute::CenterX => self.left + self.width * 0.5,
+            ConstraintAttribute::CenterY => self.top + self.height * 0.5,
+        }
+    }
+}
+
+fn fixed(section: Section<InterfaceContext>, attribute: ConstraintAttribute) -> Expression {
+    let value = match attribute {
+        ConstraintAttribute::Left => section.left(),
+        ConstraintAttribute::Right => section.right(),
+        ConstraintAttribute::Top => section.top(),
+        ConstraintAttribute::Bottom => section.bottom(),
+        ConstraintAttribute::Width => section.width(),
+        ConstraintAttribute::Height => section.height(),
+        ConstraintAttribute::CenterX => section.left() + section.width() / 2f32,
+        ConstraintAttribute::CenterY => section.top() + section.height() / 2f32,
+    };
+    Expression::from_constant(value as f64)
+}
+
+/// Solves every entity's `Constraints` together. Fixed anchors are given by `anchors`;
+/// each entity's current section is a weak preference so under-constrained entities stay put.
+pub fn solve_constraints(
+    constrained: &[(Entity, &Constraints, Section<InterfaceContext>)],
+    anchors: &HashMap<ConstraintAnchor, Section<InterfaceContext>>,
+) -> (
+    HashMap<Entity, Section<InterfaceContext>>,
+    ConstraintDiagnostics,
+) {
+    let mut solver = Solver::new();
+    let mut diagnostics = ConstraintDiagnostics::default();
+    let variables = constrained
+        .iter()
+        .map(|(entity, _, _)| (*entity, SectionVariables::new()))
+        .collect::<HashMap<Entity, SectionVariables>>();
+    for (entity, _, current) in constrained.iter() {
+        let vars = variables.get(entity).expect("constraint variables");
+        let _ = solver.add_constraints(&[
+            cassowary::Constraint::new(
+                vars.width.into(),
+                RelationalOperator::GreaterOrEqual,
+                REQUIRED,
+            ),
+            cassowary::Constraint::new(
+                vars.height.into(),
+                RelationalOperator::GreaterOrEqual,
+                REQUIRED,
+            ),
+            cassowary::Constraint::new(
+                vars.left - current.left() as f64,
+                RelationalOperator::Equal,
+                WEAK,
+            ),
+            cassowary::Constraint::new(
+                vars.top - current.top() as f64,
+                RelationalOperator::Equal,
+                WEAK,
+            ),
+            cassowary::Constraint::new(
+                vars.width - current.width() as f64,
+                RelationalOperator::Equal,
+                WEAK,
+            ),
+            cassowary::Constraint::new(
+                vars.height - current.height() as f64,
+                RelationalOperator::Equal,
+                WEAK,
+            ),
+        ]);
+    }
+    for (entity, constraints, _) in constrained.iter() {
+        let vars = variables.get(entity).expect("constraint variables");
+        for (index, constraint) in constraints.0.iter().enumerate() {
+            let rhs = match constraint.anchor {
+                None => Expression::from_constant(0f64),
+                Some((anchor, attribute)) => {
+                    let anchored = match anchor {
+                        ConstraintAnchor::Entity(other) if variables.contains_key(&other) => {
+                            Some(variables.get(&other).unwrap().expression(attribute))
+                        }
+                        _ => anchors
+                            .get(&anchor)
+                            .map(|section| fixed(*section, attribute)),
+                    };
+                    match anchored {
+                        Some(expression) => expression * constraint.multiplier as f64,
+                        None => {
+                            diagnostics.0.push(ConstraintDiagnostic {
+                                entity: *entity,
+                                index,
+                                issue: ConstraintIssue::MissingAnchor(anchor),
+                            });
+                            continue;
+                        }
+                    }
+                }
+            };
+            let operator = match constraint.relation {
+                ConstraintRelation::Equal => RelationalOperator::Equal,
+                ConstraintRelation::LessOrEqual => RelationalOperator::LessOrEqual,
+                ConstraintRelation::GreaterOrEqual => RelationalOperator::GreaterOrEqual,
+            };
+            let expression =
+                vars.expression(constraint.attribute) - (rhs + constraint.constant as f64);
+            if let Err(AddConstraintError::UnsatisfiableConstraint) = solver.add_constraint(
+                cassowary::Constraint::new(expression, operator, constraint.strength.value()),
+            ) {
+                diagnostics.0.push(ConstraintDiagnostic {
+                    entity: *entity,
+                    index,
+                    issue: ConstraintIssue::Unsatisfiable,
+                });
+            }
+        }
+    }
+    let solved = variables
+        .iter()
+        .map(|(entity, vars)| {
+            (
+                *entity,
+                Section::new(
+                    (
+                        solver.get_value(vars.left) as f32,
+                        solver.get_value(vars.top) as f32,
+                    ),
+                    (
+                        solver.get_value(vars.width) as f32,
+                        solver.get_value(vars.height) as f32,
+                    ),
+                ),
+            )
+        })
+        .collect();
+    (solved, diagnostics)
+}
+
+pub(crate) fn constraint_layout(
+    mut constrained: ParamSet<(
+        Query<(
+            Entity,
+            Ref<Constraints>,
+            &Position<InterfaceContext>,
+            Option<&Area<InterfaceContext>>,
+        )>,
+        Query<
+            (
+                &mut Position<InterfaceContext>,
+                Option<&mut Area<InterfaceContext>>,
+            ),
+            With<Constraints>,
+        >,
+    )>,
+    anchors: Query<
+        (
+            Ref<Position<InterfaceContext>>,
+            Option<Ref<Area<InterfaceContext>>>,
+        ),
+        Without<Constraints>,
+    >,
+    viewport_handle: Res<ViewportHandle>,
+    mut diagnostics: ResMut<ConstraintDiagnostics>,
+) {
+    let mut needs_solve = viewport_handle.is_changed();
+    let mut anchor_sections = HashMap::new();
+    anchor_sections.insert(
+        ConstraintAnchor::Viewport,
+        Section::new((0f32, 0f32), viewport_handle.section.area),
+    );
+    for (_, constraints, _, _) in constrained.p0().iter() {
+        needs_solve |= constraints.is_changed();
+        for constraint in constraints.0.iter() {
+            if let Some((ConstraintAnchor::Entity(anchor), _)) = constraint.anchor {
+                if let Ok((anchor_pos, anchor_area)) = anchors.get(anchor) {
+                    needs_solve |= anchor_pos.is_changed()
+                        || anchor_area.as_ref().map_or(false, |area| area.is_changed());
+                    anchor_sections.insert(
+                        ConstraintAnchor::Entity(anchor),
+                        Section::new(
+                            *anchor_pos,
+                            anchor_area.map(|area| *area).unwrap_or_default(),
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    if !needs_solve {
+        return;
+    }
+    let query = constrained.p0();
+    let current = query
+        .iter()
+        .map(|(entity, constraints, pos, area)| {
+            (
+                entity,
+                constraints.into_inner(),
+                Section::new(*pos, area.copied().unwrap_or_default()),
+            )
+        })
+        .collect::<Vec<(Entity, &Constraints, Section<InterfaceContext>)>>();
+    let (solved, found) = solve_constraints(current.as_slice(), &anchor_sections);
+    for diagnostic in found.0.iter() {
+        tracing::warn!("constraint layout: {:?}", diagnostic);
+    }
+    diagnostics.0 = found.0;
+    for (entity, section) in solved {
+        if let Ok((mut pos, area)) = constrained.p1().get_mut(entity) {
+            if *pos != section.position {
+                *pos = section.position;
+            }
+            if let Some(mut area) = area {
+                if *area != section.area {
+                    *area = section.area;
+                }
+            }
+        }
+    }
+}
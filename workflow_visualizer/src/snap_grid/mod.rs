@@ -1,11 +1,18 @@
+use crate::orientation::calc_orientation;
 use crate::{
-    Area, Attach, CoordinateUnit, DelayedBundle, InterfaceContext, Position, QueuedAnimation,
-    Section, SyncPoint, TimeDelta, ViewportHandle, Visualizer,
+    Animate, Animation, Area, Attach, CoordinateUnit, DelayedBundle, InterfaceContext, Orientation,
+    Position, QueuedAnimation, Section, SyncPoint, TimeDelta, ViewportHandle, Visualizer,
 };
-use bevy_ecs::prelude::{IntoSystemConfigs, Res, Resource};
-use bevy_ecs::query::{Changed, Or};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::prelude::{IntoSystemConfigs, Or, Ref, Res, ResMut, Resource, With};
 use bevy_ecs::system::{Commands, Query};
-pub use breakpoint::Breakpoint;
+pub use breakpoint::{Breakpoint, BreakpointDescriptor, SnapGridConfig};
+pub(crate) use constraint::constraint_layout;
+pub use constraint::{
+    solve_constraints, ConstraintAnchor, ConstraintAttribute, ConstraintDiagnostic,
+    ConstraintDiagnostics, ConstraintIssue, ConstraintRelation, ConstraintStrength, Constraints,
+    LayoutConstraint,
+};
 pub(crate) use float::reapply;
 pub use float::{
     FloatArrangement, FloatLocation, FloatPlacement, FloatPlacementDescriptor, FloatPlacer,
@@ -18,25 +25,33 @@ pub use responsive::{
     ResponsiveGridLocation, ResponsiveGridPoint, ResponsiveGridRange, ResponsiveGridView,
     ResponsiveUnit,
 };
+pub(crate) use stack::{arrange_stacks, measure_icons, measure_images, measure_text};
+pub use stack::{IntrinsicArea, Stack, StackAlignment, StackItem, StackLayout};
 
 mod breakpoint;
+mod constraint;
 mod float;
 mod marker;
 mod responsive;
+mod stack;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq)]
 pub struct Column {
     pub content: CoordinateUnit,
     pub gutter: CoordinateUnit,
     pub extension: Option<CoordinateUnit>,
 }
 impl Column {
-    pub fn new(area: Area<InterfaceContext>, breakpoint: Breakpoint) -> Self {
-        let actual = area.width.min(area.height * 1.778);
+    pub fn new(
+        area: Area<InterfaceContext>,
+        descriptor: &BreakpointDescriptor,
+        max_content_aspect: f32,
+    ) -> Self {
+        let actual = area.width.min(area.height * max_content_aspect);
+        let segments = descriptor.columns as f32;
         Self {
-            content: (actual - breakpoint.gutter() * (breakpoint.segments() + 1) as f32)
-                / breakpoint.segments() as f32,
-            gutter: breakpoint.gutter(),
+            content: (actual - descriptor.gutter * (segments + 1f32)) / segments,
+            gutter: descriptor.gutter,
             extension: if area.width > actual {
                 Some(area.width - actual)
             } else {
@@ -49,10 +64,12 @@ impl Column {
 #[cfg(test)]
 fn snap_grid_coverage() {
     let step: usize = 10;
-    for width in (4..Breakpoint::Workstation.value() as u32 + step as u32 + 2).step_by(step) {
-        for height in (4..Breakpoint::Workstation.value() as u32 + step as u32 + 2).step_by(step) {
+    let config = SnapGridConfig::default();
+    let max = config.descriptor(Breakpoint::WORKSTATION).threshold as u32;
+    for width in (4..max + step as u32 + 2).step_by(step) {
+        for height in (4..max + step as u32 + 2).step_by(step) {
             let area = Area::new(width as f32, height as f32);
-            let grid = SnapGrid::new(area);
+            let grid = SnapGrid::new(area, Orientation::new(area.as_numerical()), &config);
             println!(
                 "Snap-Grid@{}-{}: column: {:?} row: {:?}",
                 width, height, grid.column.content, grid.row.content
@@ -60,7 +77,88 @@ fn snap_grid_coverage() {
         }
     }
 }
-#[derive(Copy, Clone)]
+#[test]
+#[cfg(test)]
+fn custom_breakpoints() {
+    let config = SnapGridConfig::new(2.4)
+        .with_breakpoint(BreakpointDescriptor::new("wide", 2560f32, 32, 18, 6f32))
+        .with_breakpoint(BreakpointDescriptor::new("narrow", 800f32, 12, 12, 8f32))
+        .with_breakpoint(BreakpointDescriptor::new("ultra", 3840f32, 48, 18, 6f32));
+    assert_eq!(config.breakpoint("narrow"), Some(Breakpoint(0)));
+    assert_eq!(config.breakpoint("ultra"), Some(Breakpoint(2)));
+    assert_eq!(config.establish(700f32), Breakpoint(0));
+    assert_eq!(config.establish(2000f32), Breakpoint(1));
+    assert_eq!(config.establish(5000f32), Breakpoint(2));
+    let grid = SnapGrid::new(
+        Area::new(3000f32, 1600f32),
+        Orientation::new((3000f32, 1600f32)),
+        &config,
+    );
+    assert_eq!(grid.breakpoint, Breakpoint(2));
+    let expected = (3000f32 - 6f32 * 49f32) / 48f32;
+    assert!((grid.column.content - expected).abs() < f32::EPSILON * 100f32);
+    let unit = ResponsiveUnit::new(1).with_breakpoint(Breakpoint(2), 3);
+    let portrait = Orientation::new(Area::<crate::NumericalContext>::new(1f32, 2f32));
+    assert_eq!(unit.current(Breakpoint(0), portrait), 1);
+    assert_eq!(unit.current(Breakpoint(1), portrait), 1);
+    assert_eq!(unit.current(Breakpoint(2), portrait), 3);
+    assert_eq!(unit.current(Breakpoint(7), portrait), 3);
+}
+#[test]
+#[cfg(test)]
+fn vertical_breakpoints_and_orientation() {
+    let config = SnapGridConfig::default()
+        .with_vertical_breakpoint(BreakpointDescriptor::new("short", 500f32, 1, 6, 8f32))
+        .with_vertical_breakpoint(BreakpointDescriptor::new("tall", 1000f32, 1, 12, 8f32));
+    let grid = SnapGrid::new(
+        Area::new(1200f32, 400f32),
+        Orientation::new((1200f32, 400f32)),
+        &config,
+    );
+    assert_eq!(grid.breakpoint, Breakpoint::DESKTOP);
+    assert_eq!(grid.vertical_breakpoint, Breakpoint(0));
+    assert!(matches!(grid.orientation, Orientation::Landscape(_)));
+    let expected = (400f32 - 8f32 * 7f32) / 6f32;
+    assert!((grid.row.content - expected).abs() < f32::EPSILON * 100f32);
+    let unit = ResponsiveUnit::new(1)
+        .with_desktop(2)
+        .with_landscape(5)
+        .with_landscape_breakpoint(Breakpoint::WORKSTATION, 7);
+    assert_eq!(unit.current(grid.breakpoint, grid.orientation), 5);
+    assert_eq!(unit.current(Breakpoint::WORKSTATION, grid.orientation), 7);
+    let portrait = SnapGrid::new(
+        Area::new(400f32, 1200f32),
+        Orientation::new((400f32, 1200f32)),
+        &config,
+    );
+    assert_eq!(portrait.vertical_breakpoint, Breakpoint(1));
+    assert_eq!(unit.current(Breakpoint::DESKTOP, portrait.orientation), 2);
+    let fallback = SnapGrid::new(
+        Area::new(1200f32, 400f32),
+        Orientation::new((1200f32, 400f32)),
+        &SnapGridConfig::default(),
+    );
+    assert_eq!(fallback.vertical_breakpoint, fallback.breakpoint);
+}
+#[test]
+#[cfg(test)]
+fn empty_config_uses_default_breakpoints() {
+    let config = SnapGridConfig::new(2.0);
+    let defaults = SnapGridConfig::default();
+    assert_eq!(
+        config.descriptor(Breakpoint::TABLET),
+        defaults.descriptor(Breakpoint::TABLET)
+    );
+    assert_eq!(config.breakpoint("desktop"), Some(Breakpoint::DESKTOP));
+    assert_eq!(config.establish(900f32), Breakpoint::TABLET);
+    let grid = SnapGrid::new(
+        Area::new(1200f32, 800f32),
+        Orientation::new((1200f32, 800f32)),
+        &config,
+    );
+    assert_eq!(grid.breakpoint, Breakpoint::DESKTOP);
+}
+#[derive(Copy, Clone, PartialEq)]
 pub struct Row {
     pub content: CoordinateUnit,
     pub gutter: CoordinateUnit,
@@ -68,16 +166,16 @@ pub struct Row {
 }
 
 impl Row {
-    pub fn new(height: CoordinateUnit, breakpoint: Breakpoint) -> Self {
-        let segments = breakpoint.segments() as f32;
-        let diff = (height - breakpoint.value()).max(0f32);
-        let actual = height.min(breakpoint.value()) + diff * 0.2f32;
-        let content = Self::content_calc(actual, breakpoint.gutter(), segments);
-        let threshold = breakpoint.gutter() * 2f32;
+    pub fn new(height: CoordinateUnit, descriptor: &BreakpointDescriptor) -> Self {
+        let segments = descriptor.rows as f32;
+        let diff = (height - descriptor.threshold).max(0f32);
+        let actual = height.min(descriptor.threshold) + diff * 0.2f32;
+        let content = Self::content_calc(actual, descriptor.gutter, segments);
+        let threshold = descriptor.gutter * 2f32;
         let gutter = if content < threshold {
-            breakpoint.gutter() / 2f32
+            descriptor.gutter / 2f32
         } else {
-            breakpoint.gutter()
+            descriptor.gutter
         };
         let content = Self::content_calc(actual, gutter, segments);
         Self {
@@ -96,20 +194,36 @@ impl Row {
     }
 }
 /// Macro placement tool segmented into fixed number of columns/rows.
-#[derive(Resource, Copy, Clone)]
+#[derive(Resource, Copy, Clone, PartialEq)]
 pub struct SnapGrid {
     pub column: Column,
     pub row: Row,
     pub breakpoint: Breakpoint,
+    pub vertical_breakpoint: Breakpoint,
+    pub orientation: Orientation,
 }
 impl SnapGrid {
     pub const GUTTER_BASE: CoordinateUnit = 8f32;
-    pub fn new(area: Area<InterfaceContext>) -> Self {
-        let breakpoint = Breakpoint::establish(area.width);
+    pub fn new(
+        area: Area<InterfaceContext>,
+        orientation: Orientation,
+        config: &SnapGridConfig,
+    ) -> Self {
+        let breakpoint = config.establish(area.width);
+        let (vertical_breakpoint, row_descriptor) = match config.establish_vertical(area.height) {
+            Some(vertical) => (vertical, config.vertical_descriptor(vertical)),
+            None => (breakpoint, config.descriptor(breakpoint)),
+        };
         Self {
-            column: Column::new(area, breakpoint),
-            row: Row::new(area.height, breakpoint),
+            column: Column::new(
+                area,
+                config.descriptor(breakpoint),
+                config.max_content_aspect,
+            ),
+            row: Row::new(area.height, row_descriptor),
             breakpoint,
+            vertical_breakpoint,
+            orientation,
         }
     }
     pub fn location_coordinates(
@@ -164,94 +278,238 @@ impl SnapGrid {
     }
     pub fn responsive_view_coordinates(
         &self,
-        view: ResponsiveGridView,
+        view: &ResponsiveGridView,
     ) -> Section<InterfaceContext> {
-        self.view_coordinates(view.current(self.breakpoint))
+        self.view_coordinates(view.current(
+            self.breakpoint,
+            self.vertical_breakpoint,
+            self.orientation,
+        ))
     }
     pub fn responsive_point_coordinates(
         &self,
-        point: ResponsiveGridPoint,
+        point: &ResponsiveGridPoint,
     ) -> Position<InterfaceContext> {
-        self.point_coordinates(point.current(self.breakpoint))
+        self.point_coordinates(point.current(
+            self.breakpoint,
+            self.vertical_breakpoint,
+            self.orientation,
+        ))
     }
     pub fn responsive_range_coordinates(
         &self,
         direction: GridDirection,
-        range: ResponsiveGridRange,
+        range: &ResponsiveGridRange,
     ) -> (CoordinateUnit, CoordinateUnit) {
         match direction {
             GridDirection::Horizontal => {
-                self.range_coordinates(direction, range.current(self.breakpoint))
-            }
-            GridDirection::Vertical => {
-                self.range_coordinates(direction, range.current(self.breakpoint))
+                self.range_coordinates(direction, range.current(self.breakpoint, self.orientation))
             }
+            GridDirection::Vertical => self.range_coordinates(
+                direction,
+                range.current(self.vertical_breakpoint, self.orientation),
+            ),
         }
     }
     pub fn responsive_location_coordinates(
         &self,
         direction: GridDirection,
-        location: ResponsiveGridLocation,
+        location: &ResponsiveGridLocation,
     ) -> CoordinateUnit {
         let location = match direction {
-            GridDirection::Horizontal => location.current(self.breakpoint),
-            GridDirection::Vertical => location.current(self.breakpoint),
+            GridDirection::Horizontal => location.current(self.breakpoint, self.orientation),
+            GridDirection::Vertical => location.current(self.vertical_breakpoint, self.orientation),
         };
         self.location_coordinates(direction, location)
     }
+    /// Animates an entity carrying `begin` to `end` over `interval`.
+    /// `end` replaces `begin` once the animation completes; the targets
+    /// are re-resolved if the grid changes while in flight.
     pub fn animate_location(
         &self,
-        _begin: ResponsiveGridPoint,
-        _other: ResponsiveGridPoint,
-        _interval: TimeDelta,
-        _delay: Option<TimeDelta>,
+        begin: ResponsiveGridPoint,
+        end: ResponsiveGridPoint,
+        interval: TimeDelta,
+        delay: Option<TimeDelta>,
     ) -> (
         QueuedAnimation<Position<InterfaceContext>>,
         DelayedBundle<ResponsiveGridPoint>,
     ) {
-        todo!()
+        let start = self.responsive_point_coordinates(&begin);
+        let finish = self.responsive_point_coordinates(&end);
+        (
+            start.animate(finish, interval).with_offset(delay),
+            DelayedBundle::new(end, interval + delay.unwrap_or_default()),
+        )
     }
+    /// Animates an entity carrying `begin` to `end` over `interval`.
+    /// `end` replaces `begin` once the animation completes; the targets
+    /// are re-resolved if the grid changes while in flight.
     pub fn animate_view(
         &self,
-        _begin: ResponsiveGridView,
-        _end: ResponsiveGridView,
-        _interval: TimeDelta,
-        _delay: Option<TimeDelta>,
+        begin: ResponsiveGridView,
+        end: ResponsiveGridView,
+        interval: TimeDelta,
+        delay: Option<TimeDelta>,
     ) -> (
         QueuedAnimation<Position<InterfaceContext>>,
         QueuedAnimation<Area<InterfaceContext>>,
         DelayedBundle<ResponsiveGridView>,
     ) {
-        todo!()
+        let start = self.responsive_view_coordinates(&begin);
+        let finish = self.responsive_view_coordinates(&end);
+        (
+            start
+                .position
+                .animate(finish.position, interval)
+                .with_offset(delay),
+            start.area.animate(finish.area, interval).with_offset(delay),
+            DelayedBundle::new(end, interval + delay.unwrap_or_default()),
+        )
     }
 }
 pub(crate) fn calculate(
-    mut gridded: Query<
+    mut gridded: Query<(
+        &mut Position<InterfaceContext>,
+        Option<&mut Area<InterfaceContext>>,
+        Option<Ref<ResponsiveGridPoint>>,
+        Option<Ref<ResponsiveGridView>>,
+        Option<&Animation<Position<InterfaceContext>>>,
+        Option<&Animation<Area<InterfaceContext>>>,
+    )>,
+    grid: Res<SnapGrid>,
+) {
+    let grid_changed = grid.is_changed();
+    for (mut position, area, location, view, position_animation, area_animation) in
+        gridded.iter_mut()
+    {
+        if let Some(loc) = location {
+            if position_animation.is_some() {
+                continue;
+            }
+            if grid_changed || loc.is_changed() {
+                *position = grid.responsive_point_coordinates(&loc);
+            }
+        } else if let Some(view) = view {
+            if position_animation.is_some() || area_animation.is_some() {
+                continue;
+            }
+            if grid_changed || view.is_changed() {
+                let section = grid.responsive_view_coordinates(&view);
+                *position = section.position;
+                if let Some(mut area) = area {
+                    *area = section.area;
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn retarget_point_animations(
+    mut animated: Query<(
+        &mut Position<InterfaceContext>,
+        &mut Animation<Position<InterfaceContext>>,
+        &ResponsiveGridPoint,
+        Option<&DelayedBundle<ResponsiveGridPoint>>,
+    )>,
+    grid: Res<SnapGrid>,
+) {
+    for (mut position, mut animation, point, delayed) in animated.iter_mut() {
+        let target = delayed
+            .and_then(|delayed| delayed.bundle.as_ref())
+            .unwrap_or(point);
+        let end = grid.responsive_point_coordinates(target);
+        if animation.done() {
+            *position = end;
+        } else if grid.is_changed() {
+            if animation.running() {
+                animation.retarget(&position, end);
+            } else {
+                let start = grid.responsive_point_coordinates(point);
+                animation.retarget_pending(start, end);
+                *position = start;
+            }
+        }
+    }
+}
+
+pub(crate) fn retarget_view_animations(
+    mut animated: Query<
         (
             &mut Position<InterfaceContext>,
-            Option<&mut Area<InterfaceContext>>,
-            Option<&ResponsiveGridPoint>,
-            Option<&ResponsiveGridView>,
+            &mut Area<InterfaceContext>,
+            Option<&mut Animation<Position<InterfaceContext>>>,
+            Option<&mut Animation<Area<InterfaceContext>>>,
+            &ResponsiveGridView,
+            Option<&DelayedBundle<ResponsiveGridView>>,
         ),
-        Or<(Changed<ResponsiveGridPoint>, Changed<ResponsiveGridView>)>,
+        Or<(
+            With<Animation<Position<InterfaceContext>>>,
+            With<Animation<Area<InterfaceContext>>>,
+        )>,
     >,
     grid: Res<SnapGrid>,
 ) {
-    for (mut position, area, location, view) in gridded.iter_mut() {
-        if let Some(loc) = location {
-            *position = grid.responsive_point_coordinates(*loc);
-        } else if let Some(view) = view {
-            let section = grid.responsive_view_coordinates(*view);
-            *position = section.position;
-            if let Some(mut area) = area {
-                *area = section.area;
+    for (mut position, mut area, position_animation, area_animation, view, delayed) in
+        animated.iter_mut()
+    {
+        let target = delayed
+            .and_then(|delayed| delayed.bundle.as_ref())
+            .unwrap_or(view);
+        let end = grid.responsive_view_coordinates(target);
+        let start = grid.responsive_view_coordinates(view);
+        if let Some(mut animation) = position_animation {
+            if animation.done() {
+                *position = end.position;
+            } else if grid.is_changed() {
+                if animation.running() {
+                    animation.retarget(&position, end.position);
+                } else {
+                    animation.retarget_pending(start.position, end.position);
+                    *position = start.position;
+                }
+            }
+        }
+        if let Some(mut animation) = area_animation {
+            if animation.done() {
+                *area = end.area;
+            } else if grid.is_changed() {
+                if animation.running() {
+                    animation.retarget(&area, end.area);
+                } else {
+                    animation.retarget_pending(start.area, end.area);
+                    *area = start.area;
+                }
             }
         }
     }
 }
 
-pub(crate) fn setup_snap_grid(mut cmd: Commands, viewport_handle: Res<ViewportHandle>) {
-    cmd.insert_resource(SnapGrid::new(viewport_handle.section.area));
+pub(crate) fn setup_snap_grid(
+    mut cmd: Commands,
+    viewport_handle: Res<ViewportHandle>,
+    orientation: Res<Orientation>,
+    config: Res<SnapGridConfig>,
+) {
+    cmd.insert_resource(SnapGrid::new(
+        viewport_handle.section.area,
+        *orientation,
+        &config,
+    ));
+}
+
+pub(crate) fn configure_snap_grid(
+    viewport_handle: Res<ViewportHandle>,
+    orientation: Res<Orientation>,
+    config: Res<SnapGridConfig>,
+    mut grid: ResMut<SnapGrid>,
+) {
+    if viewport_handle.is_changed() || orientation.is_changed() || config.is_changed() {
+        let configured = SnapGrid::new(viewport_handle.section.area, *orientation, &config);
+        if configured != *grid {
+            *grid = configured;
+        }
+    }
 }
 
 pub(crate) struct SnapGridAttachment;
@@ -262,10 +520,134 @@ impl Attach for SnapGridAttachment {
             calculate.in_set(SyncPoint::Finish),
         ));
         visualizer.task(Visualizer::TASK_MAIN).add_systems((
+            configure_snap_grid
+                .in_set(SyncPoint::Config)
+                .after(calc_orientation),
             reapply.in_set(SyncPoint::SecondaryEffects),
+            measure_text.in_set(SyncPoint::PostProcessPreparation),
+            measure_icons.in_set(SyncPoint::PostProcessPreparation),
+            measure_images.in_set(SyncPoint::PostProcessPreparation),
+            constraint_layout
+                .in_set(SyncPoint::SecondaryEffects)
+                .after(calculate)
+                .after(reapply),
+            arrange_stacks
+                .in_set(SyncPoint::SecondaryEffects)
+                .after(measure_text)
+                .after(measure_icons)
+                .after(measure_images)
+                .after(calculate)
+                .after(constraint_layout),
             calculate.in_set(SyncPoint::PostProcessPreparation),
+            retarget_point_animations
+                .in_set(SyncPoint::PostProcessPreparation)
+                .after(calculate),
+            retarget_view_animations
+                .in_set(SyncPoint::PostProcessPreparation)
+                .after(calculate),
         ));
+        visualizer
+            .job
+            .container
+            .insert_resource(SnapGridConfig::default());
+        visualizer
+            .job
+            .container
+            .insert_resource(ConstraintDiagnostics::default());
         visualizer.enable_delayed_spawn::<ResponsiveGridPoint>();
         visualizer.enable_delayed_spawn::<ResponsiveGridView>();
     }
 }
+#[test]
+#[cfg(test)]
+fn stack_arrangement() {
+    use std::collections::HashMap;
+    let a = bevy_ecs::entity::Entity::from_raw(0);
+    let b = bevy_ecs::entity::Entity::from_raw(1);
+    let c = bevy_ecs::entity::Entity::from_raw(2);
+    let mut sizes = HashMap::new();
+    sizes.insert(a, Area::new(50f32, 20f32));
+    sizes.insert(b, Area::new(30f32, 10f32));
+    sizes.insert(c, Area::new(40f32, 30f32));
+    let section = Section::new((10f32, 10f32), (200f32, 40f32));
+    let mut hstack = Stack::hstack(10f32, StackAlignment::Center);
+    hstack.add(a, StackItem::new());
+    hstack.add(b, StackItem::new().with_grow(1f32));
+    hstack.add(c, StackItem::new());
+    let arranged = hstack.arrange(section, &sizes);
+    assert_eq!(arranged[&a].position, Position::new(10f32, 20f32));
+    assert_eq!(arranged[&b].area, Area::new(90f32, 10f32));
+    assert_eq!(arranged[&c].position, Position::new(170f32, 15f32));
+    let mut vstack = Stack::vstack(5f32, StackAlignment::Stretch);
+    vstack.add(a, StackItem::new());
+    vstack.add(c, StackItem::new());
+    let arranged = vstack.arrange(Section::new((0f32, 0f32), (100f32, 40f32)), &sizes);
+    assert_eq!(arranged[&a].area, Area::new(100f32, 14f32));
+    assert_eq!(arranged[&c].position, Position::new(0f32, 19f32));
+    let mut wrap = Stack::wrap(10f32, StackAlignment::Start);
+    wrap.add(a, StackItem::new());
+    wrap.add(b, StackItem::new());
+    wrap.add(c, StackItem::new());
+    let arranged = wrap.arrange(Section::new((0f32, 0f32), (100f32, 100f32)), &sizes);
+    assert_eq!(arranged[&b].position, Position::new(60f32, 0f32));
+    assert_eq!(arranged[&c].position, Position::new(0f32, 30f32));
+}
+#[test]
+#[cfg(test)]
+fn constraint_solving() {
+    use std::collections::HashMap;
+    let anchor = bevy_ecs::entity::Entity::from_raw(0);
+    let a = bevy_ecs::entity::Entity::from_raw(1);
+    let b = bevy_ecs::entity::Entity::from_raw(2);
+    let mut anchors = HashMap::new();
+    anchors.insert(
+        ConstraintAnchor::Viewport,
+        Section::new((0f32, 0f32), (400f32, 300f32)),
+    );
+    anchors.insert(
+        ConstraintAnchor::Entity(anchor),
+        Section::new((10f32, 10f32), (50f32, 20f32)),
+    );
+    let a_constraints = Constraints::new()
+        .with(
+            LayoutConstraint::new(ConstraintAttribute::Left)
+                .equal_to(anchor, ConstraintAttribute::Right)
+                .plus(8f32),
+        )
+        .with(
+            LayoutConstraint::new(ConstraintAttribute::Width)
+                .constant(ConstraintRelation::Equal, 100f32),
+        )
+        .with(
+            LayoutConstraint::new(ConstraintAttribute::CenterY)
+                .equal_to(ConstraintAnchor::Viewport, ConstraintAttribute::CenterY),
+        );
+    let b_constraints = Constraints::new()
+        .with(
+            LayoutConstraint::new(ConstraintAttribute::Width)
+                .equal_to(a, ConstraintAttribute::Width),
+        )
+        .with(
+            LayoutConstraint::new(ConstraintAttribute::Width)
+                .constant(ConstraintRelation::Equal, 20f32),
+        )
+        .with(LayoutConstraint::new(ConstraintAttribute::Top).equal_to(
+            bevy_ecs::entity::Entity::from_raw(9),
+            ConstraintAttribute::Top,
+        ));
+    let current = Section::new((0f32, 0f32), (10f32, 40f32));
+    let (solved, diagnostics) = solve_constraints(
+        &[(a, &a_constraints, current), (b, &b_constraints, current)],
+        &anchors,
+    );
+    assert_eq!(solved[&a].position, Position::new(68f32, 130f32));
+    assert_eq!(solved[&a].area, Area::new(100f32, 40f32));
+    assert_eq!(solved[&b].area.width, 100f32);
+    assert_eq!(diagnostics.0.len(), 2);
+    assert_eq!(diagnostics.0[0].issue, ConstraintIssue::Unsatisfiable);
+    assert_eq!(diagnostics.0[0].index, 1);
+    assert!(matches!(
+        diagnostics.0[1].issue,
+        ConstraintIssue::MissingAnchor(_)
+    ));
+}
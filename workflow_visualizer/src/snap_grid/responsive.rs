@@ -1,47 +1,65 @@
 use crate::snap_grid::Breakpoint;
-use crate::{GridLocation, GridPoint, GridRange, GridView};
+use crate::{GridLocation, GridPoint, GridRange, GridView, Orientation};
 use bevy_ecs::component::Component;
-#[derive(Copy, Clone)]
+/// Value that can differ per breakpoint, falling back to the nearest smaller breakpoint.
+/// Landscape variants take precedence when the grid is in a landscape orientation.
+#[derive(Clone)]
 pub struct ResponsiveUnit<T: Copy + Clone> {
-    pub mobile: T,
-    pub tablet: Option<T>,
-    pub desktop: Option<T>,
-    pub workstation: Option<T>,
+    units: Vec<Option<T>>,
+    landscape: Vec<Option<T>>,
 }
 impl<T: Copy + Clone> ResponsiveUnit<T> {
-    pub fn new(mobile: T) -> Self {
+    pub fn new(base: T) -> Self {
         Self {
-            mobile,
-            tablet: None,
-            desktop: None,
-            workstation: None,
+            units: vec![Some(base)],
+            landscape: vec![],
         }
     }
-    pub fn current(&self, breakpoint: Breakpoint) -> T {
-        match breakpoint {
-            Breakpoint::Mobile => self.mobile,
-            Breakpoint::Tablet => self.tablet.unwrap_or(self.mobile),
-            Breakpoint::Desktop => self.desktop.unwrap_or(self.tablet.unwrap_or(self.mobile)),
-            Breakpoint::Workstation => self
-                .workstation
-                .unwrap_or(self.desktop.unwrap_or(self.tablet.unwrap_or(self.mobile))),
+    pub fn current(&self, breakpoint: Breakpoint, orientation: Orientation) -> T {
+        if let Orientation::Landscape(_) = orientation {
+            if let Some(unit) = Self::fallback(self.landscape.as_slice(), breakpoint) {
+                return unit;
+            }
         }
+        Self::fallback(self.units.as_slice(), breakpoint).expect("responsive base")
     }
-    pub fn with_tablet(mut self, unit: T) -> Self {
-        self.tablet.replace(unit);
-        self
+    fn fallback(units: &[Option<T>], breakpoint: Breakpoint) -> Option<T> {
+        if units.is_empty() {
+            return None;
+        }
+        let end = breakpoint.0.min(units.len() - 1);
+        units[..=end].iter().rev().find_map(|unit| *unit)
     }
-    pub fn with_desktop(mut self, unit: T) -> Self {
-        self.desktop.replace(unit);
+    fn set(units: &mut Vec<Option<T>>, breakpoint: Breakpoint, unit: T) {
+        if units.len() <= breakpoint.0 {
+            units.resize(breakpoint.0 + 1, None);
+        }
+        units[breakpoint.0].replace(unit);
+    }
+    pub fn with_breakpoint(mut self, breakpoint: Breakpoint, unit: T) -> Self {
+        Self::set(&mut self.units, breakpoint, unit);
         self
     }
-    pub fn with_workstation(mut self, unit: T) -> Self {
-        self.workstation.replace(unit);
+    pub fn with_tablet(self, unit: T) -> Self {
+        self.with_breakpoint(Breakpoint::TABLET, unit)
+    }
+    pub fn with_desktop(self, unit: T) -> Self {
+        self.with_breakpoint(Breakpoint::DESKTOP, unit)
+    }
+    pub fn with_workstation(self, unit: T) -> Self {
+        self.with_breakpoint(Breakpoint::WORKSTATION, unit)
+    }
+    /// unit used in landscape from the first breakpoint up
+    pub fn with_landscape(self, unit: T) -> Self {
+        self.with_landscape_breakpoint(Breakpoint::MOBILE, unit)
+    }
+    pub fn with_landscape_breakpoint(mut self, breakpoint: Breakpoint, unit: T) -> Self {
+        Self::set(&mut self.landscape, breakpoint, unit);
         self
     }
 }
 pub type ResponsiveGridLocation = ResponsiveUnit<GridLocation>;
-#[derive(Component, Copy, Clone)]
+#[derive(Component, Clone)]
 pub struct ResponsiveGridPoint {
     pub x: ResponsiveGridLocation,
     pub y: ResponsiveGridLocation,
@@ -51,14 +69,19 @@ impl ResponsiveGridPoint {
     pub fn new(x: ResponsiveGridLocation, y: ResponsiveGridLocation) -> Self {
         Self { x, y }
     }
-    pub fn current(&self, breakpoint: Breakpoint) -> GridPoint {
-        let x = self.x.current(breakpoint);
-        let y = self.y.current(breakpoint);
+    pub fn current(
+        &self,
+        horizontal_breakpoint: Breakpoint,
+        vertical_breakpoint: Breakpoint,
+        orientation: Orientation,
+    ) -> GridPoint {
+        let x = self.x.current(horizontal_breakpoint, orientation);
+        let y = self.y.current(vertical_breakpoint, orientation);
         GridPoint::new(x, y)
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct ResponsiveGridRange {
     pub begin: ResponsiveGridLocation,
     pub end: ResponsiveGridLocation,
@@ -68,22 +91,30 @@ impl ResponsiveGridRange {
     pub fn new(begin: ResponsiveGridLocation, end: ResponsiveGridLocation) -> Self {
         Self { begin, end }
     }
-    pub fn current(&self, breakpoint: Breakpoint) -> GridRange {
-        GridRange::new(self.begin.current(breakpoint), self.end.current(breakpoint))
+    pub fn current(&self, breakpoint: Breakpoint, orientation: Orientation) -> GridRange {
+        GridRange::new(
+            self.begin.current(breakpoint, orientation),
+            self.end.current(breakpoint, orientation),
+        )
     }
 }
 
-#[derive(Component, Copy, Clone)]
+#[derive(Component, Clone)]
 pub struct ResponsiveGridView {
     pub horizontal: ResponsiveGridRange,
     pub vertical: ResponsiveGridRange,
 }
 
 impl ResponsiveGridView {
-    pub fn current(&self, horizontal_breakpoint: Breakpoint) -> GridView {
+    pub fn current(
+        &self,
+        horizontal_breakpoint: Breakpoint,
+        vertical_breakpoint: Breakpoint,
+        orientation: Orientation,
+    ) -> GridView {
         GridView::new(
-            self.horizontal.current(horizontal_breakpoint),
-            self.vertical.current(horizontal_breakpoint),
+            self.horizontal.current(horizontal_breakpoint, orientation),
+            self.vertical.current(vertical_breakpoint, orientation),
         )
     }
     pub fn new(horizontal: ResponsiveGridRange, vertical: ResponsiveGridRange) -> Self {
@@ -0,0 +1,384 @@
+use crate::{
+    Area, CoordinateUnit, FontRegistry, GridDirection, IconScale, IconTag, ImageSizes, ImageTag,
+    InterfaceContext, Position, ResourceHandle, ScaleFactor, Section, TextFont,
+    TextLetterDimensions, TextScale, TextValue, TextWrapStyle,
+};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::prelude::{Changed, Commands, Or, ParamSet, Query, Ref, Res, With, Without};
+use std::collections::{HashMap, HashSet};
+
+/// Size an element would like to occupy before a stack grows or shrinks it.
+/// Measured automatically for Text, Icons and Images; insert it manually for anything else.
+#[derive(Component, Copy, Clone, PartialEq, Default, Debug)]
+pub struct IntrinsicArea(pub Area<InterfaceContext>);
+
+/// Where items sit along the cross axis of a stack (or within a line of a `Wrap`).
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum StackAlignment {
+    #[default]
+    Start,
+    Center,
+    End,
+    Stretch,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StackLayout {
+    /// items placed left to right
+    HStack,
+    /// items placed top to bottom
+    VStack,
+    /// items placed left to right, breaking onto new lines when out of room
+    Wrap,
+}
+
+/// How a single item participates in a `Stack`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct StackItem {
+    pub grow: f32,
+    pub shrink: f32,
+    /// overrides the measured `IntrinsicArea` when set
+    pub basis: Option<Area<InterfaceContext>>,
+}
+
+impl StackItem {
+    pub fn new() -> Self {
+        Self {
+            grow: 0f32,
+            shrink: 1f32,
+            basis: None,
+        }
+    }
+    pub fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow.max(0f32);
+        self
+    }
+    pub fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0f32);
+        self
+    }
+    pub fn with_basis<A: Into<Area<InterfaceContext>>>(mut self, basis: A) -> Self {
+        self.basis.replace(basis.into());
+        self
+    }
+}
+
+impl Default for StackItem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// item with its main and cross axis lengths
+type SizedItem = (Entity, StackItem, CoordinateUnit, CoordinateUnit);
+
+/// Sequential layout of child entities within the section of the entity holding it.
+/// Useful for lists of variable length items that do not map to fixed grid markers.
+#[derive(Component, Clone)]
+pub struct Stack {
+    pub layout: StackLayout,
+    pub spacing: CoordinateUnit,
+    pub alignment: StackAlignment,
+    pub items: Vec<(Entity, StackItem)>,
+}
+
+impl Stack {
+    pub fn new(layout: StackLayout, spacing: CoordinateUnit, alignment: StackAlignment) -> Self {
+        Self {
+            layout,
+            spacing,
+            alignment,
+            items: vec![],
+        }
+    }
+    pub fn hstack(spacing: CoordinateUnit, alignment: StackAlignment) -> Self {
+        Self::new(StackLayout::HStack, spacing, alignment)
+    }
+    pub fn vstack(spacing: CoordinateUnit, alignment: StackAlignment) -> Self {
+        Self::new(StackLayout::VStack, spacing, alignment)
+    }
+    pub fn wrap(spacing: CoordinateUnit, alignment: StackAlignment) -> Self {
+        Self::new(StackLayout::Wrap, spacing, alignment)
+    }
+    pub fn add(&mut self, entity: Entity, item: StackItem) {
+        self.items.retain(|(e, _)| *e != entity);
+        self.items.push((entity, item));
+    }
+    pub fn remove(&mut self, entity: Entity) {
+        self.items.retain(|(e, _)| *e != entity);
+    }
+    fn direction(&self) -> GridDirection {
+        match self.layout {
+            StackLayout::VStack => GridDirection::Vertical,
+            _ => GridDirection::Horizontal,
+        }
+    }
+    /// computes the section of every item; `intrinsic` supplies sizes for items without a basis
+    pub fn arrange(
+        &self,
+        section: Section<InterfaceContext>,
+        intrinsic: &HashMap<Entity, Area<InterfaceContext>>,
+    ) -> HashMap<Entity, Section<InterfaceContext>> {
+        let direction = self.direction();
+        let sized = self
+            .items
+            .iter()
+            .map(|(entity, item)| {
+                let area = item
+                    .basis
+                    .or_else(|| intrinsic.get(entity).copied())
+                    .unwrap_or_default();
+                let (main, cross) = split(direction, area);
+                (*entity, *item, main, cross)
+            })
+            .collect::<Vec<SizedItem>>();
+        let (available_main, available_cross) = split(direction, section.area);
+        let lines = match self.layout {
+            StackLayout::Wrap => self.break_lines(sized, available_main),
+            _ => vec![sized],
+        };
+        let mut arrangement = HashMap::new();
+        let mut cross_offset = 0f32;
+        for line in lines.iter() {
+            let line_cross = match self.layout {
+                StackLayout::Wrap => line
+                    .iter()
+                    .fold(0f32, |acc, (_, _, _, cross)| acc.max(*cross)),
+                _ => available_cross,
+            };
+            let mains = self.flex(line, available_main, self.layout != StackLayout::Wrap);
+            let mut main_offset = 0f32;
+            for ((entity, _, _, cross), main) in line.iter().zip(mains) {
+                let (cross, cross_start) = match self.alignment {
+                    StackAlignment::Start => (*cross, 0f32),
+                    StackAlignment::Center => (*cross, (line_cross - cross) / 2f32),
+                    StackAlignment::End => (*cross, line_cross - cross),
+                    StackAlignment::Stretch => (line_cross, 0f32),
+                };
+                let (x, y) = join(direction, main_offset, cross_offset + cross_start);
+                let (width, height) = join(direction, main, cross);
+                arrangement.insert(
+                    *entity,
+                    Section::new(
+                        (section.position.x + x, section.position.y + y),
+                        (width, height),
+                    ),
+                );
+                main_offset += main + self.spacing;
+            }
+            cross_offset += line_cross + self.spacing;
+        }
+        arrangement
+    }
+    fn break_lines(&self, sized: Vec<SizedItem>, available: CoordinateUnit) -> Vec<Vec<SizedItem>> {
+        let mut lines = vec![];
+        let mut current = vec![];
+        let mut used = 0f32;
+        for item in sized {
+            let needed = if current.is_empty() {
+                item.2
+            } else {
+                used + self.spacing + item.2
+            };
+            if needed > available && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                used = item.2;
+            } else {
+                used = needed;
+            }
+            current.push(item);
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+    fn flex(
+        &self,
+        line: &[SizedItem],
+        available: CoordinateUnit,
+        allow_shrink: bool,
+    ) -> Vec<CoordinateUnit> {
+        let spacing = self.spacing * line.len().saturating_sub(1) as f32;
+        let used = line.iter().map(|(_, _, main, _)| *main).sum::<f32>() + spacing;
+        let free = available - used;
+        let total_grow = line.iter().map(|(_, item, _, _)| item.grow).sum::<f32>();
+        let total_shrink = line
+            .iter()
+            .map(|(_, item, main, _)| item.shrink * main)
+            .sum::<f32>();
+        line.iter()
+            .map(|(_, item, main, _)| {
+                if free > 0f32 && total_grow > 0f32 {
+                    main + free * item.grow / total_grow
+                } else if free < 0f32 && allow_shrink && total_shrink > 0f32 {
+                    (main + free * item.shrink * main / total_shrink).max(0f32)
+                } else {
+                    *main
+                }
+            })
+            .collect()
+    }
+}
+
+fn split(
+    direction: GridDirection,
+    area: Area<InterfaceContext>,
+) -> (CoordinateUnit, CoordinateUnit) {
+    match direction {
+        GridDirection::Horizontal => (area.width, area.height),
+        GridDirection::Vertical => (area.height, area.width),
+    }
+}
+
+fn join(
+    direction: GridDirection,
+    main: CoordinateUnit,
+    cross: CoordinateUnit,
+) -> (CoordinateUnit, CoordinateUnit) {
+    match direction {
+        GridDirection::Horizontal => (main, cross),
+        GridDirection::Vertical => (cross, main),
+    }
+}
+
+/// measures text wrapped to the width of the stack holding it, again when
+/// that width changes
+pub(crate) fn measure_text(
+    text: Query<(
+        Entity,
+        Ref<TextValue>,
+        &TextScale,
+        Ref<TextWrapStyle>,
+        Option<Ref<TextLetterDimensions>>,
+        Option<&TextFont>,
+        Option<&IntrinsicArea>,
+    )>,
+    stacks: Query<(Ref<Stack>, Ref<Area<InterfaceContext>>)>,
+    fonts: Res<FontRegistry>,
+    scale_factor: Res<ScaleFactor>,
+    mut cmd: Commands,
+) {
+    let mut wrap_widths = HashMap::<Entity, CoordinateUnit>::new();
+    let mut restacked = HashSet::<Entity>::new();
+    for (stack, area) in stacks.iter() {
+        let resized = stack.is_changed() || area.is_changed();
+        for (entity, _) in stack.items.iter() {
+            wrap_widths.insert(*entity, area.width);
+            if resized {
+                restacked.insert(*entity);
+            }
+        }
+    }
+    for (entity, value, scale, wrap_style, letters, font, current) in text.iter() {
+        let changed = value.is_changed()
+            || wrap_style.is_changed()
+            || letters.is_some_and(|letters| letters.is_changed());
+        if !changed && !restacked.contains(&entity) {
+            continue;
+        }
+        let primary = fonts.index(font.map(|font| font.0).unwrap_or(FontRegistry::DEFAULT));
+        let wrap = wrap_widths
+            .get(&entity)
+            .map(|width| (*wrap_style, width * scale_factor.factor()));
+        let measured = fonts.measure(primary, value.0.as_str(), scale.px(), wrap);
+        let measured = IntrinsicArea(Area::new(
+            measured.width / scale_factor.factor(),
+            measured.height / scale_factor.factor(),
+        ));
+        if current != Some(&measured) {
+            cmd.entity(entity).insert(measured);
+        }
+    }
+}
+
+pub(crate) fn measure_icons(
+    icons: Query<(Entity, &IconScale), Changed<IconScale>>,
+    mut cmd: Commands,
+) {
+    for (entity, scale) in icons.iter() {
+        cmd.entity(entity).insert(IntrinsicArea(scale.as_area()));
+    }
+}
+
+pub(crate) fn measure_images(
+    images: Query<
+        (Entity, &ResourceHandle, Option<&IntrinsicArea>),
+        (With<ImageTag>, Without<IconTag>),
+    >,
+    sizes: Res<ImageSizes>,
+    scale_factor: Res<ScaleFactor>,
+    mut cmd: Commands,
+) {
+    if !sizes.is_changed() {
+        return;
+    }
+    for (entity, handle, current) in images.iter() {
+        if let Some(size) = sizes.0.get(handle) {
+            let measured = IntrinsicArea(Area::new(
+                size.width / scale_factor.factor(),
+                size.height / scale_factor.factor(),
+            ));
+            if current != Some(&measured) {
+                cmd.entity(entity).insert(measured);
+            }
+        }
+    }
+}
+
+pub(crate) fn arrange_stacks(
+    mut stacks: ParamSet<(
+        Query<(
+            Entity,
+            &Stack,
+            &Position<InterfaceContext>,
+            &Area<InterfaceContext>,
+        )>,
+        Query<
+            Entity,
+            (
+                With<Stack>,
+                Or<(
+                    Changed<Stack>,
+                    Changed<Position<InterfaceContext>>,
+                    Changed<Area<InterfaceContext>>,
+                )>,
+            ),
+        >,
+        Query<(
+            &mut Position<InterfaceContext>,
+            Option<&mut Area<InterfaceContext>>,
+        )>,
+    )>,
+    intrinsic: Query<(Entity, &IntrinsicArea)>,
+    changed_intrinsic: Query<(), Changed<IntrinsicArea>>,
+) {
+    let intrinsic_changed = !changed_intrinsic.is_empty();
+    let changed = stacks.p1().iter().collect::<Vec<Entity>>();
+    let sizes = intrinsic
+        .iter()
+        .map(|(entity, area)| (entity, area.0))
+        .collect::<HashMap<Entity, Area<InterfaceContext>>>();
+    let mut arrangements = vec![];
+    for (entity, stack, pos, area) in stacks.p0().iter() {
+        if intrinsic_changed || changed.contains(&entity) {
+            arrangements.push(stack.arrange(Section::new(*pos, *area), &sizes));
+        }
+    }
+    for arrangement in arrangements {
+        for (entity, section) in arrangement {
+            if let Ok((mut pos, area)) = stacks.p2().get_mut(entity) {
+                if *pos != section.position {
+                    *pos = section.position;
+                }
+                if let Some(mut area) = area {
+                    if *area != section.area {
+                        *area = section.area;
+                    }
+                }
+            }
+        }
+    }
+}
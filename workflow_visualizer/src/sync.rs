@@ -39,6 +39,9 @@ pub(crate) fn set_sync_points(visualizer: &mut Visualizer) {
             .chain(),
     );
     visualizer.task(Visualizer::TASK_STARTUP).add_systems((
+        apply_deferred
+            .after(SyncPoint::Event)
+            .before(SyncPoint::Initialization),
         apply_deferred
             .after(SyncPoint::Initialization)
             .before(SyncPoint::PostInitialization),
@@ -1,78 +1,274 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use crate::text::component::{Glyph, GlyphId};
+use tracing::warn;
 
-use crate::texture_atlas::{AtlasLocation, TextureCoordinates};
-use crate::{Area, NumericalContext};
+use crate::gfx::GfxSurface;
+use crate::text::component::{Glyph, GlyphId, SdfText};
+use crate::text::distance_field::distance_field;
+use crate::text::font::FontRegistry;
+use crate::texture_atlas::{
+    AtlasShelves, AtlasTexture, AtlasTextureDimensions, TextureBindGroup, TextureCoordinates,
+};
+use crate::{Area, NumericalContext, Section};
 
-pub(crate) struct AtlasGlyphReference {
-    pub(crate) count: u32,
+pub(crate) type Bitmap = Vec<u8>;
+/// How a `GlyphCache` turns glyphs into atlas bitmaps
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum GlyphRasterization {
+    /// coverage at the requested px
+    Coverage,
+    /// `SdfText` distance fields at `SdfText::GLYPH_PX`, shared by every px
+    DistanceField,
 }
 
-impl AtlasGlyphReference {
-    pub(crate) fn new() -> Self {
-        Self { count: 0 }
-    }
-    pub(crate) fn increment(&mut self) {
-        self.count += 1;
+impl GlyphRasterization {
+    /// the cache key a glyph is stored under
+    pub(crate) fn key(&self, glyph_id: GlyphId) -> GlyphId {
+        match self {
+            GlyphRasterization::Coverage => glyph_id,
+            GlyphRasterization::DistanceField => GlyphId {
+                px: SdfText::GLYPH_PX,
+                ..glyph_id
+            },
+        }
     }
-    #[allow(unused)]
-    pub(crate) fn decrement(&mut self) {
-        let sub_value = (self.count == 0) as u32;
-        self.count -= sub_value;
+    fn rasterize(
+        &self,
+        fonts: &FontRegistry,
+        font_index: usize,
+        glyph_id: GlyphId,
+    ) -> (Area<NumericalContext>, Bitmap) {
+        let font = &fonts.font_slice()[font_index];
+        let (metrics, bitmap) = font.rasterize_config(self.key(glyph_id));
+        match self {
+            GlyphRasterization::Coverage => ((metrics.width, metrics.height).into(), bitmap),
+            GlyphRasterization::DistanceField => {
+                let spread = SdfText::SPREAD as usize;
+                let field = distance_field(&bitmap, metrics.width, metrics.height, spread);
+                (
+                    (metrics.width + 2 * spread, metrics.height + 2 * spread).into(),
+                    field,
+                )
+            }
+        }
     }
 }
-
-pub(crate) type Bitmap = Vec<u8>;
-pub(crate) struct AtlasGlyphReferences {
-    pub(crate) references: HashMap<GlyphId, AtlasGlyphReference>,
+/// A rasterized glyph resident in the `GlyphCache`
+pub(crate) struct CachedGlyph {
+    pub(crate) coords: TextureCoordinates,
+    pub(crate) area: Area<NumericalContext>,
+    /// `None` while glyphs still in use fill the page; drawn blank until a repack fits it
+    slot: Option<Section<NumericalContext>>,
+    font_index: usize,
+    pub(crate) references: u32,
+    pub(crate) last_used: u64,
+}
+/// Glyph page shared by every text render group, shelf packing glyphs of any size.
+/// Glyphs are reference counted by the keys that display them; unreferenced glyphs
+/// stay resident until their space is needed and are then evicted least recently used
+/// first. When that is not enough the page is repacked, rasterizing resident glyphs
+/// again, so bitmaps are not kept once uploaded.
+pub(crate) struct GlyphCache {
+    texture: AtlasTexture,
+    pub(crate) bind_group: TextureBindGroup,
+    shelves: AtlasShelves,
+    /// empty texels unplaced glyphs sample
+    blank: Section<NumericalContext>,
+    pub(crate) glyphs: HashMap<GlyphId, CachedGlyph>,
+    write_queue: HashMap<GlyphId, (Section<NumericalContext>, Bitmap)>,
+    pub(crate) rasterization: GlyphRasterization,
+    /// set when the page was repacked and glyphs' coordinates changed
+    pub(crate) rebuilt: bool,
+    tick: u64,
 }
 
-impl AtlasGlyphReferences {
-    pub(crate) fn new() -> Self {
+impl GlyphCache {
+    /// side of the page; smaller where the device cannot hold it
+    pub(crate) const PAGE_DIMENSION: u32 = 2048;
+    pub(crate) fn new(
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        rasterization: GlyphRasterization,
+    ) -> Self {
+        let dimension = Self::PAGE_DIMENSION.min(gfx.device.limits().max_texture_dimension_2d);
+        let (texture, bind_group, shelves, blank) = Self::page(gfx, layout, dimension as f32);
         Self {
-            references: HashMap::new(),
+            texture,
+            bind_group,
+            shelves,
+            blank,
+            glyphs: HashMap::new(),
+            write_queue: HashMap::new(),
+            rasterization,
+            rebuilt: false,
+            tick: 0,
         }
     }
-}
-pub(crate) struct AtlasWriteQueue {
-    pub(crate) queue: HashMap<AtlasLocation, (TextureCoordinates, Area<NumericalContext>, Bitmap)>,
-}
-
-impl AtlasWriteQueue {
-    pub(crate) fn new() -> Self {
-        Self {
-            queue: HashMap::new(),
+    fn page(
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        dimension: f32,
+    ) -> (
+        AtlasTexture,
+        TextureBindGroup,
+        AtlasShelves,
+        Section<NumericalContext>,
+    ) {
+        let texture = AtlasTexture::new(
+            gfx,
+            AtlasTextureDimensions {
+                dimensions: Area::new(dimension, dimension),
+            },
+            wgpu::TextureFormat::R8Unorm,
+        );
+        let bind_group = TextureBindGroup::new(gfx, layout, &texture.view);
+        let mut shelves = AtlasShelves::new((dimension, dimension));
+        let blank = shelves.allocate(Area::new(1.0, 1.0)).expect("blank texel");
+        let blank = Section::new(blank.position, (1.0, 1.0));
+        (texture, bind_group, shelves, blank)
+    }
+    /// advances the clock used to order evictions
+    pub(crate) fn tick(&mut self) {
+        self.tick += 1;
+    }
+    /// references `glyph`, rasterizing it into the page if it is not resident
+    pub(crate) fn acquire(
+        &mut self,
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        fonts: &FontRegistry,
+        glyph: &Glyph,
+    ) {
+        let glyph_id = self.rasterization.key(glyph.id);
+        if let Some(cached) = self.glyphs.get_mut(&glyph_id) {
+            cached.references += 1;
+            cached.last_used = self.tick;
+            return;
+        }
+        let (area, bitmap) = self
+            .rasterization
+            .rasterize(fonts, glyph.font_index, glyph_id);
+        let slot = self.allocate(gfx, layout, fonts, area);
+        if let Some(slot) = slot {
+            self.write_queue.insert(glyph_id, (slot, bitmap));
         }
+        self.glyphs.insert(
+            glyph_id,
+            CachedGlyph {
+                coords: self.coordinates_of(slot, area),
+                area,
+                slot,
+                font_index: glyph.font_index,
+                references: 1,
+                last_used: self.tick,
+            },
+        );
     }
-}
-pub(crate) struct AtlasAddQueue {
-    pub(crate) queue: HashSet<Glyph>,
-}
-
-impl AtlasAddQueue {
-    pub(crate) fn new() -> Self {
-        Self {
-            queue: HashSet::new(),
+    /// drops a reference; the glyph becomes evictable when none remain
+    pub(crate) fn release(&mut self, glyph_id: GlyphId) {
+        if let Some(cached) = self.glyphs.get_mut(&self.rasterization.key(glyph_id)) {
+            cached.references = cached.references.saturating_sub(1);
+            cached.last_used = self.tick;
         }
     }
-}
-pub(crate) struct AtlasGlyphs {
-    pub(crate) glyphs: HashMap<
-        GlyphId,
-        (
-            TextureCoordinates,
-            Area<NumericalContext>,
-            AtlasLocation,
-            Bitmap,
-        ),
-    >,
-}
-
-impl AtlasGlyphs {
-    pub(crate) fn new() -> Self {
-        Self {
-            glyphs: HashMap::new(),
+    pub(crate) fn get(&self, glyph_id: GlyphId) -> Option<&CachedGlyph> {
+        self.glyphs.get(&self.rasterization.key(glyph_id))
+    }
+    /// uploads queued bitmaps
+    pub(crate) fn write(&mut self, gfx: &GfxSurface) {
+        for (glyph_id, (slot, bitmap)) in self.write_queue.drain() {
+            if let Some(cached) = self.glyphs.get(&glyph_id) {
+                self.texture
+                    .write::<u8>(slot.position, bitmap.as_slice(), cached.area, gfx);
+            }
+        }
+    }
+    /// a slot for `area`, evicting unreferenced glyphs and then repacking the page
+    /// when it is full; `None` when glyphs in use leave no room
+    fn allocate(
+        &mut self,
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        fonts: &FontRegistry,
+        area: Area<NumericalContext>,
+    ) -> Option<Section<NumericalContext>> {
+        loop {
+            if let Some(slot) = self.shelves.allocate(area) {
+                return Some(slot);
+            }
+            if !self.evict() {
+                break;
+            }
+        }
+        self.repack(gfx, layout, fonts);
+        let slot = self.shelves.allocate(area);
+        if slot.is_none() {
+            warn!("glyph page full; a glyph of {:?} is drawn blank", area);
+        }
+        slot
+    }
+    /// frees the least recently used unreferenced glyph; false when there is none
+    fn evict(&mut self) -> bool {
+        let candidate = self
+            .glyphs
+            .iter()
+            .filter(|(_, cached)| cached.references == 0)
+            .min_by_key(|(_, cached)| cached.last_used)
+            .map(|(glyph_id, _)| *glyph_id);
+        let Some(glyph_id) = candidate else {
+            return false;
+        };
+        let cached = self.glyphs.remove(&glyph_id).unwrap();
+        self.write_queue.remove(&glyph_id);
+        if let Some(slot) = cached.slot {
+            self.shelves.free(slot);
+        }
+        true
+    }
+    fn coordinates_of(
+        &self,
+        slot: Option<Section<NumericalContext>>,
+        area: Area<NumericalContext>,
+    ) -> TextureCoordinates {
+        let section = match slot {
+            Some(slot) => Section::new(slot.position, area),
+            None => self.blank,
+        };
+        TextureCoordinates::from_section(
+            section,
+            AtlasTextureDimensions {
+                dimensions: self.shelves.dimensions,
+            },
+        )
+    }
+    /// recreates the page and places every resident glyph again, tallest first,
+    /// rasterizing them anew
+    fn repack(&mut self, gfx: &GfxSurface, layout: &wgpu::BindGroupLayout, fonts: &FontRegistry) {
+        let (texture, bind_group, shelves, blank) =
+            Self::page(gfx, layout, self.shelves.dimensions.width);
+        self.texture = texture;
+        self.bind_group = bind_group;
+        self.shelves = shelves;
+        self.blank = blank;
+        self.write_queue.clear();
+        let mut resident = self
+            .glyphs
+            .iter()
+            .map(|(glyph_id, cached)| (*glyph_id, cached.area.height))
+            .collect::<Vec<(GlyphId, f32)>>();
+        resident.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (glyph_id, _) in resident {
+            let font_index = self.glyphs.get(&glyph_id).unwrap().font_index;
+            let (area, bitmap) = self.rasterization.rasterize(fonts, font_index, glyph_id);
+            let slot = self.shelves.allocate(area);
+            if let Some(slot) = slot {
+                self.write_queue.insert(glyph_id, (slot, bitmap));
+            }
+            let coords = self.coordinates_of(slot, area);
+            let cached = self.glyphs.get_mut(&glyph_id).unwrap();
+            cached.slot = slot;
+            cached.coords = coords;
         }
+        self.rebuilt = true;
     }
 }
@@ -1,10 +1,16 @@
 use bevy_ecs::prelude::IntoSystemConfigs;
 
+use crate::interaction::resolve;
+use crate::text::font::FontRegistry;
 use crate::text::renderer::TextRenderer;
+use crate::text::selection::{
+    copy_with_affordance, highlight_selection, offer_copy, select_with_keyboard,
+    select_with_pointer, CopyAffordance, PointerSelection, SelectionFocus,
+};
 use crate::text::system::{
-    color_diff, create_render_groups, filter, layer_diff, letter_differential, manage, place,
-    position_diff, pull_differences, render_group_differences, resolve_draw_section_on_resize,
-    scale_change, setup, visible_section_diff,
+    auto_scale, color_diff, create_render_groups, decorate, filter, layer_diff,
+    letter_differential, letter_dimensions, manage, place, position_diff, pull_differences,
+    render_group_differences, scale_change, setup, sync_rich_text, visible_section_diff,
 };
 use crate::{Attach, SyncPoint, Visualizer};
 
@@ -18,11 +24,32 @@ impl Attach for TextAttachment {
             .task(Visualizer::TASK_STARTUP)
             .add_systems((setup.in_set(SyncPoint::Initialization),));
         engen.job.task(Visualizer::TASK_MAIN).add_systems((
+            sync_rich_text.in_set(SyncPoint::Preparation),
+            select_with_pointer
+                .in_set(SyncPoint::Preparation)
+                .before(resolve),
+            select_with_keyboard
+                .in_set(SyncPoint::Preparation)
+                .after(select_with_pointer),
+            offer_copy
+                .in_set(SyncPoint::Preparation)
+                .after(select_with_pointer),
+            copy_with_affordance
+                .in_set(SyncPoint::Preparation)
+                .after(resolve),
+            highlight_selection.in_set(SyncPoint::PostResolve),
+            auto_scale
+                .in_set(SyncPoint::Reconfigure)
+                .before(scale_change),
             scale_change.in_set(SyncPoint::Reconfigure),
+            letter_dimensions
+                .in_set(SyncPoint::Reconfigure)
+                .after(scale_change),
             place.in_set(SyncPoint::Resolve),
             manage.in_set(SyncPoint::Resolve),
             filter.in_set(SyncPoint::Resolve).after(place),
             letter_differential.in_set(SyncPoint::Resolve).after(filter),
+            decorate.in_set(SyncPoint::PostResolve),
             position_diff.in_set(SyncPoint::PushDiff),
             visible_section_diff.in_set(SyncPoint::PushDiff),
             color_diff.in_set(SyncPoint::PushDiff),
@@ -32,7 +59,19 @@ impl Attach for TextAttachment {
         engen.job.task(Visualizer::TASK_RENDER_MAIN).add_systems((
             create_render_groups.in_set(SyncPoint::Preparation),
             render_group_differences.in_set(SyncPoint::Resolve),
-            resolve_draw_section_on_resize.in_set(SyncPoint::Resolve),
         ));
+        engen.job.container.insert_resource(FontRegistry::new());
+        engen
+            .job
+            .container
+            .insert_resource(SelectionFocus::default());
+        engen
+            .job
+            .container
+            .insert_resource(PointerSelection::default());
+        engen
+            .job
+            .container
+            .insert_resource(CopyAffordance::default());
     }
 }
@@ -0,0 +1,364 @@
+use fontdue::layout::{GlyphPosition, HorizontalAlign, LinePosition};
+use fontdue::Metrics;
+use unicode_bidi::{bidi_class, get_base_direction, BidiClass, BidiInfo, Direction, Level};
+
+use crate::text::component::TextDirection;
+use crate::text::font::FontRegistry;
+use crate::Key;
+
+/// whether laying out `character` can involve right-to-left reordering
+fn is_right_to_left(character: char) -> bool {
+    matches!(
+        bidi_class(character),
+        BidiClass::R | BidiClass::AL | BidiClass::AN
+    )
+}
+/// paragraph level forced by `direction`; None resolves it per paragraph (P2, P3)
+fn default_level(direction: TextDirection) -> Option<Level> {
+    match direction {
+        TextDirection::Auto => None,
+        TextDirection::LeftToRight => Some(Level::ltr()),
+        TextDirection::RightToLeft => Some(Level::rtl()),
+    }
+}
+/// whether the first paragraph of `text` flows right-to-left
+pub(crate) fn right_to_left(text: &str, direction: TextDirection) -> bool {
+    match direction {
+        TextDirection::Auto => get_base_direction(text) == Direction::Rtl,
+        TextDirection::LeftToRight => false,
+        TextDirection::RightToLeft => true,
+    }
+}
+/// Embedding levels of a Text's characters, resolved by the Unicode Bidirectional Algorithm.
+pub(crate) struct BidiLevels<'text> {
+    info: BidiInfo<'text>,
+    /// byte offset of every character, then the length of the text
+    offsets: Vec<usize>,
+}
+
+impl<'text> BidiLevels<'text> {
+    pub(crate) fn new(text: &'text str, direction: TextDirection) -> Self {
+        let offsets = text
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain([text.len()])
+            .collect();
+        Self {
+            info: BidiInfo::new(text, default_level(direction)),
+            offsets,
+        }
+    }
+    /// resolved level of the character at `index`
+    pub(crate) fn level(&self, index: usize) -> Level {
+        self.info.levels[self.offsets[index]]
+    }
+    /// indices of the characters `start..=end`, one line, in display order left to right (L1, L2)
+    pub(crate) fn visual_order(&self, start: usize, end: usize) -> Vec<usize> {
+        let line = self.offsets[start]..self.offsets[end + 1];
+        let Some(paragraph) = self
+            .info
+            .paragraphs
+            .iter()
+            .find(|paragraph| paragraph.range.contains(&line.start))
+        else {
+            return (0..=end - start).collect();
+        };
+        let levels = self.info.reordered_levels(paragraph, line);
+        let line_levels = (start..=end)
+            .map(|index| levels[self.offsets[index]])
+            .collect::<Vec<Level>>();
+        BidiInfo::reorder_visual(&line_levels)
+    }
+}
+/// mirrored glyph of paired punctuation displayed right-to-left (L4)
+fn mirror(character: char) -> Option<char> {
+    let mirrored = match character {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        '‹' => '›',
+        '›' => '‹',
+        _ => return None,
+    };
+    Some(mirrored)
+}
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Joining {
+    None,
+    Right,
+    Dual,
+    Causing,
+    Transparent,
+}
+/// joining behavior and isolated presentation form of an Arabic letter;
+/// final, initial and medial forms follow the isolated one in that order
+fn joining(character: char) -> (Joining, Option<u32>) {
+    let dual = |isolated: u32| (Joining::Dual, Some(isolated));
+    let right = |isolated: u32| (Joining::Right, Some(isolated));
+    match character as u32 {
+        0x0622 => right(0xFE81),
+        0x0623 => right(0xFE83),
+        0x0624 => right(0xFE85),
+        0x0625 => right(0xFE87),
+        0x0626 => dual(0xFE89),
+        0x0627 => right(0xFE8D),
+        0x0628 => dual(0xFE8F),
+        0x0629 => right(0xFE93),
+        0x062A => dual(0xFE95),
+        0x062B => dual(0xFE99),
+        0x062C => dual(0xFE9D),
+        0x062D => dual(0xFEA1),
+        0x062E => dual(0xFEA5),
+        0x062F => right(0xFEA9),
+        0x0630 => right(0xFEAB),
+        0x0631 => right(0xFEAD),
+        0x0632 => right(0xFEAF),
+        0x0633 => dual(0xFEB1),
+        0x0634 => dual(0xFEB5),
+        0x0635 => dual(0xFEB9),
+        0x0636 => dual(0xFEBD),
+        0x0637 => dual(0xFEC1),
+        0x0638 => dual(0xFEC5),
+        0x0639 => dual(0xFEC9),
+        0x063A => dual(0xFECD),
+        0x0640 => (Joining::Causing, None),
+        0x0641 => dual(0xFED1),
+        0x0642 => dual(0xFED5),
+        0x0643 => dual(0xFED9),
+        0x0644 => dual(0xFEDD),
+        0x0645 => dual(0xFEE1),
+        0x0646 => dual(0xFEE5),
+        0x0647 => dual(0xFEE9),
+        0x0648 => right(0xFEED),
+        0x0649 => right(0xFEEF),
+        0x064A => dual(0xFEF1),
+        0x067E => dual(0xFB56),
+        0x0686 => dual(0xFB7A),
+        0x0698 => right(0xFB8A),
+        0x06A9 => dual(0xFB8E),
+        0x06AF => dual(0xFB92),
+        0x06CC => dual(0xFBFC),
+        _ if bidi_class(character) == BidiClass::NSM => (Joining::Transparent, None),
+        _ => (Joining::None, None),
+    }
+}
+/// isolated lam-alef ligature for the alef following a lam
+fn lam_alef(alef: char) -> Option<u32> {
+    match alef as u32 {
+        0x0622 => Some(0xFEF5),
+        0x0623 => Some(0xFEF7),
+        0x0625 => Some(0xFEF9),
+        0x0627 => Some(0xFEFB),
+        _ => None,
+    }
+}
+/// What shaping did to a character's glyph
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Shaped {
+    Unchanged,
+    /// drawn with this presentation form
+    Form(char),
+    /// drawn as part of the preceding ligature
+    Absorbed,
+}
+/// Arabic contextual forms and lam-alef ligatures, one entry per character.
+/// `supported` reports whether the character at an index can be drawn as a form.
+pub(crate) fn shape<F: Fn(usize, char) -> bool>(chars: &[char], supported: F) -> Vec<Shaped> {
+    let mut shaped = vec![Shaped::Unchanged; chars.len()];
+    let joinings = chars
+        .iter()
+        .map(|character| joining(*character).0)
+        .collect::<Vec<Joining>>();
+    let neighbor = |index: usize, forward: bool| -> Option<usize> {
+        let mut candidate = index;
+        loop {
+            candidate = match forward {
+                true => candidate + 1,
+                false => candidate.checked_sub(1)?,
+            };
+            if *joinings.get(candidate)? != Joining::Transparent {
+                return Some(candidate);
+            }
+        }
+    };
+    let joins_forward = |index: Option<usize>| {
+        index.map_or(false, |index| {
+            matches!(joinings[index], Joining::Dual | Joining::Causing)
+        })
+    };
+    let joins_backward = |index: Option<usize>| {
+        index.map_or(false, |index| {
+            matches!(
+                joinings[index],
+                Joining::Right | Joining::Dual | Joining::Causing
+            )
+        })
+    };
+    let form = |isolated: u32, offset: u32| char::from_u32(isolated + offset);
+    for index in 0..chars.len() {
+        if shaped[index] == Shaped::Absorbed {
+            continue;
+        }
+        let (joining, isolated) = joining(chars[index]);
+        let Some(isolated) = isolated else {
+            continue;
+        };
+        let previous = neighbor(index, false);
+        let next = neighbor(index, true);
+        let joined_before = joins_forward(previous);
+        if chars[index] == '\u{0644}' {
+            let ligature = next
+                .and_then(|next| lam_alef(chars[next]).map(|ligature| (next, ligature)))
+                .and_then(|(next, ligature)| {
+                    form(ligature, joined_before as u32).map(|ligature| (next, ligature))
+                })
+                .filter(|(_, ligature)| supported(index, *ligature));
+            if let Some((alef, ligature)) = ligature {
+                shaped[index] = Shaped::Form(ligature);
+                shaped[alef] = Shaped::Absorbed;
+                continue;
+            }
+        }
+        let joined_after = joining == Joining::Dual && joins_backward(next);
+        let offset = match (joined_before, joined_after) {
+            (false, false) => 0,
+            (true, false) => 1,
+            (false, true) => 2,
+            (true, true) => 3,
+        };
+        if let Some(presentation) = form(isolated, offset).filter(|form| supported(index, *form)) {
+            shaped[index] = Shaped::Form(presentation);
+        }
+    }
+    shaped
+}
+pub(crate) fn metrics_of(fonts: &FontRegistry, glyph: &GlyphPosition<()>) -> Metrics {
+    fonts.font_slice()[glyph.font_index].metrics_indexed(glyph.key.glyph_index, glyph.key.px)
+}
+/// Shapes Arabic, mirrors paired punctuation and moves each line's glyphs into display order.
+/// Glyphs keep their logical order in `placement`; only their positions and raster keys change.
+pub(crate) fn arrange(
+    placement: &mut [(Key, GlyphPosition<()>)],
+    lines: &[LinePosition],
+    fonts: &FontRegistry,
+    direction: TextDirection,
+    horizontal: HorizontalAlign,
+) {
+    let chars = placement
+        .iter()
+        .map(|(_, glyph)| glyph.parent)
+        .collect::<Vec<char>>();
+    if direction != TextDirection::RightToLeft && !chars.iter().copied().any(is_right_to_left) {
+        return;
+    }
+    let text = chars.iter().collect::<String>();
+    let levels = BidiLevels::new(&text, direction);
+    let original = placement
+        .iter()
+        .map(|(_, glyph)| metrics_of(fonts, glyph))
+        .collect::<Vec<Metrics>>();
+    let lookup = |index: usize, character: char| {
+        fonts.font_slice()[placement[index].1.font_index].lookup_glyph_index(character)
+    };
+    let shaped = shape(&chars, |index, form| lookup(index, form) != 0);
+    let substitutes = (0..chars.len())
+        .map(|index| {
+            let substitute = match shaped[index] {
+                Shaped::Form(form) => Some(form),
+                _ if levels.level(index).is_rtl() => mirror(chars[index]),
+                _ => None,
+            };
+            substitute
+                .map(|character| lookup(index, character))
+                .filter(|glyph_index| *glyph_index != 0)
+        })
+        .collect::<Vec<Option<u16>>>();
+    let mut advances = vec![0f32; placement.len()];
+    for (index, (_, glyph)) in placement.iter_mut().enumerate() {
+        if shaped[index] == Shaped::Absorbed {
+            glyph.width = 0;
+            glyph.height = 0;
+            continue;
+        }
+        if let Some(glyph_index) = substitutes[index] {
+            let before = original[index].bounds;
+            let baseline = glyph.y - (-before.height - before.ymin).floor();
+            glyph.key.glyph_index = glyph_index;
+            let metrics = metrics_of(fonts, glyph);
+            glyph.y = baseline + (-metrics.bounds.height - metrics.bounds.ymin).floor();
+            glyph.width = metrics.width;
+            glyph.height = metrics.height;
+        }
+        advances[index] = metrics_of(fonts, glyph).advance_width;
+    }
+    let alignment = match horizontal {
+        HorizontalAlign::Left => 0f32,
+        HorizontalAlign::Center => 0.5f32,
+        HorizontalAlign::Right => 1f32,
+    };
+    for line in lines.iter() {
+        let end = line.glyph_end.min(placement.len().saturating_sub(1));
+        if line.glyph_start >= placement.len() || line.glyph_start > end {
+            continue;
+        }
+        let range = line.glyph_start..=end;
+        let start = placement[line.glyph_start].1.x - original[line.glyph_start].bounds.xmin;
+        let before = original[range.clone()]
+            .iter()
+            .map(|metrics| metrics.advance_width)
+            .sum::<f32>();
+        let after = advances[range.clone()].iter().sum::<f32>();
+        let mut pen = start + (before - after) * alignment;
+        for offset in levels.visual_order(line.glyph_start, end) {
+            let index = line.glyph_start + offset;
+            let glyph = &mut placement[index].1;
+            let xmin = match shaped[index] {
+                Shaped::Absorbed => 0f32,
+                _ => metrics_of(fonts, glyph).bounds.xmin,
+            };
+            glyph.x = (pen + xmin).floor();
+            pen += advances[index];
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn reordering() {
+    let text = "abc אבג 123.";
+    assert!(!right_to_left(text, TextDirection::Auto));
+    let levels = BidiLevels::new(text, TextDirection::Auto);
+    assert_eq!(
+        levels.visual_order(0, 11),
+        vec![0, 1, 2, 3, 8, 9, 10, 7, 6, 5, 4, 11]
+    );
+    let text = "שלום, world";
+    assert!(right_to_left(text, TextDirection::Auto));
+    let levels = BidiLevels::new(text, TextDirection::Auto);
+    assert_eq!(
+        levels.visual_order(0, 10),
+        vec![6, 7, 8, 9, 10, 5, 4, 3, 2, 1, 0]
+    );
+    let text = "abc \u{5D0}  \nxy";
+    let levels = BidiLevels::new(text, TextDirection::RightToLeft);
+    // trailing whitespace takes the paragraph level (L1)
+    assert_eq!(levels.visual_order(0, 7), vec![7, 6, 5, 4, 3, 0, 1, 2]);
+    assert_eq!(levels.visual_order(8, 9), vec![0, 1]);
+    let shaped = shape(&"سلام".chars().collect::<Vec<char>>(), |_, _| true);
+    assert_eq!(
+        shaped,
+        vec![
+            Shaped::Form('\u{FEB3}'),
+            Shaped::Form('\u{FEFC}'),
+            Shaped::Absorbed,
+            Shaped::Form('\u{FEE1}'),
+        ]
+    );
+}
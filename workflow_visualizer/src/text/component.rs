@@ -1,11 +1,13 @@
 use std::collections::{HashMap, HashSet};
 
-use bevy_ecs::prelude::{Bundle, Component};
-use fontdue::layout::{CoordinateSystem, GlyphPosition, Layout, WrapStyle};
+use bevy_ecs::prelude::{Bundle, Component, Entity};
+use fontdue::layout::{
+    CoordinateSystem, GlyphPosition, HorizontalAlign, Layout, VerticalAlign, WrapStyle,
+};
 
 use crate::{
-    Area, Color, DeviceContext, EnableVisibility, InterfaceContext, Key, Layer, MonoSpacedFont,
-    NumericalContext, Position, Section, Tag, VisibleSection,
+    Area, Color, CoordinateUnit, DeviceContext, EnableVisibility, InterfaceContext, Key, Layer,
+    MonoSpacedFont, NumericalContext, Position, ResourceHandle, Section, Tag, VisibleSection,
 };
 pub type TextTag = Tag<Text>;
 /// Entry point to spawn a Text element
@@ -26,6 +28,9 @@ pub struct Text {
     pub(crate) text_letter_dimensions: TextLetterDimensions,
     pub(crate) text_grid_placement: TextGridPlacement,
     pub(crate) text_line_structure: TextLineStructure,
+    pub(crate) glyph_styles: GlyphStyles,
+    pub(crate) decorations: TextDecorations,
+    pub(crate) line_extents: LineExtents,
     pub(crate) section: Section<InterfaceContext>,
 }
 
@@ -52,6 +57,9 @@ impl Text {
             text_letter_dimensions: TextLetterDimensions(Area::default()),
             text_grid_placement: TextGridPlacement(HashMap::new()),
             text_line_structure: TextLineStructure::new(),
+            glyph_styles: GlyphStyles::default(),
+            decorations: TextDecorations::default(),
+            line_extents: LineExtents::default(),
             scale: scale.into(),
             section: Section::default(),
         }
@@ -68,20 +76,177 @@ impl TextWrapStyle {
         TextWrapStyle(WrapStyle::Word)
     }
 }
+/// `Start` and `End` follow the text's direction; `Left` and `Right` do not
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TextHorizontalAlignment {
+    #[default]
+    Start,
+    Left,
+    Center,
+    Right,
+    End,
+}
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TextVerticalAlignment {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+/// Where lines sit within the Text's section; top-start when absent
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TextAlignment {
+    pub horizontal: TextHorizontalAlignment,
+    pub vertical: TextVerticalAlignment,
+}
+impl TextAlignment {
+    pub fn new(horizontal: TextHorizontalAlignment, vertical: TextVerticalAlignment) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+    pub fn centered() -> Self {
+        Self::new(
+            TextHorizontalAlignment::Center,
+            TextVerticalAlignment::Middle,
+        )
+    }
+    pub(crate) fn horizontal_align(&self, right_to_left: bool) -> HorizontalAlign {
+        match (self.horizontal, right_to_left) {
+            (TextHorizontalAlignment::Left, _)
+            | (TextHorizontalAlignment::Start, false)
+            | (TextHorizontalAlignment::End, true) => HorizontalAlign::Left,
+            (TextHorizontalAlignment::Center, _) => HorizontalAlign::Center,
+            (TextHorizontalAlignment::Right, _)
+            | (TextHorizontalAlignment::Start, true)
+            | (TextHorizontalAlignment::End, false) => HorizontalAlign::Right,
+        }
+    }
+    pub(crate) fn vertical_align(&self) -> VerticalAlign {
+        match self.vertical {
+            TextVerticalAlignment::Top => VerticalAlign::Top,
+            TextVerticalAlignment::Middle => VerticalAlign::Middle,
+            TextVerticalAlignment::Bottom => VerticalAlign::Bottom,
+        }
+    }
+}
+/// Base direction of a Text's paragraphs.
+/// `Auto` takes it from the first strong character of each paragraph.
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TextDirection {
+    #[default]
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+/// What happens to lines that do not fit
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TextOverflowMode {
+    /// glyphs outside the visible section are dropped by `filter`
+    #[default]
+    Clip,
+    /// the last kept line ends in `…`
+    Ellipsis,
+    /// the last glyphs of the last kept line fade out
+    Fade,
+}
+/// Limits the Text to the lines that fit its section (or `max_lines`) and marks the cut
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TextOverflow {
+    pub mode: TextOverflowMode,
+    pub max_lines: Option<u32>,
+}
+impl TextOverflow {
+    pub const FADE_GLYPHS: usize = 4;
+    pub const ELLIPSIS: char = '\u{2026}';
+    pub fn new(mode: TextOverflowMode) -> Self {
+        Self {
+            mode,
+            max_lines: None,
+        }
+    }
+    pub fn clip() -> Self {
+        Self::new(TextOverflowMode::Clip)
+    }
+    pub fn ellipsis() -> Self {
+        Self::new(TextOverflowMode::Ellipsis)
+    }
+    pub fn fade() -> Self {
+        Self::new(TextOverflowMode::Fade)
+    }
+    pub fn with_max_lines(mut self, max_lines: u32) -> Self {
+        self.max_lines.replace(max_lines.max(1));
+        self
+    }
+}
+/// Outline drawn around `SdfText` glyphs; `width` in interface units
+#[derive(Copy, Clone, PartialEq)]
+pub struct TextOutline {
+    pub color: Color,
+    pub width: CoordinateUnit,
+}
+/// Soft shadow cast by `SdfText` glyphs; `offset` and `softness` in interface units
+#[derive(Copy, Clone, PartialEq)]
+pub struct TextShadow {
+    pub color: Color,
+    pub offset: Position<InterfaceContext>,
+    pub softness: CoordinateUnit,
+}
+/// Renders a Text from signed distance fields generated once per glyph,
+/// so it stays sharp at any `TextScale` without re-rasterizing.
+/// Outlines and shadows reach at most `SdfText::SPREAD / SdfText::GLYPH_PX` of the text scale
+/// past each glyph; anything further is cut off.
+#[derive(Component, Copy, Clone, PartialEq, Default)]
+pub struct SdfText {
+    pub outline: Option<TextOutline>,
+    pub shadow: Option<TextShadow>,
+}
+impl SdfText {
+    /// size distance fields are generated at
+    pub const GLYPH_PX: f32 = 48f32;
+    /// px of distance encoded past the glyph edge at `GLYPH_PX`
+    pub const SPREAD: u32 = 8;
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_outline<C: Into<Color>>(mut self, color: C, width: CoordinateUnit) -> Self {
+        self.outline.replace(TextOutline {
+            color: color.into(),
+            width,
+        });
+        self
+    }
+    pub fn with_shadow<C: Into<Color>, P: Into<Position<InterfaceContext>>>(
+        mut self,
+        color: C,
+        offset: P,
+        softness: CoordinateUnit,
+    ) -> Self {
+        self.shadow.replace(TextShadow {
+            color: color.into(),
+            offset: offset.into(),
+            softness,
+        });
+        self
+    }
+}
 pub(crate) type GlyphId = fontdue::layout::GlyphRasterConfig;
 #[derive(Clone, Hash, Eq, PartialEq, Debug)]
 pub(crate) struct Glyph {
     pub(crate) character: char,
     pub(crate) scale: TextScale,
     pub(crate) id: GlyphId,
+    pub(crate) font_index: usize,
 }
 
 impl Glyph {
-    pub(crate) fn new(character: char, scale: TextScale, id: GlyphId) -> Self {
+    pub(crate) fn new(character: char, scale: TextScale, id: GlyphId, font_index: usize) -> Self {
         Self {
             character,
             scale,
             id,
+            font_index,
         }
     }
 }
@@ -89,6 +254,103 @@ impl Glyph {
 /// The text to render
 #[derive(Component, Clone)]
 pub struct TextValue(pub String);
+/// Styled run of text within a `RichText`; unset fields use the entity's own values
+#[derive(Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub color: Option<Color>,
+    pub scale: Option<TextScale>,
+    pub font: Option<ResourceHandle>,
+    pub underline: bool,
+    pub strikethrough: bool,
+}
+impl TextSpan {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            scale: None,
+            font: None,
+            underline: false,
+            strikethrough: false,
+        }
+    }
+    pub fn with_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.color.replace(color.into());
+        self
+    }
+    pub fn with_scale<TS: Into<TextScale>>(mut self, scale: TS) -> Self {
+        self.scale.replace(scale.into());
+        self
+    }
+    pub fn with_font(mut self, font: ResourceHandle) -> Self {
+        self.font.replace(font);
+        self
+    }
+    pub fn underlined(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+    pub fn struck_through(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+}
+impl<S: Into<String>> From<S> for TextSpan {
+    fn from(value: S) -> Self {
+        TextSpan::new(value)
+    }
+}
+/// Spans laid out together in one wrapped block.
+/// Insert on a `Text` entity; its `TextValue` is kept as the concatenated plain text.
+#[derive(Component, Clone, Default)]
+pub struct RichText(pub Vec<TextSpan>);
+impl RichText {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+    pub fn with<T: Into<TextSpan>>(mut self, span: T) -> Self {
+        self.push(span);
+        self
+    }
+    pub fn push<T: Into<TextSpan>>(&mut self, span: T) {
+        self.0.push(span.into());
+    }
+    pub fn plain(&self) -> String {
+        self.0.iter().map(|span| span.text.as_str()).collect()
+    }
+}
+/// per-glyph styling from the last placement; `RichText` span indices and overflow fade
+#[derive(Component, Default)]
+pub(crate) struct GlyphStyles {
+    pub(crate) spans: HashMap<Key, usize>,
+    pub(crate) fades: HashMap<Key, f32>,
+}
+impl GlyphStyles {
+    pub(crate) fn color(&self, rich_text: Option<&RichText>, base: Color, key: Key) -> Color {
+        let mut color = self
+            .spans
+            .get(&key)
+            .and_then(|span| rich_text?.0.get(*span)?.color)
+            .unwrap_or(base);
+        if let Some(fade) = self.fades.get(&key) {
+            color.alpha *= fade;
+        }
+        color
+    }
+}
+/// top and bottom of each kept line in device pixels, relative to the Text's position
+#[derive(Component, Default)]
+pub(crate) struct LineExtents(pub(crate) Vec<(f32, f32)>);
+/// underline/strikethrough segments from the last placement and the lines drawing them
+#[derive(Component, Default)]
+pub(crate) struct TextDecorations {
+    pub(crate) segments: Vec<(Position<DeviceContext>, Position<DeviceContext>, usize)>,
+    pub(crate) lines: Vec<Entity>,
+}
+/// marks a `Line` drawing a decoration of the Text it holds
+#[derive(Component, Copy, Clone)]
+pub(crate) struct TextDecorationLine(pub(crate) Entity);
 /// The scale of the Text
 #[derive(Component, Clone, Copy, Hash, Eq, PartialEq, Debug)]
 pub struct TextScale(pub u32);
@@ -102,9 +364,25 @@ impl TextScale {
         self.0 as f32
     }
 }
+/// Recomputes `TextScale` whenever the Text's section changes (resize, breakpoint switch)
+/// so the string fits it when wrapped per its `TextWrapStyle`, staying within `min..=max`
+#[derive(Component, Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AutoScale {
+    pub min: TextScale,
+    pub max: TextScale,
+}
+impl AutoScale {
+    pub fn new<TS: Into<TextScale>>(min: TS, max: TS) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+}
 /// The size of a letter at an alignment
 /// This holds `Area<DeviceContext>` as character dimensions are
-/// scaled before rasterization to prevent visual artifacts of stretching
+/// scaled before rasterization to prevent visual artifacts of stretching.
+/// For proportional fonts this is only the nominal cell of the entity's font.
 #[derive(Component, Copy, Clone)]
 pub struct TextLetterDimensions(pub Area<DeviceContext>);
 /// where the letter is in the Text Grid
@@ -128,6 +406,43 @@ impl TextGridLocation {
             .floor() as u32;
         Self::new(x, y)
     }
+    /// Grid location under `position` using the placed glyphs instead of a fixed cell,
+    /// for proportional fonts or text mixing fallback fonts.
+    /// Glyphs are hit in display order; `x` stays the logical index within the line.
+    pub fn from_glyphs(
+        position: Position<DeviceContext>,
+        glyphs: &[GlyphPosition<()>],
+        line_structure: &TextLineStructure,
+    ) -> Self {
+        let mut location = Self::new(0, 0);
+        for (y, (start, end)) in line_structure.0.iter().enumerate() {
+            let line = glyphs
+                .get(*start as usize..=(*end as usize).min(glyphs.len().saturating_sub(1)))
+                .unwrap_or_default();
+            let top = line
+                .iter()
+                .filter(|glyph| glyph.height != 0)
+                .map(|glyph| glyph.y)
+                .fold(f32::MAX, f32::min);
+            if y != 0 && top > position.y {
+                break;
+            }
+            let x = line
+                .iter()
+                .enumerate()
+                .filter(|(_, glyph)| glyph.x <= position.x)
+                .max_by(|(a, left), (b, right)| left.x.total_cmp(&right.x).then(a.cmp(b)))
+                .or_else(|| {
+                    line.iter()
+                        .enumerate()
+                        .min_by(|(_, left), (_, right)| left.x.total_cmp(&right.x))
+                })
+                .map(|(x, _)| x)
+                .unwrap_or_default();
+            location = Self::new(x as u32, y as u32);
+        }
+        location
+    }
 }
 /// Mapping of what glyphs keys are placed where in the TextGrid
 #[derive(Component, Debug)]
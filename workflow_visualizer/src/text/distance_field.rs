@@ -0,0 +1,128 @@
+use crate::text::atlas::Bitmap;
+
+const FAR: f32 = 1e20;
+
+/// Signed distance field of a coverage bitmap, padded by `spread` on every side.
+/// 0.5 (128) marks the glyph edge; values rise inside and fall outside,
+/// saturating `spread` px from the edge.
+pub(crate) fn distance_field(
+    coverage: &[u8],
+    width: usize,
+    height: usize,
+    spread: usize,
+) -> Bitmap {
+    let padded_width = width + 2 * spread;
+    let padded_height = height + 2 * spread;
+    let inside = |x: usize, y: usize| -> bool {
+        if x < spread || y < spread || x >= width + spread || y >= height + spread {
+            return false;
+        }
+        coverage[(y - spread) * width + (x - spread)] >= 128
+    };
+    let to_inside = squared_distances(padded_width, padded_height, |x, y| inside(x, y));
+    let to_outside = squared_distances(padded_width, padded_height, |x, y| !inside(x, y));
+    let mut field = Vec::with_capacity(padded_width * padded_height);
+    for index in 0..padded_width * padded_height {
+        let distance = if to_inside[index] == 0f32 {
+            to_outside[index].sqrt() - 0.5
+        } else {
+            0.5 - to_inside[index].sqrt()
+        };
+        let value = (0.5 + distance / (2 * spread.max(1)) as f32).clamp(0f32, 1f32);
+        field.push((value * 255f32).round() as u8);
+    }
+    field
+}
+/// squared euclidean distance of every cell to the nearest `feature` cell
+fn squared_distances<F: Fn(usize, usize) -> bool>(
+    width: usize,
+    height: usize,
+    feature: F,
+) -> Vec<f32> {
+    let mut grid = vec![FAR; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            if feature(x, y) {
+                grid[y * width + x] = 0f32;
+            }
+        }
+    }
+    let length = width.max(height);
+    let mut column = vec![0f32; length];
+    let mut distances = vec![0f32; length];
+    let mut parabolas = vec![0usize; length];
+    let mut bounds = vec![0f32; length + 1];
+    for x in 0..width {
+        for y in 0..height {
+            column[y] = grid[y * width + x];
+        }
+        transform(
+            &column[..height],
+            &mut distances[..height],
+            &mut parabolas,
+            &mut bounds,
+        );
+        for y in 0..height {
+            grid[y * width + x] = distances[y];
+        }
+    }
+    for y in 0..height {
+        column[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        transform(
+            &column[..width],
+            &mut distances[..width],
+            &mut parabolas,
+            &mut bounds,
+        );
+        grid[y * width..(y + 1) * width].copy_from_slice(&distances[..width]);
+    }
+    grid
+}
+/// one dimensional distance transform as the lower envelope of parabolas (Felzenszwalb & Huttenlocher)
+fn transform(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+    let intersection = |q: usize, p: usize| -> f32 {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32
+    };
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = -FAR;
+    z[1] = FAR;
+    for q in 1..n {
+        let mut s = intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = FAR;
+    }
+    k = 0;
+    for (q, distance) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let offset = q as f32 - v[k] as f32;
+        *distance = offset * offset + f[v[k]];
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn square_field() {
+    let coverage = vec![255u8; 10 * 10];
+    let field = distance_field(&coverage, 10, 10, 4);
+    assert_eq!(field.len(), 18 * 18);
+    let at = |x: usize, y: usize| field[y * 18 + x];
+    assert!(at(9, 9) == 255);
+    assert!(at(0, 0) == 0);
+    assert!(at(4, 9) > 128);
+    assert!(at(3, 9) < 128);
+    assert!(at(5, 9) > at(4, 9));
+    assert!(at(2, 9) < at(3, 9));
+}
@@ -1,11 +1,13 @@
+use std::collections::HashMap;
 use std::ops::{Deref, Sub};
 
-use bevy_ecs::prelude::Resource;
+use bevy_ecs::prelude::{Component, Resource};
+use fontdue::layout::WrapStyle;
 use fontdue::{Font as fdFont, FontSettings};
 
 use crate::coord::NumericalContext;
-use crate::text::component::TextScale;
-use crate::{Area, CoordinateUnit, InterfaceContext, Position, Section};
+use crate::text::component::{TextScale, TextWrapStyle};
+use crate::{Area, CoordinateUnit, InterfaceContext, Position, ResourceHandle, Section};
 
 #[derive(Resource)]
 pub struct MonoSpacedFont {
@@ -121,6 +123,196 @@ impl MonoSpacedFont {
         (metrics.advance_width.ceil(), height.ceil()).into()
     }
 }
+/// Fonts available to `Text` entities, looked up by `ResourceHandle`.
+/// Characters missing from an entity's font are drawn with the first fallback that has them.
+#[derive(Resource)]
+pub struct FontRegistry {
+    fonts: Vec<fdFont>,
+    handles: HashMap<ResourceHandle, usize>,
+    fallbacks: Vec<usize>,
+}
+
+impl FontRegistry {
+    /// handle of the bundled JetBrains Mono, used by `Text` without a `TextFont`
+    pub const DEFAULT: ResourceHandle = ResourceHandle(0);
+    pub fn new() -> Self {
+        let mut registry = Self {
+            fonts: vec![],
+            handles: HashMap::new(),
+            fallbacks: vec![],
+        };
+        registry
+            .load(
+                Self::DEFAULT,
+                include_bytes!("JetBrainsMono-Regular.ttf").as_slice(),
+                MonoSpacedFont::DEFAULT_OPT_SCALE,
+            )
+            .expect("text font creation");
+        registry
+    }
+    /// parses `font_data` and stores it under `handle`, replacing any font already there
+    pub fn load<Data: Deref<Target = [u8]>, T: Into<TextScale>>(
+        &mut self,
+        handle: ResourceHandle,
+        font_data: Data,
+        opt_scale: T,
+    ) -> Result<(), &'static str> {
+        let font = fdFont::from_bytes(
+            font_data,
+            FontSettings {
+                scale: opt_scale.into().px(),
+                ..FontSettings::default()
+            },
+        )?;
+        match self.handles.get(&handle) {
+            Some(index) => self.fonts[*index] = font,
+            None => {
+                self.handles.insert(handle, self.fonts.len());
+                self.fonts.push(font);
+            }
+        }
+        Ok(())
+    }
+    /// appends a loaded font to the fallback chain consulted after an entity's own font
+    pub fn add_fallback(&mut self, handle: ResourceHandle) {
+        if let Some(index) = self.handles.get(&handle).copied() {
+            if !self.fallbacks.contains(&index) {
+                self.fallbacks.push(index);
+            }
+        }
+    }
+    pub fn font(&self, handle: ResourceHandle) -> Option<&fdFont> {
+        self.handles.get(&handle).map(|index| &self.fonts[*index])
+    }
+    pub fn font_slice(&self) -> &[fdFont] {
+        self.fonts.as_slice()
+    }
+    /// index into `font_slice` for `handle`; unknown handles use the default font
+    pub fn index(&self, handle: ResourceHandle) -> usize {
+        self.handles
+            .get(&handle)
+            .or_else(|| self.handles.get(&Self::DEFAULT))
+            .copied()
+            .unwrap_or_default()
+    }
+    /// font index that renders `character`, preferring `primary`
+    pub fn resolve(&self, primary: usize, character: char) -> usize {
+        if character.is_control() || self.fonts[primary].lookup_glyph_index(character) != 0 {
+            return primary;
+        }
+        self.fallbacks
+            .iter()
+            .copied()
+            .find(|index| self.fonts[*index].lookup_glyph_index(character) != 0)
+            .unwrap_or(primary)
+    }
+    /// splits `text` into byte ranges that share a resolved font
+    pub(crate) fn runs(&self, primary: usize, text: &str) -> Vec<(usize, usize, usize)> {
+        let mut runs: Vec<(usize, usize, usize)> = vec![];
+        for (offset, character) in text.char_indices() {
+            let index = self.resolve(primary, character);
+            let end = offset + character.len_utf8();
+            match runs.last_mut() {
+                Some(run) if run.2 == index => run.1 = end,
+                _ => runs.push((offset, end, index)),
+            }
+        }
+        runs
+    }
+    /// nominal cell of a font; the advance of 'a' with the line height
+    pub fn character_dimensions(&self, index: usize, px: f32) -> Area<NumericalContext> {
+        let font = &self.fonts[index];
+        let metrics = font.metrics('a', px);
+        let height = font
+            .horizontal_line_metrics(px)
+            .map(|metrics| metrics.new_line_size)
+            .unwrap_or(px);
+        (metrics.advance_width.ceil(), height.ceil()).into()
+    }
+    /// advance of `character` after fallback
+    pub fn advance(&self, primary: usize, character: char, px: f32) -> f32 {
+        self.fonts[self.resolve(primary, character)]
+            .metrics(character, px)
+            .advance_width
+    }
+    /// width of the longest line and number of lines, wrapping lines wider than
+    /// the width of `wrap` in its style
+    pub fn measure(
+        &self,
+        primary: usize,
+        text: &str,
+        px: f32,
+        wrap: Option<(TextWrapStyle, f32)>,
+    ) -> Area<NumericalContext> {
+        let widths = text
+            .lines()
+            .flat_map(|line| self.wrapped_widths(primary, line, px, wrap))
+            .collect::<Vec<f32>>();
+        let longest = widths.iter().copied().fold(0f32, f32::max);
+        let height = self.character_dimensions(primary, px).height;
+        (longest.ceil(), height * widths.len().max(1) as f32).into()
+    }
+    /// widths of the lines `line` wraps into; words move to the next line whole
+    /// unless they are wider than a line themselves, spaces may hang past the end
+    fn wrapped_widths(
+        &self,
+        primary: usize,
+        line: &str,
+        px: f32,
+        wrap: Option<(TextWrapStyle, f32)>,
+    ) -> Vec<f32> {
+        let mut widths = vec![];
+        let mut width = 0f32;
+        // width before the last run of spaces and where the word after it starts
+        let mut last_break = None;
+        let mut previous_space = false;
+        for character in line.chars() {
+            let advance = self.advance(primary, character, px);
+            let space = character.is_whitespace();
+            if let Some((style, max_width)) = wrap {
+                if !space && width > 0f32 && width + advance > max_width {
+                    match (style.0, last_break.take()) {
+                        (WrapStyle::Word, Some((kept, next))) => {
+                            widths.push(kept);
+                            width -= next;
+                        }
+                        _ => {
+                            widths.push(width);
+                            width = 0f32;
+                        }
+                    }
+                }
+            }
+            if space && !previous_space {
+                last_break = Some((width, width));
+            }
+            width += advance;
+            if let Some((_, next)) = last_break.as_mut().filter(|_| space) {
+                *next = width;
+            }
+            previous_space = space;
+        }
+        widths.push(width);
+        widths
+    }
+}
+
+impl Default for FontRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Font a `Text` entity is laid out with, as registered in the `FontRegistry`
+#[derive(Component, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TextFont(pub ResourceHandle);
+
+impl Default for TextFont {
+    fn default() -> Self {
+        Self(FontRegistry::DEFAULT)
+    }
+}
+
 pub struct TextSectionDescriptor {
     pub scale: TextScale,
     pub section: Section<InterfaceContext>,
@@ -157,3 +349,51 @@ fn tester() {
         println!("Scale: {:?} dims: {:?}", x, dims);
     }
 }
+#[cfg(test)]
+#[test]
+fn fallback_resolution() {
+    let mut registry = FontRegistry::new();
+    let primary = registry.index(FontRegistry::DEFAULT);
+    let secondary = ResourceHandle(1);
+    registry
+        .load(
+            secondary,
+            include_bytes!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/NotoSansHebrew-Regular.ttf"
+            ))
+            .as_slice(),
+            MonoSpacedFont::DEFAULT_OPT_SCALE,
+        )
+        .unwrap();
+    registry.add_fallback(secondary);
+    let fallback = registry.index(secondary);
+    assert_ne!(primary, fallback);
+    assert_eq!(registry.resolve(primary, 'a'), primary);
+    // JetBrains Mono has no Hebrew
+    assert_eq!(registry.resolve(primary, '\u{5D0}'), fallback);
+    // neither font has it so it stays with the primary
+    assert_eq!(registry.resolve(primary, '\u{1F600}'), primary);
+    assert_eq!(registry.runs(primary, "ab c"), vec![(0, 4, primary)]);
+    assert_eq!(
+        registry.runs(primary, "ab \u{5E9}\u{5DC}\u{5D5}\u{5DD} c"),
+        vec![(0, 3, primary), (3, 11, fallback), (11, 13, primary)]
+    );
+    assert!(registry
+        .load(ResourceHandle(2), [0u8; 4].as_slice(), 12)
+        .is_err());
+    let measured = registry.measure(primary, "ab\nabcd", 20f32, None);
+    let cell = registry.character_dimensions(primary, 20f32);
+    assert_eq!(measured.height, cell.height * 2f32);
+    assert_eq!(measured.width, (cell.width * 4f32).ceil());
+    // monospaced, so a line of five and a half cells fits "ab cd" but not "ab cd e"
+    let line = Some((TextWrapStyle::word(), cell.width * 5.5));
+    let wrapped = registry.measure(primary, "ab cd ef gh", 20f32, line);
+    assert_eq!(wrapped.height, cell.height * 2f32);
+    assert!(wrapped.width <= cell.width * 5.5);
+    let letters = Some((TextWrapStyle::letter(), cell.width * 3.5));
+    let wrapped = registry.measure(primary, "abcdefg", 20f32, letters);
+    assert_eq!(wrapped.height, cell.height * 3f32);
+    let long_word = registry.measure(primary, "abcdefg", 20f32, line);
+    assert_eq!(long_word.height, cell.height * 2f32);
+}
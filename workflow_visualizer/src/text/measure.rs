@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
+use fontdue::Font as fdFont;
+
+use crate::text::component::{AutoScale, TextScale, TextWrapStyle};
+use crate::text::font::{FontRegistry, MonoSpacedFont};
+use crate::{
+    Area, CoordinateUnit, DeviceContext, InterfaceContext, Position, ResourceHandle, Section,
+};
+
+/// A placed glyph of a measurement
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MeasuredGlyph {
+    pub character: char,
+    /// byte offset of the character in the measured string
+    pub byte_offset: usize,
+    pub section: Section<InterfaceContext>,
+}
+
+/// A line of a measurement, after wrapping
+#[derive(Clone, PartialEq, Debug)]
+pub struct MeasuredLine {
+    /// bytes of the measured string on this line
+    pub bytes: Range<usize>,
+    /// indices into `TextMeasurement::glyphs`
+    pub glyphs: Range<usize>,
+    pub width: CoordinateUnit,
+}
+
+/// Result of `TextMeasurer::measure`
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct TextMeasurement {
+    pub lines: Vec<MeasuredLine>,
+    pub glyphs: Vec<MeasuredGlyph>,
+    pub area: Area<InterfaceContext>,
+}
+
+impl TextMeasurement {
+    /// byte offsets where a new line starts because of wrapping or a hard break
+    pub fn line_breaks(&self) -> Vec<usize> {
+        self.lines
+            .iter()
+            .skip(1)
+            .map(|line| line.bytes.start)
+            .collect()
+    }
+}
+
+/// Lays out text exactly as a `Text` entity would, without spawning entities or touching the GPU.
+/// Sizes are in `InterfaceContext`; `scale_factor` is applied as it is before rasterization.
+pub struct TextMeasurer {
+    layout: Layout,
+    scale_factor: CoordinateUnit,
+}
+
+impl TextMeasurer {
+    pub fn new(scale_factor: CoordinateUnit) -> Self {
+        Self {
+            layout: Layout::new(CoordinateSystem::PositiveYDown),
+            scale_factor,
+        }
+    }
+    pub fn measure(
+        &mut self,
+        font: &MonoSpacedFont,
+        text: &str,
+        scale: TextScale,
+        wrap_style: TextWrapStyle,
+        max_width: Option<CoordinateUnit>,
+    ) -> TextMeasurement {
+        let runs = if text.is_empty() {
+            vec![]
+        } else {
+            vec![(0, text.len(), MonoSpacedFont::index())]
+        };
+        self.measure_runs(font.font_slice(), runs, text, scale, wrap_style, max_width)
+    }
+    /// as `measure`, with `font` from the registry and its fallbacks for missing glyphs
+    pub fn measure_with_registry(
+        &mut self,
+        fonts: &FontRegistry,
+        font: ResourceHandle,
+        text: &str,
+        scale: TextScale,
+        wrap_style: TextWrapStyle,
+        max_width: Option<CoordinateUnit>,
+    ) -> TextMeasurement {
+        let runs = fonts.runs(fonts.index(font), text);
+        self.measure_runs(fonts.font_slice(), runs, text, scale, wrap_style, max_width)
+    }
+    /// largest scale within `bounds` whose layout fits `area`; `bounds.min` if none does
+    pub fn fit_scale(
+        &mut self,
+        fonts: &FontRegistry,
+        font: ResourceHandle,
+        text: &str,
+        wrap_style: TextWrapStyle,
+        area: Area<InterfaceContext>,
+        bounds: AutoScale,
+    ) -> TextScale {
+        let (mut low, mut high) = (bounds.min.0, bounds.max.0.max(bounds.min.0));
+        while low < high {
+            let scale = low + (high - low + 1) / 2;
+            let measurement = self.measure_with_registry(
+                fonts,
+                font,
+                text,
+                TextScale(scale),
+                wrap_style,
+                Some(area.width),
+            );
+            if measurement.area.width <= area.width && measurement.area.height <= area.height {
+                low = scale;
+            } else {
+                high = scale - 1;
+            }
+        }
+        TextScale(low)
+    }
+    fn measure_runs(
+        &mut self,
+        fonts: &[fdFont],
+        runs: Vec<(usize, usize, usize)>,
+        text: &str,
+        scale: TextScale,
+        wrap_style: TextWrapStyle,
+        max_width: Option<CoordinateUnit>,
+    ) -> TextMeasurement {
+        let px = (scale.px() * self.scale_factor).floor();
+        self.layout.reset(&LayoutSettings {
+            max_width: max_width.map(|width| width * self.scale_factor),
+            wrap_style: wrap_style.0,
+            line_height: MonoSpacedFont::TEXT_HEIGHT_CORRECTION,
+            ..LayoutSettings::default()
+        });
+        let mut run_offsets = vec![];
+        for (start, end, font_index) in runs.iter().copied() {
+            run_offsets.push((self.layout.glyphs().len(), start));
+            self.layout
+                .append(fonts, &TextStyle::new(&text[start..end], px, font_index));
+        }
+        let primary = runs.first().map(|run| run.2).unwrap_or_default();
+        let diff = (1f32 - MonoSpacedFont::TEXT_HEIGHT_CORRECTION)
+            * fonts[primary]
+                .horizontal_line_metrics(px)
+                .map(|metrics| metrics.new_line_size)
+                .unwrap_or(px);
+        let mut measurement = TextMeasurement::default();
+        let mut advances = vec![];
+        for (index, glyph) in self.layout.glyphs().iter().enumerate() {
+            let run_start = run_offsets
+                .iter()
+                .rev()
+                .find(|(glyph_start, _)| *glyph_start <= index)
+                .map(|(_, byte_start)| *byte_start)
+                .unwrap_or_default();
+            let advance = if glyph.parent.is_control() {
+                0f32
+            } else {
+                fonts[glyph.font_index]
+                    .metrics_indexed(glyph.key.glyph_index, px)
+                    .advance_width
+            };
+            advances.push(glyph.x + advance);
+            let position = Position::<DeviceContext>::new(glyph.x, glyph.y - diff)
+                .to_interface(self.scale_factor);
+            let area = Area::<DeviceContext>::new(glyph.width as f32, glyph.height as f32)
+                .to_interface(self.scale_factor);
+            measurement.glyphs.push(MeasuredGlyph {
+                character: glyph.parent,
+                byte_offset: run_start + glyph.byte_offset,
+                section: Section::new(position, area),
+            });
+        }
+        for line in self.layout.lines().into_iter().flatten() {
+            let glyphs = line.glyph_start..(line.glyph_end + 1).min(measurement.glyphs.len());
+            let width = advances[glyphs.clone()]
+                .iter()
+                .copied()
+                .fold(0f32, f32::max)
+                / self.scale_factor;
+            let bytes_start = measurement
+                .glyphs
+                .get(glyphs.start)
+                .map(|glyph| glyph.byte_offset)
+                .unwrap_or(text.len());
+            let bytes_end = measurement
+                .glyphs
+                .get(glyphs.end)
+                .map(|glyph| glyph.byte_offset)
+                .unwrap_or(text.len());
+            measurement.lines.push(MeasuredLine {
+                bytes: bytes_start..bytes_end,
+                glyphs,
+                width,
+            });
+        }
+        let width = measurement
+            .lines
+            .iter()
+            .map(|line| line.width)
+            .fold(0f32, f32::max);
+        measurement.area = Area::new(width, self.layout.height() / self.scale_factor);
+        measurement
+    }
+}
+
+impl Default for TextMeasurer {
+    fn default() -> Self {
+        Self::new(1f32)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn measurement() {
+    let font = MonoSpacedFont::jet_brains_mono(MonoSpacedFont::DEFAULT_OPT_SCALE);
+    let mut measurer = TextMeasurer::new(2f32);
+    let cell = font.character_dimensions(40f32);
+    let single = measurer.measure(
+        &font,
+        "hello world",
+        TextScale(20),
+        TextWrapStyle::word(),
+        None,
+    );
+    assert_eq!(single.lines.len(), 1);
+    assert_eq!(single.glyphs.len(), 11);
+    assert!((single.area.width - cell.width * 11f32 / 2f32).abs() <= 11f32);
+    let wrapped = measurer.measure(
+        &font,
+        "hello world",
+        TextScale(20),
+        TextWrapStyle::word(),
+        Some(single.area.width * 0.75),
+    );
+    assert_eq!(wrapped.lines.len(), 2);
+    assert_eq!(wrapped.line_breaks(), vec![6]);
+    assert!(wrapped.area.width < single.area.width);
+    assert!(wrapped.area.height > single.area.height);
+    let broken = measurer.measure(&font, "a\nbc", TextScale(20), TextWrapStyle::word(), None);
+    assert_eq!(broken.lines.len(), 2);
+    assert_eq!(broken.lines[1].bytes, 2..4);
+}
+
+#[cfg(test)]
+#[test]
+fn fitting() {
+    let fonts = FontRegistry::new();
+    let mut measurer = TextMeasurer::new(1f32);
+    let area = Area::new(200f32, 40f32);
+    let fitted = measurer.fit_scale(
+        &fonts,
+        FontRegistry::DEFAULT,
+        "heading text",
+        TextWrapStyle::word(),
+        area,
+        AutoScale::new(8, 120),
+    );
+    assert!(fitted.0 > 8 && fitted.0 < 120);
+    let fits = |scale: u32, measurer: &mut TextMeasurer| {
+        let measured = measurer.measure_with_registry(
+            &fonts,
+            FontRegistry::DEFAULT,
+            "heading text",
+            TextScale(scale),
+            TextWrapStyle::word(),
+            Some(area.width),
+        );
+        measured.area.width <= area.width && measured.area.height <= area.height
+    };
+    assert!(fits(fitted.0, &mut measurer));
+    assert!(!fits(fitted.0 + 1, &mut measurer));
+}
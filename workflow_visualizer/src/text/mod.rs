@@ -1,17 +1,26 @@
 pub use attachment::TextAttachment;
 
 pub use component::{
-    Text, TextGridLocation, TextGridPlacement, TextLetterDimensions, TextLineStructure, TextScale,
-    TextTag, TextValue, TextWrapStyle,
+    AutoScale, RichText, SdfText, Text, TextAlignment, TextDirection, TextGridLocation,
+    TextGridPlacement, TextHorizontalAlignment, TextLetterDimensions, TextLineStructure,
+    TextOutline, TextOverflow, TextOverflowMode, TextScale, TextShadow, TextSpan, TextTag,
+    TextValue, TextVerticalAlignment, TextWrapStyle,
 };
 pub use font::{
-    KnownTextDimension, MonoSpacedFont, TextSectionDescriptor, TextSectionDescriptorKnown,
+    FontRegistry, KnownTextDimension, MonoSpacedFont, TextFont, TextSectionDescriptor,
+    TextSectionDescriptorKnown,
 };
+pub use measure::{MeasuredGlyph, MeasuredLine, TextMeasurement, TextMeasurer};
+pub use selection::Selectable;
 
 mod atlas;
 mod attachment;
+mod bidi;
 mod component;
+mod distance_field;
 mod font;
+mod measure;
 mod render_group;
 mod renderer;
+mod selection;
 mod system;
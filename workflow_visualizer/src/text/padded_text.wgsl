@@ -7,9 +7,6 @@ var<uniform> viewport: Viewport;
 @group(0)
 @binding(1)
 var<uniform> viewport_offset: vec4<f32>;
-@group(2)
-@binding(0)
-var<uniform> text_placement: vec4<f32>;
 struct VertexInput {
     @builtin(vertex_index) vertex_index: u32,
     @location(0) vertex_position: vec2<f32>,
@@ -18,11 +15,15 @@ struct VertexInput {
     @location(3) null_bit: u32,
     @location(4) color: vec4<f32>,
     @location(5) tex_coords: vec4<f32>,
+    @location(6) placement: vec4<f32>,
+    @location(7) clip: vec4<f32>,
 };
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) sample_coords: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) device_position: vec2<f32>,
+    @location(3) clip: vec4<f32>,
 };
 fn adjust_coords_of(vertex_index: u32, tex_coords: vec4<f32>) -> vec2<f32> {
     var adjusted_coords = vec2<f32>(0.0, 0.0);
@@ -61,17 +62,17 @@ fn vertex_entry(
     let null_mult = f32(!nulled);
     var adjusted_coords = adjust_coords_of(vertex_input.vertex_index, vertex_input.tex_coords);
     var coordinates = vec4<f32>(
-        text_placement.x + vertex_input.position.x + (vertex_input.vertex_position.x * vertex_input.area.x),
-        text_placement.y + vertex_input.position.y + (vertex_input.vertex_position.y * vertex_input.area.y),
-        text_placement.z,
+        vertex_input.placement.x + vertex_input.position.x + (vertex_input.vertex_position.x * vertex_input.area.x),
+        vertex_input.placement.y + vertex_input.position.y + (vertex_input.vertex_position.y * vertex_input.area.y),
+        vertex_input.placement.z,
         1.0);
     var offset_coordinates = vec4<f32>(coordinates.rg - viewport_offset.rg, coordinates.ba);
-    return VertexOutput(viewport.view_matrix * offset_coordinates * null_mult, adjusted_coords, vertex_input.color);
+    return VertexOutput(viewport.view_matrix * offset_coordinates * null_mult, adjusted_coords, vertex_input.color, coordinates.rg, vertex_input.clip);
 }
 @group(1)
 @binding(0)
 var rasterization_sampler: sampler;
-@group(3)
+@group(2)
 @binding(0)
 var rasterization_tex: texture_2d<f32>;
 @fragment
@@ -79,7 +80,10 @@ fn fragment_entry(
     fragment_input: VertexOutput,
 ) -> @location(0) vec4<f32> {
     let coverage = textureSample(rasterization_tex, rasterization_sampler, fragment_input.sample_coords).r;
-    if (coverage == 0.0) {
+    let clip = fragment_input.clip;
+    let position = fragment_input.device_position;
+    let clipped = position.x < clip.x || position.y < clip.y || position.x > clip.z || position.y > clip.w;
+    if (coverage == 0.0 || clipped) {
         discard;
     }
     return vec4<f32>(fragment_input.color.rgb, fragment_input.color.a * coverage);
@@ -1,64 +1,95 @@
 use std::collections::HashMap;
 
-use crate::gfx::GfxSurface;
-use crate::text::atlas::{AtlasAddQueue, AtlasGlyphReferences, AtlasGlyphs, AtlasWriteQueue};
-use crate::text::component::{GlyphId, TextValue};
-use crate::texture_atlas::{TextureAtlas, TextureBindGroup, TextureCoordinates};
-use crate::{
-    AlignedUniform, Color, DeviceContext, Indexer, InstanceAttributeManager, Key, Layer, NullBit,
-    Position, RawArea, RawPosition, Section, VisibleSection,
-};
+use bytemuck::{Pod, Zeroable};
 
-pub(crate) struct RenderGroupBindGroup {
-    pub(crate) bind_group: wgpu::BindGroup,
+use crate::text::component::{GlyphId, SdfText};
+use crate::{Color, CoordinateUnit, DeviceContext, Key, Layer, Position, Section};
+
+/// Per-instance offset of a glyph's render group; `[x, y, z, _]`
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, Default)]
+pub(crate) struct GlyphPlacement {
+    pub(crate) data: [f32; 4],
 }
 
-impl RenderGroupBindGroup {
-    pub(crate) fn new(
-        gfx_surface: &GfxSurface,
-        layout: &wgpu::BindGroupLayout,
-        text_placement: &AlignedUniform<f32>,
-    ) -> Self {
+impl GlyphPlacement {
+    pub(crate) fn new(position: Position<DeviceContext>, layer: Layer) -> Self {
         Self {
-            bind_group: gfx_surface
-                .device
-                .create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("render group bind group"),
-                    layout,
-                    entries: &[text_placement.uniform.bind_group_entry(0)],
-                }),
+            data: [position.x, position.y, layer.z, 0.0],
         }
     }
 }
-pub(crate) struct DrawSection {
-    pub(crate) section: Option<Section<DeviceContext>>,
+/// Per-instance clip rect of a glyph's render group; `[left, top, right, bottom]`
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, Default)]
+pub(crate) struct GlyphClip {
+    pub(crate) data: [f32; 4],
 }
 
-impl DrawSection {
-    pub(crate) fn new() -> Self {
-        Self { section: None }
-    }
-}
-pub(crate) struct PositionWrite {
-    pub(crate) write: Option<Position<DeviceContext>>,
-}
-
-impl PositionWrite {
-    pub(crate) fn new() -> Self {
-        Self { write: None }
+impl GlyphClip {
+    pub(crate) const UNBOUNDED: CoordinateUnit = 1e9;
+    pub(crate) fn new(section: Option<Section<DeviceContext>>) -> Self {
+        match section {
+            Some(section) => Self {
+                data: [
+                    section.left(),
+                    section.top(),
+                    section.right(),
+                    section.bottom(),
+                ],
+            },
+            None => Self {
+                data: [
+                    -Self::UNBOUNDED,
+                    -Self::UNBOUNDED,
+                    Self::UNBOUNDED,
+                    Self::UNBOUNDED,
+                ],
+            },
+        }
     }
 }
 
-pub(crate) struct LayerWrite {
-    pub(crate) write: Option<Layer>,
+/// Per-instance `SdfText` parameters, in device px at the glyph's scale
+#[repr(C)]
+#[derive(Pod, Zeroable, Copy, Clone, Default)]
+pub(crate) struct GlyphEffects {
+    pub(crate) outline: Color,
+    pub(crate) shadow: Color,
+    /// `[outline width, shadow softness, shadow x, shadow y]`
+    pub(crate) params: [f32; 4],
+    /// `[spread, _, _, _]`; how far the quad extends past the glyph on each side
+    pub(crate) spread: [f32; 4],
 }
 
-impl LayerWrite {
-    pub(crate) fn new() -> Self {
-        Self { write: None }
+impl GlyphEffects {
+    pub(crate) fn new(sdf: &SdfText, glyph_px: f32, scale_factor: CoordinateUnit) -> Self {
+        let transparent = Color::from_rgba(0.0, 0.0, 0.0, 0.0);
+        let mut effects = Self {
+            outline: transparent,
+            shadow: transparent,
+            params: [0.0; 4],
+            spread: [
+                SdfText::SPREAD as f32 * glyph_px / SdfText::GLYPH_PX,
+                0.0,
+                0.0,
+                0.0,
+            ],
+        };
+        if let Some(outline) = sdf.outline {
+            effects.outline = outline.color;
+            effects.params[0] = outline.width * scale_factor;
+        }
+        if let Some(shadow) = sdf.shadow {
+            let offset = shadow.offset.to_device(scale_factor);
+            effects.shadow = shadow.color;
+            effects.params[1] = shadow.softness * scale_factor;
+            effects.params[2] = offset.x;
+            effects.params[3] = offset.y;
+        }
+        effects
     }
 }
-
 pub(crate) struct KeyedGlyphIds {
     pub(crate) ids: HashMap<Key, GlyphId>,
 }
@@ -70,39 +101,31 @@ impl KeyedGlyphIds {
         }
     }
 }
-
-#[derive(Copy, Clone)]
-pub(crate) struct RenderGroupUniqueGlyphs {
-    pub(crate) unique_glyphs: u32,
+/// A Text entity's share of the `TextRenderer` instance buffers
+pub(crate) struct RenderGroup {
+    pub(crate) position: Position<DeviceContext>,
+    pub(crate) layer: Layer,
+    pub(crate) clip: GlyphClip,
+    pub(crate) sdf: Option<SdfText>,
+    pub(crate) keyed_glyph_ids: KeyedGlyphIds,
 }
 
-impl RenderGroupUniqueGlyphs {
-    pub(crate) fn from_text(text: &TextValue) -> Self {
-        let length = text.0.len();
+impl RenderGroup {
+    pub(crate) fn new(
+        position: Position<DeviceContext>,
+        layer: Layer,
+        clip: GlyphClip,
+        sdf: Option<SdfText>,
+    ) -> Self {
         Self {
-            unique_glyphs: length.min(1024) as u32,
+            position,
+            layer,
+            clip,
+            sdf,
+            keyed_glyph_ids: KeyedGlyphIds::new(),
         }
     }
-}
-pub(crate) struct RenderGroup {
-    pub(crate) position: Position<DeviceContext>,
-    pub(crate) visible_section: VisibleSection,
-    pub(crate) position_write: PositionWrite,
-    pub(crate) layer_write: LayerWrite,
-    pub(crate) keyed_glyph_ids: KeyedGlyphIds,
-    pub(crate) draw_section: DrawSection,
-    pub(crate) text_placement: AlignedUniform<f32>,
-    pub(crate) indexer: Indexer<Key>,
-    pub(crate) glyph_positions: InstanceAttributeManager<RawPosition>,
-    pub(crate) glyph_areas: InstanceAttributeManager<RawArea>,
-    pub(crate) glyph_colors: InstanceAttributeManager<Color>,
-    pub(crate) null_bits: InstanceAttributeManager<NullBit>,
-    pub(crate) glyph_tex_coords: InstanceAttributeManager<TextureCoordinates>,
-    pub(crate) render_group_bind_group: RenderGroupBindGroup,
-    pub(crate) atlas: TextureAtlas,
-    pub(crate) atlas_bind_group: TextureBindGroup,
-    pub(crate) atlas_glyph_references: AtlasGlyphReferences,
-    pub(crate) atlas_write_queue: AtlasWriteQueue,
-    pub(crate) atlas_add_queue: AtlasAddQueue,
-    pub(crate) atlas_glyphs: AtlasGlyphs,
+    pub(crate) fn placement(&self) -> GlyphPlacement {
+        GlyphPlacement::new(self.position, self.layer)
+    }
 }
@@ -5,13 +5,15 @@ use tracing::trace;
 use wgpu::util::DeviceExt;
 
 use crate::gfx::{GfxSurface, GfxSurfaceConfiguration, MsaaRenderAdapter};
-use crate::text::component::Difference;
-use crate::text::render_group::{RenderGroup, RenderGroupUniqueGlyphs};
+use crate::text::atlas::{GlyphCache, GlyphRasterization};
+use crate::text::component::{Difference, SdfText};
+use crate::text::render_group::{GlyphClip, GlyphEffects, GlyphPlacement, RenderGroup};
+use crate::texture_atlas::TextureBindGroup;
 use crate::texture_atlas::TextureCoordinates;
-use crate::texture_atlas::{AtlasBlock, TextureBindGroup};
 use crate::{
-    Color, InterfaceContext, Layer, NullBit, Position, RawArea, RawPosition, Render,
-    RenderPassHandle, RenderPhase, ScaleFactor, Viewport, VisibleSection, Visualizer,
+    Color, Indexer, InstanceAttributeManager, InterfaceContext, Key, Layer, NullBit, Position,
+    RawArea, RawPosition, Render, RenderPassHandle, RenderPhase, ScaleFactor, Viewport,
+    VisibleSection, Visualizer,
 };
 
 pub(crate) const AABB: [Vertex; 6] = [
@@ -45,14 +47,105 @@ pub(crate) fn aabb_vertex_buffer(gfx_surface: &GfxSurface) -> wgpu::Buffer {
         })
 }
 
+/// Instance buffers drawn in a single call, sampling glyphs from one shared `GlyphCache`.
+/// Each entity's `RenderGroup` owns a set of keys in them.
+pub(crate) struct GlyphBatch {
+    pub(crate) glyph_cache: GlyphCache,
+    pub(crate) indexer: Indexer<(Entity, Key)>,
+    pub(crate) glyph_positions: InstanceAttributeManager<RawPosition>,
+    pub(crate) glyph_areas: InstanceAttributeManager<RawArea>,
+    pub(crate) glyph_colors: InstanceAttributeManager<Color>,
+    pub(crate) null_bits: InstanceAttributeManager<NullBit>,
+    pub(crate) glyph_tex_coords: InstanceAttributeManager<TextureCoordinates>,
+    pub(crate) glyph_placements: InstanceAttributeManager<GlyphPlacement>,
+    pub(crate) glyph_clips: InstanceAttributeManager<GlyphClip>,
+    /// only for `GlyphRasterization::DistanceField`
+    pub(crate) glyph_effects: Option<InstanceAttributeManager<GlyphEffects>>,
+}
+
+impl GlyphBatch {
+    pub(crate) const INITIAL_INSTANCES: u32 = 256;
+    pub(crate) fn new(
+        gfx: &GfxSurface,
+        layout: &wgpu::BindGroupLayout,
+        rasterization: GlyphRasterization,
+    ) -> Self {
+        Self {
+            glyph_cache: GlyphCache::new(gfx, layout, rasterization),
+            indexer: Indexer::new(Self::INITIAL_INSTANCES),
+            glyph_positions: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_areas: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_colors: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            null_bits: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_tex_coords: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_placements: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_clips: InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES),
+            glyph_effects: (rasterization == GlyphRasterization::DistanceField)
+                .then(|| InstanceAttributeManager::new(gfx, Self::INITIAL_INSTANCES)),
+        }
+    }
+    /// nulls a group's instances and releases its glyphs
+    pub(crate) fn remove_group(&mut self, entity: Entity, render_group: RenderGroup) {
+        for (key, glyph_id) in render_group.keyed_glyph_ids.ids {
+            if let Some(index) = self.indexer.remove((entity, key)) {
+                self.null_bits.queue_write(index, NullBit::null());
+            }
+            self.glyph_cache.release(glyph_id);
+        }
+    }
+    /// grows the instance buffers if the indexer outgrew them
+    pub(crate) fn grow(&mut self, gfx_surface: &GfxSurface) {
+        if self.indexer.should_grow() {
+            self.indexer.max = self.indexer.max.next_power_of_two();
+            let max = self.indexer.max();
+            self.glyph_positions.grow(gfx_surface, max);
+            self.glyph_areas.grow(gfx_surface, max);
+            self.glyph_colors.grow(gfx_surface, max);
+            self.null_bits.grow(gfx_surface, max);
+            self.glyph_tex_coords.grow(gfx_surface, max);
+            self.glyph_placements.grow(gfx_surface, max);
+            self.glyph_clips.grow(gfx_surface, max);
+            if let Some(effects) = self.glyph_effects.as_mut() {
+                effects.grow(gfx_surface, max);
+            }
+        }
+    }
+    pub(crate) fn write(&mut self, gfx_surface: &GfxSurface) {
+        self.glyph_positions.write(gfx_surface);
+        self.glyph_areas.write(gfx_surface);
+        self.glyph_colors.write(gfx_surface);
+        self.null_bits.write(gfx_surface);
+        self.glyph_tex_coords.write(gfx_surface);
+        self.glyph_placements.write(gfx_surface);
+        self.glyph_clips.write(gfx_surface);
+        if let Some(effects) = self.glyph_effects.as_mut() {
+            effects.write(gfx_surface);
+        }
+        self.glyph_cache.write(gfx_surface);
+    }
+}
+/// Draws all coverage text in one call and all `SdfText` in another
 #[cfg_attr(not(target_family = "wasm"), derive(Resource))]
 pub(crate) struct TextRenderer {
     pub(crate) pipeline: wgpu::RenderPipeline,
+    pub(crate) sdf_pipeline: wgpu::RenderPipeline,
     pub(crate) vertex_buffer: wgpu::Buffer,
     pub(crate) sampler_bind_group: wgpu::BindGroup,
     pub(crate) render_groups: HashMap<Entity, RenderGroup>,
-    pub(crate) render_group_bind_group_layout: wgpu::BindGroupLayout,
     pub(crate) atlas_bind_group_layout: wgpu::BindGroupLayout,
+    pub(crate) coverage: GlyphBatch,
+    pub(crate) distance_field: GlyphBatch,
+}
+
+impl TextRenderer {
+    pub(crate) fn remove_group(&mut self, entity: Entity) {
+        if let Some(render_group) = self.render_groups.remove(&entity) {
+            match render_group.sdf {
+                Some(_) => self.distance_field.remove_group(entity, render_group),
+                None => self.coverage.remove_group(entity, render_group),
+            }
+        }
+    }
 }
 
 #[derive(Resource, Clone)]
@@ -60,12 +153,10 @@ pub(crate) struct Extraction {
     pub(crate) added: HashMap<
         Entity,
         (
-            u32,
             Position<InterfaceContext>,
             VisibleSection,
             Layer,
-            RenderGroupUniqueGlyphs,
-            AtlasBlock,
+            Option<SdfText>,
         ),
     >,
     pub(crate) removed: HashSet<Entity>,
@@ -127,22 +218,6 @@ impl Render for TextRenderer {
             }],
         };
         let sampler_bind_group = gfx.device.create_bind_group(&sampler_bind_group_descriptor);
-        let render_group_bind_group_layout_descriptor = wgpu::BindGroupLayoutDescriptor {
-            label: Some("rasterization bind group"),
-            entries: &[wgpu::BindGroupLayoutEntry {
-                binding: 0,
-                visibility: wgpu::ShaderStages::VERTEX,
-                ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
-                },
-                count: None,
-            }],
-        };
-        let render_group_bind_group_layout = gfx
-            .device
-            .create_bind_group_layout(&render_group_bind_group_layout_descriptor);
         let atlas_bind_group_layout_descriptor = wgpu::BindGroupLayoutDescriptor {
             label: Some("atlas bind group layout descriptor"),
             entries: &[TextureBindGroup::entry(0)],
@@ -155,7 +230,6 @@ impl Render for TextRenderer {
             bind_group_layouts: &[
                 viewport.bind_group_layout(),
                 &sampler_bind_group_layout,
-                &render_group_bind_group_layout,
                 &atlas_bind_group_layout,
             ],
             push_constant_ranges: &[],
@@ -164,10 +238,14 @@ impl Render for TextRenderer {
         let shader = gfx
             .device
             .create_shader_module(wgpu::include_wgsl!("padded_text.wgsl"));
-        let vertex_state = wgpu::VertexState {
-            module: &shader,
-            entry_point: "vertex_entry",
-            buffers: &[
+        let pipeline = text_pipeline(
+            gfx,
+            viewport,
+            gfx_config,
+            msaa,
+            &layout,
+            &shader,
+            &[
                 wgpu::VertexBufferLayout {
                     array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
                     step_mode: wgpu::VertexStepMode::Vertex,
@@ -198,47 +276,89 @@ impl Render for TextRenderer {
                     step_mode: wgpu::VertexStepMode::Instance,
                     attributes: &wgpu::vertex_attr_array![5 => Float32x4],
                 },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphPlacement>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![6 => Float32x4],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphClip>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![7 => Float32x4],
+                },
             ],
-        };
-        let primitive_state = wgpu::PrimitiveState {
-            topology: wgpu::PrimitiveTopology::TriangleList,
-            strip_index_format: None,
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: Some(wgpu::Face::Back),
-            unclipped_depth: false,
-            polygon_mode: wgpu::PolygonMode::Fill,
-            conservative: false,
-        };
-        let depth_stencil_state = Some(wgpu::DepthStencilState {
-            format: viewport.depth_format(),
-            depth_write_enabled: true,
-            depth_compare: wgpu::CompareFunction::Less,
-            stencil: wgpu::StencilState::default(),
-            bias: wgpu::DepthBiasState::default(),
-        });
-        let fragment_state = wgpu::FragmentState {
-            module: &shader,
-            entry_point: "fragment_entry",
-            targets: &gfx_config.alpha_color_target_state(),
-        };
-        let descriptor = wgpu::RenderPipelineDescriptor {
-            label: Some("text pipeline"),
-            layout: Some(&layout),
-            vertex: vertex_state,
-            primitive: primitive_state,
-            depth_stencil: depth_stencil_state,
-            multisample: msaa.multisample_state(),
-            fragment: Some(fragment_state),
-            multiview: None,
-        };
-        let pipeline = gfx.device.create_render_pipeline(&descriptor);
-
+        );
+        // distance field quads are built from the vertex index so effects fit in the buffer limit
+        let sdf_shader = gfx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("sdf_text.wgsl"));
+        let sdf_pipeline = text_pipeline(
+            gfx,
+            viewport,
+            gfx_config,
+            msaa,
+            &layout,
+            &sdf_shader,
+            &[
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<RawPosition>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![0 => Float32x2],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<RawArea>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![1 => Float32x2],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<NullBit>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![2 => Uint32],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Color>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![3 => Float32x4],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<TextureCoordinates>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![4 => Float32x4],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphPlacement>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![5 => Float32x4],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphClip>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![6 => Float32x4],
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<GlyphEffects>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        7 => Float32x4,
+                        8 => Float32x4,
+                        9 => Float32x4,
+                        10 => Float32x4
+                    ],
+                },
+            ],
+        );
         TextRenderer {
             pipeline,
+            sdf_pipeline,
             vertex_buffer: aabb_vertex_buffer(gfx),
             sampler_bind_group,
             render_groups: HashMap::new(),
-            render_group_bind_group_layout,
+            coverage: GlyphBatch::new(gfx, &atlas_bind_group_layout, GlyphRasterization::Coverage),
+            distance_field: GlyphBatch::new(
+                gfx,
+                &atlas_bind_group_layout,
+                GlyphRasterization::DistanceField,
+            ),
             atlas_bind_group_layout,
         }
     }
@@ -248,63 +368,118 @@ impl Render for TextRenderer {
     }
     fn render<'a>(&'a self, render_pass_handle: &mut RenderPassHandle<'a>, viewport: &'a Viewport) {
         trace!("starting text render fn");
-        render_pass_handle.0.set_pipeline(&self.pipeline);
-        render_pass_handle
-            .0
-            .set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass_handle
-            .0
-            .set_bind_group(0, viewport.bind_group(), &[]);
-        render_pass_handle
-            .0
-            .set_bind_group(1, &self.sampler_bind_group, &[]);
-        for (_, render_group) in self.render_groups.iter() {
-            if render_group.indexer.has_instances() {
-                render_pass_handle.0.set_bind_group(
-                    2,
-                    &render_group.render_group_bind_group.bind_group,
-                    &[],
-                );
-                render_pass_handle.0.set_bind_group(
-                    3,
-                    &render_group.atlas_bind_group.bind_group,
-                    &[],
-                );
-                render_pass_handle
-                    .0
-                    .set_vertex_buffer(1, render_group.glyph_positions.gpu.buffer.slice(..));
-                render_pass_handle
-                    .0
-                    .set_vertex_buffer(2, render_group.glyph_areas.gpu.buffer.slice(..));
-                render_pass_handle
-                    .0
-                    .set_vertex_buffer(3, render_group.null_bits.gpu.buffer.slice(..));
-                render_pass_handle
-                    .0
-                    .set_vertex_buffer(4, render_group.glyph_colors.gpu.buffer.slice(..));
-                render_pass_handle
-                    .0
-                    .set_vertex_buffer(5, render_group.glyph_tex_coords.gpu.buffer.slice(..));
-                if let Some(draw_section) = render_group.draw_section.section {
-                    render_pass_handle.0.set_scissor_rect(
-                        draw_section.position.x as u32,
-                        draw_section.position.y as u32,
-                        draw_section.area.width.max(1.0) as u32,
-                        draw_section.area.height.max(1.0) as u32,
-                    )
-                }
+        if self.coverage.indexer.has_instances() {
+            render_pass_handle.0.set_pipeline(&self.pipeline);
+            render_pass_handle
+                .0
+                .set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            bind_batch(
+                render_pass_handle,
+                viewport,
+                &self.sampler_bind_group,
+                &self.coverage,
+                1,
+            );
+            render_pass_handle
+                .0
+                .draw(0..AABB.len() as u32, 0..self.coverage.indexer.count());
+        }
+        if self.distance_field.indexer.has_instances() {
+            render_pass_handle.0.set_pipeline(&self.sdf_pipeline);
+            bind_batch(
+                render_pass_handle,
+                viewport,
+                &self.sampler_bind_group,
+                &self.distance_field,
+                0,
+            );
+            if let Some(effects) = self.distance_field.glyph_effects.as_ref() {
                 render_pass_handle
                     .0
-                    .draw(0..AABB.len() as u32, 0..render_group.indexer.count());
-                if render_group.draw_section.section.is_some() {
-                    render_pass_handle.0.set_scissor_rect(
-                        0,
-                        0,
-                        viewport.as_section().area.width as u32,
-                        viewport.as_section().area.height as u32,
-                    )
-                }
+                    .set_vertex_buffer(7, effects.gpu.buffer.slice(..));
             }
+            render_pass_handle
+                .0
+                .draw(0..AABB.len() as u32, 0..self.distance_field.indexer.count());
         }
     }
 }
+/// binds the shared groups and a batch's instance buffers starting at `first_slot`
+fn bind_batch<'a>(
+    render_pass_handle: &mut RenderPassHandle<'a>,
+    viewport: &'a Viewport,
+    sampler_bind_group: &'a wgpu::BindGroup,
+    batch: &'a GlyphBatch,
+    first_slot: u32,
+) {
+    render_pass_handle
+        .0
+        .set_bind_group(0, viewport.bind_group(), &[]);
+    render_pass_handle
+        .0
+        .set_bind_group(1, sampler_bind_group, &[]);
+    render_pass_handle
+        .0
+        .set_bind_group(2, &batch.glyph_cache.bind_group.bind_group, &[]);
+    let buffers = [
+        &batch.glyph_positions.gpu.buffer,
+        &batch.glyph_areas.gpu.buffer,
+        &batch.null_bits.gpu.buffer,
+        &batch.glyph_colors.gpu.buffer,
+        &batch.glyph_tex_coords.gpu.buffer,
+        &batch.glyph_placements.gpu.buffer,
+        &batch.glyph_clips.gpu.buffer,
+    ];
+    for (offset, buffer) in buffers.into_iter().enumerate() {
+        render_pass_handle
+            .0
+            .set_vertex_buffer(first_slot + offset as u32, buffer.slice(..));
+    }
+}
+fn text_pipeline(
+    gfx: &GfxSurface,
+    viewport: &Viewport,
+    gfx_config: &GfxSurfaceConfiguration,
+    msaa: &MsaaRenderAdapter,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    buffers: &[wgpu::VertexBufferLayout],
+) -> wgpu::RenderPipeline {
+    let vertex_state = wgpu::VertexState {
+        module: shader,
+        entry_point: "vertex_entry",
+        buffers,
+    };
+    let primitive_state = wgpu::PrimitiveState {
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        strip_index_format: None,
+        front_face: wgpu::FrontFace::Ccw,
+        cull_mode: Some(wgpu::Face::Back),
+        unclipped_depth: false,
+        polygon_mode: wgpu::PolygonMode::Fill,
+        conservative: false,
+    };
+    let depth_stencil_state = Some(wgpu::DepthStencilState {
+        format: viewport.depth_format(),
+        depth_write_enabled: true,
+        depth_compare: wgpu::CompareFunction::Less,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    });
+    let fragment_state = wgpu::FragmentState {
+        module: shader,
+        entry_point: "fragment_entry",
+        targets: &gfx_config.alpha_color_target_state(),
+    };
+    let descriptor = wgpu::RenderPipelineDescriptor {
+        label: Some("text pipeline"),
+        layout: Some(layout),
+        vertex: vertex_state,
+        primitive: primitive_state,
+        depth_stencil: depth_stencil_state,
+        multisample: msaa.multisample_state(),
+        fragment: Some(fragment_state),
+        multiview: None,
+    };
+    gfx.device.create_render_pipeline(&descriptor)
+}
@@ -0,0 +1,127 @@
+struct Viewport {
+    view_matrix: mat4x4<f32>,
+};
+@group(0)
+@binding(0)
+var<uniform> viewport: Viewport;
+@group(0)
+@binding(1)
+var<uniform> viewport_offset: vec4<f32>;
+struct VertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) position: vec2<f32>,
+    @location(1) area: vec2<f32>,
+    @location(2) null_bit: u32,
+    @location(3) color: vec4<f32>,
+    @location(4) tex_coords: vec4<f32>,
+    @location(5) placement: vec4<f32>,
+    @location(6) clip: vec4<f32>,
+    @location(7) outline: vec4<f32>,
+    @location(8) shadow: vec4<f32>,
+    @location(9) params: vec4<f32>,
+    @location(10) spread: vec4<f32>,
+};
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) sample_coords: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) device_position: vec2<f32>,
+    @location(3) clip: vec4<f32>,
+    @location(4) tex_coords: vec4<f32>,
+    @location(5) area_and_spread: vec4<f32>,
+    @location(6) outline: vec4<f32>,
+    @location(7) shadow: vec4<f32>,
+    @location(8) params: vec4<f32>,
+};
+fn corner_of(vertex_index: u32) -> vec2<f32> {
+    var corner = vec2<f32>(0.0, 0.0);
+    switch (i32(vertex_index)) {
+        case 1, 4: {
+            corner = vec2<f32>(0.0, 1.0);
+        }
+        case 2, 3: {
+            corner = vec2<f32>(1.0, 0.0);
+        }
+        case 5: {
+            corner = vec2<f32>(1.0, 1.0);
+        }
+        default: {}
+    }
+    return corner;
+}
+@vertex
+fn vertex_entry(
+    vertex_input: VertexInput,
+) -> VertexOutput {
+    let nulled = bool(vertex_input.null_bit) == true;
+    let null_mult = f32(!nulled);
+    let corner = corner_of(vertex_input.vertex_index);
+    let tex = vertex_input.tex_coords;
+    let sample_coords = vec2<f32>(mix(tex.r, tex.b, corner.x), mix(tex.g, tex.a, corner.y));
+    let spread = vertex_input.spread.x;
+    var coordinates = vec4<f32>(
+        vertex_input.placement.x + vertex_input.position.x - spread + (corner.x * vertex_input.area.x),
+        vertex_input.placement.y + vertex_input.position.y - spread + (corner.y * vertex_input.area.y),
+        vertex_input.placement.z,
+        1.0);
+    var offset_coordinates = vec4<f32>(coordinates.rg - viewport_offset.rg, coordinates.ba);
+    return VertexOutput(
+        viewport.view_matrix * offset_coordinates * null_mult,
+        sample_coords,
+        vertex_input.color,
+        coordinates.rg,
+        vertex_input.clip,
+        tex,
+        vec4<f32>(vertex_input.area, spread, 0.0),
+        vertex_input.outline,
+        vertex_input.shadow,
+        vertex_input.params,
+    );
+}
+@group(1)
+@binding(0)
+var rasterization_sampler: sampler;
+@group(2)
+@binding(0)
+var distance_field: texture_2d<f32>;
+// straight alpha `top` composited over `under`
+fn over(top: vec4<f32>, under: vec4<f32>) -> vec4<f32> {
+    let alpha = top.a + under.a * (1.0 - top.a);
+    if (alpha == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    let rgb = (top.rgb * top.a + under.rgb * under.a * (1.0 - top.a)) / alpha;
+    return vec4<f32>(rgb, alpha);
+}
+@fragment
+fn fragment_entry(
+    fragment_input: VertexOutput,
+) -> @location(0) vec4<f32> {
+    let tex = fragment_input.tex_coords;
+    let area = fragment_input.area_and_spread.xy;
+    // device px represented by the full 0..1 range of the field
+    let range = 2.0 * fragment_input.area_and_spread.z;
+    let field = textureSample(distance_field, rasterization_sampler, fragment_input.sample_coords).r;
+    let signed_distance = (field - 0.5) * range;
+    let fill = clamp(signed_distance + 0.5, 0.0, 1.0);
+    let outline_width = fragment_input.params.x;
+    let outline = clamp(signed_distance + outline_width + 0.5, 0.0, 1.0) * f32(outline_width > 0.0);
+    var shape = over(
+        vec4<f32>(fragment_input.color.rgb, fragment_input.color.a * fill),
+        vec4<f32>(fragment_input.outline.rgb, fragment_input.outline.a * outline),
+    );
+    let softness = fragment_input.params.y;
+    let shadow_offset = fragment_input.params.zw / area * (tex.ba - tex.rg);
+    let shadow_coords = clamp(fragment_input.sample_coords - shadow_offset, tex.rg, tex.ba);
+    let shadow_field = textureSample(distance_field, rasterization_sampler, shadow_coords).r;
+    let shadow_distance = (shadow_field - 0.5) * range + outline_width;
+    let shadow = smoothstep(-softness - 0.5, softness + 0.5, shadow_distance);
+    shape = over(shape, vec4<f32>(fragment_input.shadow.rgb, fragment_input.shadow.a * shadow));
+    let clip = fragment_input.clip;
+    let position = fragment_input.device_position;
+    let clipped = position.x < clip.x || position.y < clip.y || position.x > clip.z || position.y > clip.w;
+    if (shape.a == 0.0 || clipped) {
+        discard;
+    }
+    return shape;
+}
@@ -0,0 +1,578 @@
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::{
+    Changed, Commands, Component, Entity, EventReader, Local, NonSendMut, Or, Query, Res, ResMut,
+    Resource, With, Without,
+};
+use winit::event::ElementState;
+use winit::keyboard::{Key, NamedKey};
+
+use crate::text::component::{LineExtents, Placement};
+use crate::{
+    Area, Button, ButtonBorder, ButtonIcon, ButtonText, ButtonType, Clipboard, Color, Despawn,
+    DeviceContext, Interaction, InteractionLocations, InteractionPhase, InteractionPhases,
+    InterfaceContext, KeyboardInput, Layer, Panel, PanelTag, PanelType, Position,
+    PrimaryInteraction, ScaleFactor, Section, TextGridLocation, TextLineStructure, TextValue,
+    TimeDelta, TimeMarker, TimeTracker, Triggered, Visibility,
+};
+
+/// Opts a `Text` into selection by pointer drag or Shift+arrows; Ctrl+C copies the selection.
+/// A long press selects the whole text and offers a Copy button above it; tapping it copies.
+#[derive(Component, Copy, Clone)]
+pub struct Selectable {
+    anchor: Option<TextGridLocation>,
+    head: Option<TextGridLocation>,
+    pub highlight: Color,
+}
+
+impl Selectable {
+    /// seconds a press must be held, without dragging, to count as a long press
+    pub const LONG_PRESS: f64 = 0.6;
+    /// width and height of the Copy button offered after a long press
+    pub const COPY_BUTTON: (f32, f32) = (72f32, 32f32);
+    pub fn new() -> Self {
+        Self {
+            anchor: None,
+            head: None,
+            highlight: Color::from_rgba(0.26, 0.52, 0.96, 0.45),
+        }
+    }
+    pub fn with_highlight<C: Into<Color>>(mut self, highlight: C) -> Self {
+        self.highlight = highlight.into();
+        self
+    }
+    pub fn select(&mut self, anchor: TextGridLocation, head: TextGridLocation) {
+        self.anchor.replace(anchor);
+        self.head.replace(head);
+    }
+    pub fn clear(&mut self) {
+        self.anchor.take();
+        self.head.take();
+    }
+    /// first and last selected locations in reading order, inclusive
+    pub fn range(&self) -> Option<(TextGridLocation, TextGridLocation)> {
+        let (anchor, head) = (self.anchor?, self.head?);
+        let (start, end) = if (anchor.y, anchor.x) <= (head.y, head.x) {
+            (anchor, head)
+        } else {
+            (head, anchor)
+        };
+        Some((start, end))
+    }
+    fn has_selection(&self) -> bool {
+        self.anchor.is_some() || self.head.is_some()
+    }
+}
+
+impl Default for Selectable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// the `Selectable` that keyboard selection and copy apply to
+#[derive(Resource, Default)]
+pub(crate) struct SelectionFocus(pub(crate) Option<Entity>);
+
+/// Copy button offered for the selection of `text`
+#[derive(Copy, Clone)]
+pub(crate) struct OfferedCopy {
+    button: Entity,
+    text: Entity,
+    section: Section<InterfaceContext>,
+}
+
+/// the Copy button shown after a long press, if any
+#[derive(Resource, Default)]
+pub(crate) struct CopyAffordance(Option<OfferedCopy>);
+
+impl CopyAffordance {
+    fn dismiss(&mut self, cmd: &mut Commands) {
+        if let Some(offered) = self.0.take() {
+            if let Some(mut button) = cmd.get_entity(offered.button) {
+                button.insert(Despawn::default());
+            }
+        }
+    }
+}
+
+/// glyph index of a grid location, clamped to its line
+fn glyph_index(location: TextGridLocation, lines: &TextLineStructure) -> Option<usize> {
+    let (start, end) = lines.0.get(location.y as usize)?;
+    Some((start + location.x).min(*end) as usize)
+}
+
+fn grid_location(index: usize, lines: &TextLineStructure) -> Option<TextGridLocation> {
+    lines
+        .0
+        .iter()
+        .enumerate()
+        .find(|(_, (start, end))| (*start as usize..=*end as usize).contains(&index))
+        .map(|(y, (start, _))| TextGridLocation::new(index as u32 - start, y as u32))
+}
+
+/// logical indices of a line's glyphs, left to right on screen
+fn display_order(y: usize, placement: &Placement, lines: &TextLineStructure) -> Vec<usize> {
+    let Some((start, end)) = lines.0.get(y) else {
+        return vec![];
+    };
+    let end = (*end as usize).min(placement.0.len().saturating_sub(1));
+    let mut order = (*start as usize..=end).collect::<Vec<usize>>();
+    order.sort_by(|a, b| placement.0[*a].1.x.total_cmp(&placement.0[*b].1.x));
+    order
+}
+
+/// location one glyph to the left or right of `head` on screen;
+/// past either end of the line it continues on the adjacent line
+fn visual_step(
+    head: TextGridLocation,
+    placement: &Placement,
+    lines: &TextLineStructure,
+    rightward: bool,
+) -> Option<TextGridLocation> {
+    let index = glyph_index(head, lines)?;
+    let order = display_order(head.y as usize, placement, lines);
+    let step = order
+        .iter()
+        .position(|glyph| *glyph == index)
+        .and_then(|at| match rightward {
+            true => order.get(at + 1),
+            false => at.checked_sub(1).and_then(|at| order.get(at)),
+        });
+    match step {
+        Some(stepped) => grid_location(*stepped, lines),
+        None => {
+            let (start, end) = lines.0.get(head.y as usize)?;
+            match rightward {
+                true => grid_location(*end as usize + 1, lines),
+                false => (*start as usize)
+                    .checked_sub(1)
+                    .and_then(|index| grid_location(index, lines)),
+            }
+        }
+    }
+}
+
+fn select_all(selectable: &mut Selectable, lines: &TextLineStructure) {
+    if let Some((last, (start, end))) = lines.0.iter().enumerate().next_back() {
+        selectable.select(
+            TextGridLocation::new(0, 0),
+            TextGridLocation::new(end - start, last as u32),
+        );
+    }
+}
+
+fn selected_text(
+    selectable: &Selectable,
+    placement: &Placement,
+    lines: &TextLineStructure,
+) -> Option<String> {
+    let (start, end) = selectable.range()?;
+    let (start, end) = (glyph_index(start, lines)?, glyph_index(end, lines)?);
+    let text = placement
+        .0
+        .iter()
+        .take(end + 1)
+        .skip(start)
+        .map(|(_, glyph)| glyph.parent)
+        .collect::<String>();
+    Some(text)
+}
+
+fn location_under(
+    position: Position<InterfaceContext>,
+    text_position: Position<InterfaceContext>,
+    placement: &Placement,
+    lines: &TextLineStructure,
+    scale_factor: f32,
+) -> TextGridLocation {
+    let relative = Position::<InterfaceContext>::new(
+        position.x - text_position.x,
+        position.y - text_position.y,
+    )
+    .to_device(scale_factor);
+    let glyphs = placement
+        .0
+        .iter()
+        .map(|(_, glyph)| *glyph)
+        .collect::<Vec<_>>();
+    TextGridLocation::from_glyphs(relative, &glyphs, lines)
+}
+
+/// press being turned into a selection; read by `offer_copy` to detect a long press
+#[derive(Resource, Default)]
+pub(crate) struct PointerSelection {
+    interaction: Option<Interaction>,
+    entity: Option<Entity>,
+    started: Option<TimeMarker>,
+    dragged: bool,
+}
+
+pub(crate) fn select_with_pointer(
+    (primary, locations, phases): (
+        Res<PrimaryInteraction>,
+        Res<InteractionLocations>,
+        Res<InteractionPhases>,
+    ),
+    time_tracker: Res<TimeTracker>,
+    scale_factor: Res<ScaleFactor>,
+    mut selectables: Query<(
+        Entity,
+        &mut Selectable,
+        &Position<InterfaceContext>,
+        &Area<InterfaceContext>,
+        &Placement,
+        &TextLineStructure,
+    )>,
+    mut focus: ResMut<SelectionFocus>,
+    mut pointer: ResMut<PointerSelection>,
+    affordance: Res<CopyAffordance>,
+) {
+    let prime = match primary.0 {
+        Some(prime) => prime,
+        None => {
+            *pointer = PointerSelection::default();
+            return;
+        }
+    };
+    let (phase, location) = match (phases.0.get(&prime), locations.0.get(&prime)) {
+        (Some(phase), Some(location)) => (*phase, *location),
+        _ => return,
+    };
+    match phase {
+        InteractionPhase::Started => {
+            if pointer.interaction != Some(prime) {
+                *pointer = PointerSelection::default();
+                pointer.interaction.replace(prime);
+                let on_copy = affordance
+                    .0
+                    .is_some_and(|offered| offered.section.contains(location.start()));
+                if on_copy {
+                    return;
+                }
+                focus.0.take();
+                for (entity, mut selectable, pos, area, placement, lines) in selectables.iter_mut()
+                {
+                    if focus.0.is_none() && Section::new(*pos, *area).contains(location.start()) {
+                        let start = location_under(
+                            location.start(),
+                            *pos,
+                            placement,
+                            lines,
+                            scale_factor.factor(),
+                        );
+                        selectable.clear();
+                        selectable.anchor.replace(start);
+                        focus.0.replace(entity);
+                        pointer.entity.replace(entity);
+                        pointer.started.replace(time_tracker.mark());
+                    } else if selectable.has_selection() {
+                        selectable.clear();
+                    }
+                }
+            }
+        }
+        InteractionPhase::Moved => {
+            if let Some(entity) = pointer.entity {
+                if let Ok((_, mut selectable, pos, _, placement, lines)) =
+                    selectables.get_mut(entity)
+                {
+                    let head = location_under(
+                        location.current(),
+                        *pos,
+                        placement,
+                        lines,
+                        scale_factor.factor(),
+                    );
+                    if selectable.anchor != Some(head) {
+                        pointer.dragged = true;
+                    }
+                    if selectable.head != Some(head) {
+                        selectable.head.replace(head);
+                    }
+                }
+            }
+        }
+        InteractionPhase::Ended | InteractionPhase::Cancelled => {
+            pointer.entity.take();
+            pointer.started.take();
+        }
+    }
+}
+
+/// after a long press without dragging, selects the whole text and offers a Copy button above it
+pub(crate) fn offer_copy(
+    time_tracker: Res<TimeTracker>,
+    mut pointer: ResMut<PointerSelection>,
+    mut selectables: Query<(
+        &mut Selectable,
+        &Position<InterfaceContext>,
+        &Layer,
+        &TextLineStructure,
+    )>,
+    mut affordance: ResMut<CopyAffordance>,
+    mut cmd: Commands,
+) {
+    let (Some(entity), Some(started)) = (pointer.entity, pointer.started) else {
+        return;
+    };
+    if pointer.dragged || time_tracker.time_since(started) < TimeDelta(Selectable::LONG_PRESS) {
+        return;
+    }
+    pointer.started.take();
+    let Ok((mut selectable, pos, layer, lines)) = selectables.get_mut(entity) else {
+        return;
+    };
+    select_all(&mut selectable, lines);
+    affordance.dismiss(&mut cmd);
+    let (width, height) = Selectable::COPY_BUTTON;
+    let section = Section::<InterfaceContext>::new(
+        (pos.x, (pos.y - height - height / 4f32).max(0f32)),
+        (width, height),
+    );
+    let button = cmd
+        .spawn(Button::new(
+            ButtonType::Press,
+            *layer - Layer::from(2),
+            Color::OFF_WHITE,
+            Color::GREY_DARK,
+            ButtonText::some(TextValue("Copy".to_string())),
+            ButtonIcon::none(),
+            ButtonBorder::None,
+        ))
+        .insert((section.position, section.area))
+        .id();
+    affordance.0.replace(OfferedCopy {
+        button,
+        text: entity,
+        section,
+    });
+}
+
+/// copies the selection when its Copy button is tapped;
+/// the button goes away after copying or once the selection is cleared
+pub(crate) fn copy_with_affordance(
+    mut affordance: ResMut<CopyAffordance>,
+    buttons: Query<&Triggered>,
+    selectables: Query<(&Selectable, &Placement, &TextLineStructure)>,
+    mut clipboard: NonSendMut<Clipboard>,
+    mut cmd: Commands,
+) {
+    let Some(offered) = affordance.0 else {
+        return;
+    };
+    let selection = selectables
+        .get(offered.text)
+        .ok()
+        .filter(|(selectable, _, _)| selectable.has_selection());
+    let Some((selectable, placement, lines)) = selection else {
+        affordance.dismiss(&mut cmd);
+        return;
+    };
+    if buttons
+        .get(offered.button)
+        .is_ok_and(|triggered| triggered.active())
+    {
+        if let Some(text) = selected_text(selectable, placement, lines) {
+            clipboard.write(text);
+        }
+        affordance.dismiss(&mut cmd);
+    }
+}
+
+fn shortcut(event: &KeyboardInput, character: &str) -> bool {
+    (event.modifiers.control_key() || event.modifiers.super_key())
+        && matches!(&event.key, Key::Character(c) if c.eq_ignore_ascii_case(character))
+}
+
+pub(crate) fn select_with_keyboard(
+    mut events: EventReader<KeyboardInput>,
+    focus: Res<SelectionFocus>,
+    mut selectables: Query<(&mut Selectable, &Placement, &TextLineStructure)>,
+    mut clipboard: NonSendMut<Clipboard>,
+) {
+    let focused = match focus.0 {
+        Some(focused) => focused,
+        None => return,
+    };
+    let (mut selectable, placement, lines) = match selectables.get_mut(focused) {
+        Ok(selectable) => selectable,
+        Err(_) => return,
+    };
+    for event in events.iter() {
+        if event.state != ElementState::Pressed {
+            continue;
+        }
+        if shortcut(event, "c") {
+            if let Some(text) = selected_text(&selectable, placement, lines) {
+                clipboard.write(text);
+            }
+            continue;
+        }
+        if shortcut(event, "a") {
+            select_all(&mut selectable, lines);
+            continue;
+        }
+        if !event.modifiers.shift_key() {
+            continue;
+        }
+        let head = match selectable.head.or(selectable.anchor) {
+            Some(head) => head,
+            None => continue,
+        };
+        let moved = match &event.key {
+            Key::Named(NamedKey::ArrowLeft) => visual_step(head, placement, lines, false),
+            Key::Named(NamedKey::ArrowRight) => visual_step(head, placement, lines, true),
+            Key::Named(NamedKey::ArrowUp) => head
+                .y
+                .checked_sub(1)
+                .map(|y| TextGridLocation::new(head.x, y)),
+            Key::Named(NamedKey::ArrowDown) => lines
+                .0
+                .get(head.y as usize + 1)
+                .map(|_| TextGridLocation::new(head.x, head.y + 1)),
+            _ => None,
+        };
+        if let Some(moved) = moved.and_then(|moved| {
+            let index = glyph_index(moved, lines)?;
+            grid_location(index, lines)
+        }) {
+            selectable.head.replace(moved);
+        }
+    }
+}
+
+/// one highlight section per selected line, relative to the text's position
+fn highlight_sections(
+    selectable: &Selectable,
+    placement: &Placement,
+    lines: &TextLineStructure,
+    extents: &LineExtents,
+) -> Vec<Section<DeviceContext>> {
+    let (start, end) = match selectable.range() {
+        Some(range) => range,
+        None => return vec![],
+    };
+    let (start, end) = match (glyph_index(start, lines), glyph_index(end, lines)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return vec![],
+    };
+    let mut sections = vec![];
+    for (y, (line_start, line_end)) in lines.0.iter().enumerate() {
+        if start.max(*line_start as usize) > end.min(*line_end as usize) {
+            continue;
+        }
+        let Some((top, bottom)) = extents.0.get(y) else {
+            continue;
+        };
+        let order = display_order(y, placement, lines);
+        let mut run: Option<(f32, f32)> = None;
+        for (at, index) in order.iter().enumerate() {
+            let glyph = placement.0[*index].1;
+            if (start..=end).contains(index) {
+                let right = match order.get(at + 1) {
+                    Some(next) => placement.0[*next].1.x,
+                    None => glyph.x + glyph.width as f32,
+                };
+                let left = run.map_or(glyph.x, |(left, _)| left);
+                run.replace((left, right));
+            } else if let Some((left, right)) = run.take() {
+                sections.push(Section::new(
+                    (left, *top),
+                    ((right - left).max(1f32), bottom - top),
+                ));
+            }
+        }
+        if let Some((left, right)) = run.take() {
+            sections.push(Section::new(
+                (left, *top),
+                ((right - left).max(1f32), bottom - top),
+            ));
+        }
+    }
+    sections
+}
+
+pub(crate) fn highlight_selection(
+    selectables: Query<
+        (
+            Entity,
+            &Selectable,
+            &Position<InterfaceContext>,
+            &Layer,
+            &Visibility,
+            &Placement,
+            &TextLineStructure,
+            &LineExtents,
+        ),
+        Or<(
+            Changed<Selectable>,
+            Changed<Position<InterfaceContext>>,
+            Changed<Placement>,
+            Changed<Visibility>,
+        )>,
+    >,
+    existing: Query<(), With<Selectable>>,
+    mut panels: Query<
+        (
+            &mut Position<InterfaceContext>,
+            &mut Area<InterfaceContext>,
+            &mut Color,
+        ),
+        (With<PanelTag>, Without<Selectable>),
+    >,
+    scale_factor: Res<ScaleFactor>,
+    mut highlights: Local<HashMap<Entity, Vec<Entity>>>,
+    mut cmd: Commands,
+) {
+    for (entity, selectable, pos, layer, visibility, placement, lines, extents) in
+        selectables.iter()
+    {
+        let sections = if visibility.visible() {
+            highlight_sections(selectable, placement, lines, extents)
+        } else {
+            vec![]
+        };
+        let spawned = highlights.entry(entity).or_default();
+        while spawned.len() > sections.len() {
+            if let Some(panel) = spawned.pop() {
+                cmd.entity(panel).despawn();
+            }
+        }
+        for (index, section) in sections.iter().enumerate() {
+            let section = section.to_interface(scale_factor.factor());
+            let position = Position::new(pos.x + section.left(), pos.y + section.top());
+            match spawned.get(index) {
+                Some(panel) => {
+                    if let Ok((mut panel_pos, mut panel_area, mut color)) = panels.get_mut(*panel) {
+                        *panel_pos = position;
+                        *panel_area = section.area;
+                        *color = selectable.highlight;
+                    }
+                }
+                None => {
+                    let panel = cmd
+                        .spawn(Panel::new(
+                            PanelType::Flat,
+                            *layer + 1.into(),
+                            selectable.highlight,
+                            selectable.highlight,
+                        ))
+                        .insert((position, section.area))
+                        .id();
+                    spawned.push(panel);
+                }
+            }
+        }
+    }
+    let stale = highlights
+        .keys()
+        .filter(|entity| existing.get(**entity).is_err())
+        .copied()
+        .collect::<Vec<Entity>>();
+    for entity in stale {
+        for panel in highlights.remove(&entity).unwrap_or_default() {
+            cmd.entity(panel).despawn();
+        }
+    }
+}
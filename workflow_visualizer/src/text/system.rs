@@ -2,36 +2,33 @@ use std::collections::{HashMap, HashSet};
 
 use crate::gfx::GfxSurface;
 use crate::instance::key::KeyFactory;
-use crate::text::atlas::{
-    AtlasAddQueue, AtlasGlyphReference, AtlasGlyphReferences, AtlasGlyphs, AtlasWriteQueue, Bitmap,
-};
+use crate::path::Path;
+use crate::text::atlas::GlyphRasterization;
+use crate::text::bidi;
 use crate::text::component::{
-    Cache, Difference, FilteredPlacement, Glyph, GlyphId, Placement, Placer, TextGridLocation,
-    TextGridPlacement, TextLetterDimensions, TextLineStructure, TextScale, TextValue,
-    TextWrapStyle,
-};
-use crate::text::font::MonoSpacedFont;
-use crate::text::render_group::{
-    DrawSection, KeyedGlyphIds, LayerWrite, PositionWrite, RenderGroup, RenderGroupBindGroup,
-    RenderGroupUniqueGlyphs,
+    AutoScale, Cache, Difference, FilteredPlacement, Glyph, GlyphStyles, LineExtents, Placement,
+    Placer, RichText, SdfText, TextAlignment, TextDecorationLine, TextDecorations, TextDirection,
+    TextGridLocation, TextGridPlacement, TextLetterDimensions, TextLineStructure, TextOverflow,
+    TextOverflowMode, TextScale, TextTag, TextValue, TextWrapStyle,
 };
+use crate::text::font::{FontRegistry, MonoSpacedFont, TextFont};
+use crate::text::measure::TextMeasurer;
+use crate::text::render_group::{GlyphClip, GlyphEffects, RenderGroup};
 use crate::text::renderer::{Extraction, TextRenderer};
-use crate::texture_atlas::{
-    AtlasBlock, AtlasDimension, AtlasLocation, AtlasPosition, TextureAtlas, TextureBindGroup,
-    TextureCoordinates,
-};
-use crate::window::WindowResize;
 use crate::{
-    AlignedUniform, Area, Color, DeviceContext, Indexer, InstanceAttributeManager,
-    InterfaceContext, Key, Layer, NullBit, NumericalContext, Position, ScaleFactor, Section,
-    Viewport, Visibility, VisibleSection,
+    Area, Color, DeviceContext, InterfaceContext, Key, Layer, Line, LineTag, NullBit, Position,
+    ScaleFactor, Section, Visibility, VisibleSection,
 };
 use bevy_ecs::prelude::{
-    Added, Changed, Commands, Entity, EventReader, Or, Query, RemovedComponents, Res, ResMut,
+    Added, Changed, Commands, DetectChanges, Entity, Or, Query, Ref, RemovedComponents, Res,
+    ResMut, With, Without,
 };
 #[cfg(target_family = "wasm")]
 use bevy_ecs::prelude::{NonSend, NonSendMut};
-use fontdue::layout::{GlyphPosition, LayoutSettings, TextStyle};
+use fontdue::layout::{
+    CoordinateSystem, GlyphPosition, HorizontalAlign, Layout, LayoutSettings, LinePosition,
+    TextStyle,
+};
 use tracing::trace;
 
 pub(crate) fn setup(_scale_factor: Res<ScaleFactor>, mut cmd: Commands) {
@@ -40,25 +37,32 @@ pub(crate) fn setup(_scale_factor: Res<ScaleFactor>, mut cmd: Commands) {
         MonoSpacedFont::DEFAULT_OPT_SCALE,
     ));
 }
+pub(crate) fn sync_rich_text(
+    mut text_query: Query<(&RichText, &mut TextValue), Changed<RichText>>,
+) {
+    for (rich_text, mut text) in text_query.iter_mut() {
+        text.0 = rich_text.plain();
+    }
+}
 pub(crate) fn place(
-    mut text_query: Query<
-        (
-            &mut Placer,
-            &mut Placement,
-            &mut TextLineStructure,
-            &mut TextGridPlacement,
-            &TextValue,
-            &Area<InterfaceContext>,
-            &TextWrapStyle,
-            &TextScale,
-        ),
-        Or<(
-            Changed<Area<InterfaceContext>>,
-            Changed<TextValue>,
-            Changed<TextScale>,
-        )>,
-    >,
-    fonts: Res<MonoSpacedFont>,
+    mut text_query: Query<(
+        &mut Placer,
+        &mut Placement,
+        &mut TextLineStructure,
+        &mut TextGridPlacement,
+        &mut GlyphStyles,
+        &mut TextDecorations,
+        &mut LineExtents,
+        Ref<TextValue>,
+        Ref<Area<InterfaceContext>>,
+        &TextWrapStyle,
+        Ref<TextScale>,
+        Option<Ref<TextFont>>,
+        Option<Ref<RichText>>,
+        (Option<Ref<TextAlignment>>, Option<Ref<TextDirection>>),
+        Option<Ref<TextOverflow>>,
+    )>,
+    fonts: Res<FontRegistry>,
     scale_factor: Res<ScaleFactor>,
 ) {
     for (
@@ -66,24 +70,83 @@ pub(crate) fn place(
         mut placement,
         mut line_structure,
         mut grid_placement,
+        mut glyph_styles,
+        mut decorations,
+        mut line_extents,
         text,
         area,
         wrap_style,
         text_scale,
+        text_font,
+        rich_text,
+        (alignment, direction),
+        overflow,
     ) in text_query.iter_mut()
     {
+        let font_changed = text_font.as_ref().map_or(false, |font| font.is_changed());
+        let rich_changed = rich_text.as_ref().map_or(false, |rich| rich.is_changed());
+        let alignment_changed = alignment.as_ref().map_or(false, |a| a.is_changed())
+            || direction.as_ref().map_or(false, |d| d.is_changed());
+        let overflow_changed = overflow.as_ref().map_or(false, |o| o.is_changed());
+        if !(fonts.is_changed()
+            || font_changed
+            || rich_changed
+            || alignment_changed
+            || overflow_changed
+            || text.is_changed()
+            || area.is_changed()
+            || text_scale.is_changed())
+        {
+            continue;
+        }
+        let primary = fonts.index(
+            text_font
+                .map(|font| font.0)
+                .unwrap_or(FontRegistry::DEFAULT),
+        );
         let area = area.to_device(scale_factor.factor());
+        let alignment = alignment.map(|alignment| *alignment).unwrap_or_default();
+        let direction = direction.map(|direction| *direction).unwrap_or_default();
+        let horizontal_align =
+            alignment.horizontal_align(bidi::right_to_left(text.0.as_str(), direction));
         placer.0.reset(&LayoutSettings {
             max_width: Some(area.width),
             max_height: Some(area.height),
+            horizontal_align,
+            vertical_align: alignment.vertical_align(),
             wrap_style: wrap_style.0,
             line_height: MonoSpacedFont::TEXT_HEIGHT_CORRECTION,
             ..LayoutSettings::default()
         });
-        placer.0.append(
-            fonts.font_slice(),
-            &TextStyle::new(text.0.as_str(), text_scale.px(), MonoSpacedFont::index()),
-        );
+        let mut span_ranges = vec![];
+        match rich_text.as_ref() {
+            Some(rich_text) => {
+                for (index, span) in rich_text.0.iter().enumerate() {
+                    let span_primary = span.font.map(|font| fonts.index(font)).unwrap_or(primary);
+                    let px = span
+                        .scale
+                        .map(|scale| scale.px() * scale_factor.factor())
+                        .unwrap_or(text_scale.px())
+                        .floor();
+                    let start = placer.0.glyphs().len();
+                    for (run_start, run_end, font_index) in fonts.runs(span_primary, &span.text) {
+                        placer.0.append(
+                            fonts.font_slice(),
+                            &TextStyle::new(&span.text[run_start..run_end], px, font_index),
+                        );
+                    }
+                    span_ranges.push((start, placer.0.glyphs().len(), index));
+                }
+            }
+            None => {
+                for (start, end, font_index) in fonts.runs(primary, text.0.as_str()) {
+                    placer.0.append(
+                        fonts.font_slice(),
+                        &TextStyle::new(&text.0[start..end], text_scale.px(), font_index),
+                    );
+                }
+            }
+        }
         let mut key_factory = KeyFactory::new();
         placement.0 = placer
             .0
@@ -91,14 +154,55 @@ pub(crate) fn place(
             .iter()
             .map(|g| (key_factory.generate(), *g))
             .collect::<Vec<(Key, GlyphPosition<()>)>>();
+        let mut spans_by_glyph = vec![None; placement.0.len()];
+        glyph_styles.spans.clear();
+        for (start, end, index) in span_ranges.iter().copied() {
+            for glyph in start..end {
+                spans_by_glyph[glyph] = Some(index);
+                glyph_styles.spans.insert(placement.0[glyph].0, index);
+            }
+        }
         line_structure.0.clear();
         grid_placement.0.clear();
-        if placer.0.lines().is_none() {
-            continue;
+        decorations.segments.clear();
+        line_extents.0.clear();
+        glyph_styles.fades.clear();
+        let lines = match placer.0.lines() {
+            Some(lines) => lines.clone(),
+            None => continue,
+        };
+        let diff = (1f32 - MonoSpacedFont::TEXT_HEIGHT_CORRECTION)
+            * fonts.character_dimensions(primary, text_scale.px()).height;
+        let kept = overflow
+            .as_ref()
+            .map(|overflow| kept_lines(overflow, &lines, area.height, diff))
+            .unwrap_or(lines.len());
+        if let (Some(overflow), Some(last)) = (overflow.as_ref(), lines.get(kept.wrapping_sub(1))) {
+            if kept < lines.len() {
+                truncate_overflow(
+                    overflow.mode,
+                    &mut placement.0,
+                    &mut glyph_styles,
+                    last,
+                    placer.0.glyphs(),
+                    &fonts,
+                    primary,
+                    text_scale.px(),
+                    area.width,
+                    horizontal_align,
+                );
+            }
         }
+        bidi::arrange(
+            &mut placement.0,
+            &lines[..kept],
+            &fonts,
+            direction,
+            horizontal_align,
+        );
         let mut line_count = 0;
         key_factory = KeyFactory::new();
-        for line in placer.0.lines().unwrap().iter() {
+        for line in lines.iter().take(kept) {
             for x in 0..=(line.glyph_end - line.glyph_start) {
                 let grid_location = TextGridLocation::new(x as u32, line_count);
                 let key = key_factory.generate();
@@ -108,14 +212,233 @@ pub(crate) fn place(
                 .0
                 .push((line.glyph_start as u32, line.glyph_end as u32));
             line_count += 1;
+            line_extents.0.push((
+                line.baseline_y - line.max_ascent - diff,
+                line.baseline_y - line.min_descent - diff,
+            ));
+            if let Some(rich_text) = rich_text.as_ref() {
+                decorate_line(
+                    &mut decorations,
+                    rich_text,
+                    line,
+                    &placement.0,
+                    &spans_by_glyph,
+                    diff,
+                );
+            }
         }
-        let diff = (1f32 - MonoSpacedFont::TEXT_HEIGHT_CORRECTION)
-            * fonts.character_dimensions(text_scale.px()).height;
         for (_key, glyph_position) in placement.0.iter_mut() {
             glyph_position.y -= diff;
         }
     }
 }
+/// number of lines left after applying `max_lines` and, unless clipping, the section height
+fn kept_lines(overflow: &TextOverflow, lines: &[LinePosition], height: f32, diff: f32) -> usize {
+    let mut kept = lines.len();
+    if overflow.mode != TextOverflowMode::Clip {
+        kept = lines
+            .iter()
+            .take_while(|line| line.baseline_y - line.min_descent - diff <= height + 0.5)
+            .count()
+            .max(1);
+    }
+    match overflow.max_lines {
+        Some(max_lines) => kept.min(max_lines as usize),
+        None => kept,
+    }
+}
+/// drops glyphs after `last` and marks the cut with an ellipsis or fade,
+/// realigning the shortened line
+#[allow(clippy::too_many_arguments)]
+fn truncate_overflow(
+    mode: TextOverflowMode,
+    placement: &mut Vec<(Key, GlyphPosition<()>)>,
+    glyph_styles: &mut GlyphStyles,
+    last: &LinePosition,
+    glyphs: &[GlyphPosition<()>],
+    fonts: &FontRegistry,
+    primary: usize,
+    px: f32,
+    width: f32,
+    horizontal_align: HorizontalAlign,
+) {
+    placement.truncate(last.glyph_end + 1);
+    match mode {
+        TextOverflowMode::Clip => {}
+        TextOverflowMode::Ellipsis => {
+            let font_index = fonts.resolve(primary, TextOverflow::ELLIPSIS);
+            let ellipsis_width = fonts.advance(primary, TextOverflow::ELLIPSIS, px);
+            let next_x = |index: usize| match glyphs.get(index + 1) {
+                Some(next) => next.x,
+                None => glyphs[index].x + glyphs[index].width as f32,
+            };
+            let first = &glyphs[last.glyph_start];
+            let start = first.x - bidi::metrics_of(fonts, first).bounds.xmin;
+            let mut end = last.glyph_end;
+            while end > last.glyph_start && next_x(end) - start + ellipsis_width > width {
+                end -= 1;
+            }
+            let x = next_x(end);
+            placement.truncate(end + 1);
+            let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+            layout.append(
+                fonts.font_slice(),
+                &TextStyle::new(TextOverflow::ELLIPSIS.to_string().as_str(), px, font_index),
+            );
+            let baseline = layout
+                .lines()
+                .and_then(|lines| lines.first())
+                .map(|line| line.baseline_y)
+                .unwrap_or_default();
+            if let Some(mut ellipsis) = layout.glyphs().first().copied() {
+                ellipsis.x += x;
+                ellipsis.y += last.baseline_y - baseline;
+                placement.push((Key::new(glyphs.len() as u32), ellipsis));
+            }
+            let alignment = match horizontal_align {
+                HorizontalAlign::Left => return,
+                HorizontalAlign::Center => 0.5f32,
+                HorizontalAlign::Right => 1f32,
+            };
+            let shift = ((width - (x - start + ellipsis_width)) * alignment - start).floor();
+            for (_, glyph) in placement.iter_mut().skip(last.glyph_start) {
+                glyph.x += shift;
+            }
+        }
+        TextOverflowMode::Fade => {
+            let faded = placement
+                .iter()
+                .skip(last.glyph_start)
+                .rev()
+                .filter(|(_, glyph)| !glyph.parent.is_whitespace())
+                .take(TextOverflow::FADE_GLYPHS)
+                .map(|(key, _)| *key)
+                .collect::<Vec<Key>>();
+            for (index, key) in faded.iter().enumerate() {
+                let fade = (index + 1) as f32 / (faded.len() + 1) as f32;
+                glyph_styles.fades.insert(*key, fade);
+            }
+        }
+    }
+}
+/// underline and strikethrough segments for runs of decorated spans on one line
+fn decorate_line(
+    decorations: &mut TextDecorations,
+    rich_text: &RichText,
+    line: &LinePosition,
+    placement: &[(Key, GlyphPosition<()>)],
+    spans_by_glyph: &[Option<usize>],
+    diff: f32,
+) {
+    let end = line.glyph_end.min(placement.len().saturating_sub(1));
+    let mut glyph = line.glyph_start;
+    while glyph <= end && glyph < placement.len() {
+        let span_index = spans_by_glyph[glyph];
+        let mut last = glyph;
+        while last < end && spans_by_glyph[last + 1] == span_index {
+            last += 1;
+        }
+        if let Some(span) = span_index.and_then(|index| rich_text.0.get(index)) {
+            let run = &placement[glyph..=last];
+            let left = run
+                .iter()
+                .map(|(_, glyph)| glyph.x)
+                .fold(f32::MAX, f32::min);
+            let right = run
+                .iter()
+                .map(|(_, glyph)| glyph.x + glyph.width as f32)
+                .fold(f32::MIN, f32::max);
+            let mut heights = vec![];
+            if span.underline {
+                heights.push(line.baseline_y - line.min_descent * 0.5);
+            }
+            if span.strikethrough {
+                heights.push(line.baseline_y - line.max_ascent * 0.3);
+            }
+            for y in heights {
+                decorations.segments.push((
+                    Position::new(left, y - diff),
+                    Position::new(right, y - diff),
+                    span_index.unwrap_or_default(),
+                ));
+            }
+        }
+        glyph = last + 1;
+    }
+}
+/// keeps one `Line` per decoration segment in step with the text's position, layer and colors
+pub(crate) fn decorate(
+    mut text_query: Query<
+        (
+            Entity,
+            &mut TextDecorations,
+            &Position<InterfaceContext>,
+            &Layer,
+            &Color,
+            &Visibility,
+            Option<&RichText>,
+        ),
+        (
+            With<TextTag>,
+            Or<(
+                Changed<TextDecorations>,
+                Changed<Position<InterfaceContext>>,
+                Changed<Layer>,
+                Changed<Color>,
+                Changed<Visibility>,
+            )>,
+        ),
+    >,
+    mut lines: Query<(&mut Path, &mut Layer, &mut Color), (With<LineTag>, Without<TextTag>)>,
+    scale_factor: Res<ScaleFactor>,
+    mut cmd: Commands,
+) {
+    for (entity, mut decorations, pos, layer, color, visibility, rich_text) in text_query.iter_mut()
+    {
+        let decorations = &mut *decorations;
+        let segments = if visibility.visible() {
+            decorations.segments.as_slice()
+        } else {
+            &[]
+        };
+        while decorations.lines.len() > segments.len() {
+            if let Some(line) = decorations.lines.pop() {
+                cmd.entity(line).despawn();
+            }
+        }
+        for (index, (start, end, span)) in segments.iter().enumerate() {
+            let points = [start, end]
+                .iter()
+                .map(|point| {
+                    let point = point.to_interface(scale_factor.factor());
+                    Position::new(pos.x + point.x, pos.y + point.y)
+                })
+                .collect::<Vec<Position<InterfaceContext>>>();
+            let line_color = rich_text
+                .and_then(|rich_text| rich_text.0.get(*span))
+                .and_then(|span| span.color)
+                .unwrap_or(*color);
+            match decorations.lines.get(index) {
+                Some(line) => {
+                    if let Ok((mut path, mut line_layer, mut existing)) = lines.get_mut(*line) {
+                        path.points = points;
+                        *line_layer = *layer;
+                        *existing = line_color;
+                    }
+                }
+                None => {
+                    let line = cmd
+                        .spawn((
+                            Line::new(points, *layer, line_color),
+                            TextDecorationLine(entity),
+                        ))
+                        .id();
+                    decorations.lines.push(line);
+                }
+            }
+        }
+    }
+}
 pub(crate) fn letter_differential(
     mut text_query: Query<
         (
@@ -125,12 +448,22 @@ pub(crate) fn letter_differential(
             &mut Difference,
             &TextScale,
             &Color,
+            &GlyphStyles,
+            Option<&RichText>,
         ),
         Changed<FilteredPlacement>,
     >,
 ) {
-    for (filtered_placement, visibility, mut cache, mut difference, text_scale, color) in
-        text_query.iter_mut()
+    for (
+        filtered_placement,
+        visibility,
+        mut cache,
+        mut difference,
+        text_scale,
+        color,
+        glyph_styles,
+        rich_text,
+    ) in text_query.iter_mut()
     {
         if visibility.visible() {
             let mut retained_keys = HashSet::new();
@@ -139,6 +472,7 @@ pub(crate) fn letter_differential(
             for (key, placed_glyph) in filtered_placement.0.iter() {
                 if placed_glyph.parent.is_ascii_control()
                     || placed_glyph.parent.is_ascii_whitespace()
+                    || (placed_glyph.width == 0 && placed_glyph.height == 0)
                 {
                     if cache.exists(*key) {
                         keys_to_remove.insert(*key);
@@ -148,8 +482,13 @@ pub(crate) fn letter_differential(
                 let glyph_position = (placed_glyph.x, placed_glyph.y).into();
                 let glyph_id = placed_glyph.key;
                 let character = placed_glyph.parent;
-                let glyph = Glyph::new(character, *text_scale, glyph_id);
+                let glyph = Glyph::new(character, *text_scale, glyph_id, placed_glyph.font_index);
+                let color = glyph_styles.color(rich_text, *color, *key);
                 if cache.exists(*key) {
+                    if cache.glyph_color.get(key) != Some(&color) {
+                        difference.glyph_color_update.insert(*key, color);
+                        cache.glyph_color.insert(*key, color);
+                    }
                     retained_keys.insert(*key);
                     if cache.glyph_position_different(*key, glyph_position) {
                         difference.updated.insert(*key, glyph_position);
@@ -160,8 +499,8 @@ pub(crate) fn letter_differential(
                         cache.glyphs.insert(*key, glyph_id);
                     }
                 } else {
-                    difference.glyph_color_update.insert(*key, *color);
-                    cache.glyph_color.insert(*key, *color);
+                    difference.glyph_color_update.insert(*key, color);
+                    cache.glyph_color.insert(*key, color);
                     difference.added.insert(*key, glyph_position);
                     difference.glyph_add.insert(*key, glyph);
                     cache.add(*key, glyph_id, glyph_position);
@@ -191,7 +530,7 @@ pub(crate) fn filter(
             &Area<InterfaceContext>,
             &TextLetterDimensions,
         ),
-        Or<(Changed<TextValue>, Changed<VisibleSection>)>,
+        Or<(Changed<Placement>, Changed<VisibleSection>)>,
     >,
     scale_factor: Res<ScaleFactor>,
 ) {
@@ -222,14 +561,61 @@ pub(crate) fn filter(
         }
     }
 }
+pub(crate) fn auto_scale(
+    mut text_query: Query<
+        (
+            &AutoScale,
+            &TextValue,
+            &Area<InterfaceContext>,
+            &TextWrapStyle,
+            Option<&TextFont>,
+            &mut TextScale,
+        ),
+        Or<(
+            Changed<Area<InterfaceContext>>,
+            Changed<TextValue>,
+            Changed<AutoScale>,
+            Changed<TextWrapStyle>,
+            Changed<TextFont>,
+        )>,
+    >,
+    fonts: Res<FontRegistry>,
+    scale_factor: Res<ScaleFactor>,
+) {
+    let mut measurer = TextMeasurer::new(scale_factor.factor());
+    for (bounds, text, area, wrap_style, text_font, mut text_scale) in text_query.iter_mut() {
+        let font = text_font
+            .map(|font| font.0)
+            .unwrap_or(FontRegistry::DEFAULT);
+        let fitted = measurer.fit_scale(&fonts, font, &text.0, *wrap_style, *area, *bounds);
+        // TextScale is held scaled by `scale_change`
+        if (fitted.px() * scale_factor.factor()) as u32 != text_scale.0 {
+            *text_scale = fitted;
+        }
+    }
+}
 pub(crate) fn scale_change(
-    mut text_query: Query<(&mut TextLetterDimensions, &mut TextScale), Changed<TextScale>>,
+    mut text_query: Query<&mut TextScale, Changed<TextScale>>,
     scale_factor: Res<ScaleFactor>,
-    font: Res<MonoSpacedFont>,
 ) {
-    for (mut text_letter_dimensions, mut text_scale) in text_query.iter_mut() {
+    for mut text_scale in text_query.iter_mut() {
         text_scale.0 = (text_scale.0 as f32 * scale_factor.factor()) as u32;
-        let letter_dimensions = font.character_dimensions(text_scale.px());
+    }
+}
+pub(crate) fn letter_dimensions(
+    mut text_query: Query<
+        (&mut TextLetterDimensions, &TextScale, Option<&TextFont>),
+        Or<(Changed<TextScale>, Changed<TextFont>)>,
+    >,
+    fonts: Res<FontRegistry>,
+) {
+    for (mut text_letter_dimensions, text_scale, text_font) in text_query.iter_mut() {
+        let primary = fonts.index(
+            text_font
+                .map(|font| font.0)
+                .unwrap_or(FontRegistry::DEFAULT),
+        );
+        let letter_dimensions = fonts.character_dimensions(primary, text_scale.px());
         let letter_dimensions =
             Area::<DeviceContext>::from((letter_dimensions.width, letter_dimensions.height));
         *text_letter_dimensions = TextLetterDimensions(letter_dimensions);
@@ -240,31 +626,22 @@ pub(crate) fn manage(
         (
             Entity,
             &Visibility,
-            &TextValue,
             &Position<InterfaceContext>,
             &VisibleSection,
             &Layer,
-            &TextScale,
+            Option<&SdfText>,
             &mut Cache,
             &mut Difference,
         ),
-        Or<(Changed<Visibility>, Added<TextValue>, Changed<TextScale>)>,
+        Or<(Changed<Visibility>, Added<TextValue>, Changed<SdfText>)>,
     >,
     mut removed: RemovedComponents<TextValue>,
+    decoration_lines: Query<(Entity, &TextDecorationLine)>,
     mut extraction: ResMut<Extraction>,
-    fonts: Res<MonoSpacedFont>,
+    mut cmd: Commands,
 ) {
-    for (
-        entity,
-        visibility,
-        text,
-        pos,
-        visible_section,
-        layer,
-        text_scale,
-        mut cache,
-        mut difference,
-    ) in text_query.iter_mut()
+    for (entity, visibility, pos, visible_section, layer, sdf, mut cache, mut difference) in
+        text_query.iter_mut()
     {
         if visibility.visible() {
             *cache = Cache::new();
@@ -272,24 +649,23 @@ pub(crate) fn manage(
             difference.position.replace(*pos);
             difference.layer.replace(*layer);
             difference.visible_section.replace(*visible_section);
-            extraction.added.insert(
-                entity,
-                (
-                    text.0.len() as u32,
-                    *pos,
-                    *visible_section,
-                    *layer,
-                    RenderGroupUniqueGlyphs::from_text(text),
-                    AtlasBlock::new(fonts.character_dimensions(text_scale.px())),
-                ),
-            );
+            extraction
+                .added
+                .insert(entity, (*pos, *visible_section, *layer, sdf.copied()));
         } else {
             extraction.removed.insert(entity);
         }
     }
-    for entity in removed.iter() {
-        extraction.removed.insert(entity);
+    let removed = removed.iter().collect::<HashSet<Entity>>();
+    if removed.is_empty() {
+        return;
+    }
+    for (line, decoration) in decoration_lines.iter() {
+        if removed.contains(&decoration.0) {
+            cmd.entity(line).despawn();
+        }
     }
+    extraction.removed.extend(removed);
 }
 pub(crate) fn position_diff(
     mut text_query: Query<
@@ -321,12 +697,22 @@ pub(crate) fn visible_section_diff(
     }
 }
 pub(crate) fn color_diff(
-    mut text_query: Query<(&Color, &mut Cache, &mut Difference), Changed<Color>>,
+    mut text_query: Query<
+        (
+            &Color,
+            &GlyphStyles,
+            Option<&RichText>,
+            &mut Cache,
+            &mut Difference,
+        ),
+        Changed<Color>,
+    >,
 ) {
-    for (color, mut cache, mut difference) in text_query.iter_mut() {
+    for (color, glyph_styles, rich_text, mut cache, mut difference) in text_query.iter_mut() {
         for key in cache.keys.clone().iter() {
-            difference.glyph_color_update.insert(*key, *color);
-            cache.glyph_color.insert(*key, *color);
+            let color = glyph_styles.color(rich_text, *color, *key);
+            difference.glyph_color_update.insert(*key, color);
+            cache.glyph_color.insert(*key, color);
         }
     }
 }
@@ -355,63 +741,22 @@ pub(crate) fn create_render_groups(
     extraction: Res<Extraction>,
     #[cfg(not(target_family = "wasm"))] mut renderer: ResMut<TextRenderer>,
     #[cfg(target_family = "wasm")] mut renderer: NonSendMut<TextRenderer>,
-    #[cfg(not(target_family = "wasm"))] gfx_surface: Res<GfxSurface>,
-    #[cfg(target_family = "wasm")] gfx_surface: NonSend<GfxSurface>,
     scale_factor: Res<ScaleFactor>,
 ) {
     for entity in extraction.removed.iter() {
-        renderer.render_groups.remove(entity);
+        renderer.remove_group(*entity);
     }
-    for (entity, (max, pos, visible_section, layer, unique_glyphs, atlas_block)) in
-        extraction.added.iter()
-    {
+    for (entity, (pos, visible_section, layer, sdf)) in extraction.added.iter() {
+        renderer.remove_group(*entity);
         let position = pos.to_device(scale_factor.factor());
-        let text_placement = AlignedUniform::new(
-            &gfx_surface.device,
-            Some([position.x, position.y, layer.z, 0.0]),
-        );
-        let render_group_bind_group = RenderGroupBindGroup::new(
-            &gfx_surface,
-            &renderer.render_group_bind_group_layout,
-            &text_placement,
-        );
-        let atlas_dimension = AtlasDimension::from_unique_glyphs(unique_glyphs.unique_glyphs);
-        let atlas = TextureAtlas::new(
-            &gfx_surface,
-            *atlas_block,
-            atlas_dimension,
-            wgpu::TextureFormat::R8Unorm,
-        );
-        let atlas_bind_group = TextureBindGroup::new(
-            &gfx_surface,
-            &renderer.atlas_bind_group_layout,
-            atlas.view(),
-        );
-        renderer.render_groups.insert(
-            *entity,
-            RenderGroup {
-                position,
-                visible_section: *visible_section,
-                position_write: PositionWrite::new(),
-                layer_write: LayerWrite::new(),
-                keyed_glyph_ids: KeyedGlyphIds::new(),
-                draw_section: DrawSection::new(),
-                text_placement,
-                indexer: Indexer::new(*max),
-                glyph_positions: InstanceAttributeManager::new(&gfx_surface, *max),
-                glyph_areas: InstanceAttributeManager::new(&gfx_surface, *max),
-                glyph_colors: InstanceAttributeManager::new(&gfx_surface, *max),
-                null_bits: InstanceAttributeManager::new(&gfx_surface, *max),
-                glyph_tex_coords: InstanceAttributeManager::new(&gfx_surface, *max),
-                render_group_bind_group,
-                atlas,
-                atlas_bind_group,
-                atlas_glyph_references: AtlasGlyphReferences::new(),
-                atlas_write_queue: AtlasWriteQueue::new(),
-                atlas_add_queue: AtlasAddQueue::new(),
-                atlas_glyphs: AtlasGlyphs::new(),
-            },
+        let clip = GlyphClip::new(
+            visible_section
+                .section
+                .map(|section| section.to_device(scale_factor.factor())),
         );
+        renderer
+            .render_groups
+            .insert(*entity, RenderGroup::new(position, *layer, clip, *sdf));
     }
 }
 pub(crate) fn render_group_differences(
@@ -420,298 +765,231 @@ pub(crate) fn render_group_differences(
     #[cfg(target_family = "wasm")] mut renderer: NonSendMut<TextRenderer>,
     #[cfg(not(target_family = "wasm"))] gfx_surface: Res<GfxSurface>,
     #[cfg(target_family = "wasm")] gfx_surface: NonSend<GfxSurface>,
-    font: Res<MonoSpacedFont>,
-    #[cfg(not(target_family = "wasm"))] viewport: Res<Viewport>,
-    #[cfg(target_family = "wasm")] viewport: NonSend<Viewport>,
+    fonts: Res<FontRegistry>,
     scale_factor: Res<ScaleFactor>,
 ) {
+    let renderer = &mut *renderer;
+    renderer.coverage.glyph_cache.tick();
+    renderer.distance_field.glyph_cache.tick();
     for (entity, difference) in extraction.differences.iter() {
         trace!("processing differences for {:?}", entity);
-        let mut render_group = renderer.render_groups.remove(entity).unwrap();
-        let mut draw_section_resize_needed = false;
+        let Some(render_group) = renderer.render_groups.get_mut(entity) else {
+            continue;
+        };
+        let batch = match render_group.sdf {
+            Some(_) => &mut renderer.distance_field,
+            None => &mut renderer.coverage,
+        };
+        let mut placement_dirty = false;
+        let mut clip_dirty = false;
         if let Some(v_sec) = difference.visible_section {
-            render_group.visible_section = v_sec;
-            draw_section_resize_needed = true;
+            render_group.clip = GlyphClip::new(
+                v_sec
+                    .section
+                    .map(|section| section.to_device(scale_factor.factor())),
+            );
+            clip_dirty = true;
         }
         if let Some(position) = difference.position {
             let scaled = position.to_device(scale_factor.factor());
             // TODO without .floor() here glyph positioning is not on pixel boundary and skews px placement
-            let scaled = Position::new(scaled.x.floor(), scaled.y.floor());
-            render_group.position_write.write.replace(scaled);
-            draw_section_resize_needed = true;
+            render_group.position = Position::new(scaled.x.floor(), scaled.y.floor());
+            placement_dirty = true;
         }
         if let Some(layer) = difference.layer {
-            render_group.layer_write.write.replace(layer);
+            render_group.layer = layer;
+            placement_dirty = true;
         }
         for key in difference.remove.iter() {
-            render_group.keyed_glyph_ids.ids.remove(key);
-            let index = render_group.indexer.get_index(*key).unwrap();
-            render_group.null_bits.queue_write(index, NullBit::null());
-            render_group.indexer.remove(*key);
+            if let Some(glyph_id) = render_group.keyed_glyph_ids.ids.remove(key) {
+                batch.glyph_cache.release(glyph_id);
+            }
+            if let Some(index) = batch.indexer.remove((*entity, *key)) {
+                batch.null_bits.queue_write(index, NullBit::null());
+            }
         }
         for (key, glyph_position) in difference.added.iter() {
-            let index = render_group.indexer.next(*key);
+            let index = batch.indexer.next((*entity, *key));
             let glyph_position_raw =
                 Position::<DeviceContext>::new(glyph_position.x.floor(), glyph_position.y.floor())
                     .as_raw();
-            render_group
-                .glyph_positions
-                .queue_write(index, glyph_position_raw);
-            render_group
-                .null_bits
-                .queue_write(index, NullBit::not_null());
+            batch.glyph_positions.queue_write(index, glyph_position_raw);
+            batch
+                .glyph_placements
+                .queue_write(index, render_group.placement());
+            batch.glyph_clips.queue_write(index, render_group.clip);
+            batch.null_bits.queue_write(index, NullBit::not_null());
         }
         for (key, color) in difference.glyph_color_update.iter() {
-            let index = render_group.indexer.get_index(*key).unwrap();
-            render_group.glyph_colors.queue_write(index, *color);
-        }
-        if render_group.indexer.should_grow() {
-            let max = render_group.indexer.max();
-            render_group.glyph_positions.grow(&gfx_surface, max);
-            render_group.glyph_areas.grow(&gfx_surface, max);
-            render_group.glyph_colors.grow(&gfx_surface, max);
-            render_group.glyph_tex_coords.grow(&gfx_surface, max);
-            render_group.null_bits.grow(&gfx_surface, max);
+            let index = batch.indexer.get_index((*entity, *key)).unwrap();
+            batch.glyph_colors.queue_write(index, *color);
         }
         for (key, glyph_position) in difference.updated.iter() {
-            let index = render_group.indexer.get_index(*key).unwrap();
+            let index = batch.indexer.get_index((*entity, *key)).unwrap();
             let glyph_position_raw =
                 Position::<DeviceContext>::new(glyph_position.x.floor(), glyph_position.y.floor())
                     .as_raw();
-            render_group
-                .glyph_positions
-                .queue_write(index, glyph_position_raw);
+            batch.glyph_positions.queue_write(index, glyph_position_raw);
         }
         for (key, glyph) in difference.glyph_add.iter() {
-            render_group.keyed_glyph_ids.ids.insert(*key, glyph.id);
-            render_group.atlas_add_queue.queue.insert(glyph.clone());
-        }
-        let mut add_retained_glyphs = HashSet::new();
-        for glyph in render_group.atlas_add_queue.queue.iter() {
-            if render_group.atlas_glyphs.glyphs.contains_key(&glyph.id) {
-                add_retained_glyphs.insert(glyph.clone());
-            }
-        }
-        for glyph in add_retained_glyphs {
-            render_group
-                .atlas_glyph_references
-                .references
-                .get_mut(&glyph.id)
-                .unwrap()
-                .increment();
-            render_group.atlas_add_queue.queue.remove(&glyph);
-        }
-        let mut orphaned_glyphs = HashSet::new();
-        for (glyph_id, reference) in render_group.atlas_glyph_references.references.iter() {
-            if reference.count == 0 {
-                orphaned_glyphs.insert(*glyph_id);
+            batch.glyph_cache.acquire(
+                &gfx_surface,
+                &renderer.atlas_bind_group_layout,
+                &fonts,
+                glyph,
+            );
+            if let Some(replaced) = render_group.keyed_glyph_ids.ids.insert(*key, glyph.id) {
+                batch.glyph_cache.release(replaced);
             }
-        }
-        // retain filter
-        // ...
-        // free
-        for glyph_id in orphaned_glyphs {
-            let (_, _, location, _) = render_group.atlas_glyphs.glyphs.remove(&glyph_id).unwrap();
-            render_group.atlas.free_locations.free.insert(location);
-            render_group
-                .atlas_glyph_references
-                .references
-                .remove(&glyph_id);
-        }
-        let adjusted_glyphs = {
-            let mut adjusted_glyphs = HashSet::new();
-            let num_new_glyphs = render_group.atlas_add_queue.queue.len() as u32;
-            if num_new_glyphs != 0
-                && num_new_glyphs > render_group.atlas.free_locations.free.len() as u32
-            {
-                let current_dimension = render_group.atlas.dimension.dimension;
-                let current_total = current_dimension.pow(2);
-                let mut incremental_dimension_add = 1;
-                let mut next_size_up_total = (current_dimension + incremental_dimension_add).pow(2);
-                while next_size_up_total - current_total < num_new_glyphs {
-                    incremental_dimension_add += 1;
-                    next_size_up_total = (current_dimension + incremental_dimension_add).pow(2);
-                }
-                let new_dimension =
-                    AtlasDimension::new(current_dimension + incremental_dimension_add);
-                render_group.atlas = TextureAtlas::new(
-                    &gfx_surface,
-                    render_group.atlas.block,
-                    new_dimension,
-                    wgpu::TextureFormat::R8Unorm,
-                );
-                render_group.atlas_bind_group = TextureBindGroup::new(
-                    &gfx_surface,
-                    &renderer.atlas_bind_group_layout,
-                    render_group.atlas.view(),
+            let cached = batch.glyph_cache.get(glyph.id).unwrap();
+            let index = batch.indexer.get_index((*entity, *key)).unwrap();
+            let mut area = cached.area;
+            if let (Some(sdf), Some(effects)) = (render_group.sdf, batch.glyph_effects.as_mut()) {
+                let glyph_scale = glyph.id.px / SdfText::GLYPH_PX;
+                area = Area::new(area.width * glyph_scale, area.height * glyph_scale);
+                effects.queue_write(
+                    index,
+                    GlyphEffects::new(&sdf, glyph.id.px, scale_factor.factor()),
                 );
-                let mut writes = Vec::<(
-                    GlyphId,
-                    AtlasLocation,
-                    TextureCoordinates,
-                    Area<NumericalContext>,
-                    Bitmap,
-                )>::new();
-                for (glyph_id, (_, glyph_area, atlas_location, bitmap)) in
-                    render_group.atlas_glyphs.glyphs.iter()
-                {
-                    let position = AtlasPosition::new(*atlas_location, render_group.atlas.block);
-                    let glyph_section = Section::new(position.position, *glyph_area);
-                    let coords = TextureCoordinates::from_section(
-                        glyph_section,
-                        render_group.atlas.texture_dimensions,
-                    );
-                    writes.push((
-                        *glyph_id,
-                        *atlas_location,
-                        coords,
-                        *glyph_area,
-                        bitmap.clone(),
-                    ));
-                    render_group
-                        .atlas
-                        .free_locations
-                        .free
-                        .remove(atlas_location);
-                    adjusted_glyphs.insert(*glyph_id);
-                }
-                for write in writes {
-                    render_group
-                        .atlas_glyphs
-                        .glyphs
-                        .get_mut(&write.0)
-                        .unwrap()
-                        .0 = write.2;
-                    render_group
-                        .atlas_write_queue
-                        .queue
-                        .insert(write.1, (write.2, write.3, write.4));
-                }
             }
-            adjusted_glyphs
-        };
-        let add_queue = render_group
-            .atlas_add_queue
-            .queue
-            .drain()
-            .collect::<HashSet<Glyph>>();
-        for add in add_queue {
-            render_group
-                .atlas_glyph_references
-                .references
-                .insert(add.id, AtlasGlyphReference::new());
-            render_group
-                .atlas_glyph_references
-                .references
-                .get_mut(&add.id)
-                .unwrap()
-                .increment();
-            let rasterization = font.font().rasterize(add.character, add.scale.px());
-            // TODO since subpixel , combine them here to save space
-            let glyph_area: Area<NumericalContext> =
-                (rasterization.0.width, rasterization.0.height).into();
-            let location = render_group.atlas.free_locations.next();
-            let position = AtlasPosition::new(location, render_group.atlas.block);
-            let glyph_section = Section::new(position.position, glyph_area);
-            let coords = TextureCoordinates::from_section(
-                glyph_section,
-                render_group.atlas.texture_dimensions,
-            );
-            render_group
-                .atlas_write_queue
-                .queue
-                .insert(location, (coords, glyph_area, rasterization.1.clone()));
-            render_group
-                .atlas_glyphs
-                .glyphs
-                .insert(add.id, (coords, glyph_area, location, rasterization.1));
-        }
-        let mut glyph_info_writes = HashSet::<(Key, GlyphId)>::new();
-        for adj_glyph in adjusted_glyphs {
-            for (key, glyph_id) in render_group.keyed_glyph_ids.ids.iter() {
-                if adj_glyph == *glyph_id {
-                    glyph_info_writes.insert((*key, *glyph_id));
+            batch.glyph_areas.queue_write(index, area.as_raw());
+            batch.glyph_tex_coords.queue_write(index, cached.coords);
+        }
+        if placement_dirty || clip_dirty {
+            let placement = render_group.placement();
+            for key in render_group.keyed_glyph_ids.ids.keys() {
+                let index = batch.indexer.get_index((*entity, *key)).unwrap();
+                if placement_dirty {
+                    batch.glyph_placements.queue_write(index, placement);
+                }
+                if clip_dirty {
+                    batch.glyph_clips.queue_write(index, render_group.clip);
                 }
             }
         }
-        for (key, glyph_id) in glyph_info_writes {
-            let (coords, area, _, _) = render_group
-                .atlas_glyphs
-                .glyphs
-                .get(&glyph_id)
-                .unwrap()
-                .clone();
-            let index = render_group.indexer.get_index(key).unwrap();
-            render_group.glyph_areas.queue_write(index, area.as_raw());
-            render_group.glyph_tex_coords.queue_write(index, coords);
-        }
-        for (key, glyph) in difference.glyph_add.iter() {
-            let (coords, area, _, _) = render_group
-                .atlas_glyphs
-                .glyphs
-                .get(&glyph.id)
-                .unwrap()
-                .clone();
-            let index = render_group.indexer.get_index(*key).unwrap();
-            render_group.glyph_areas.queue_write(index, area.as_raw());
-            render_group.glyph_tex_coords.queue_write(index, coords);
-        }
-        render_group.glyph_positions.write(&gfx_surface);
-        render_group.glyph_areas.write(&gfx_surface);
-        render_group.glyph_colors.write(&gfx_surface);
-        render_group.glyph_tex_coords.write(&gfx_surface);
-        render_group.null_bits.write(&gfx_surface);
-        let mut dirty = false;
-        if let Some(position) = render_group.position_write.write.take() {
-            render_group.position = position;
-            render_group.text_placement.set_aspect(0, position.x);
-            render_group.text_placement.set_aspect(1, position.y);
-            dirty = true;
-        }
-        if let Some(layer) = render_group.layer_write.write.take() {
-            render_group.text_placement.set_aspect(2, layer.z);
-            dirty = true;
-        }
-        if dirty {
-            render_group.text_placement.update(&gfx_surface.queue);
-        }
-        for (location, (_, glyph_area, bitmap)) in render_group.atlas_write_queue.queue.iter() {
-            render_group
-                .atlas
-                .write::<u8>(*location, bitmap.as_slice(), *glyph_area, &gfx_surface);
-        }
-        if draw_section_resize_needed {
-            if let Some(v_sec) = render_group.visible_section.section {
-                let v_sec = v_sec.to_device(scale_factor.factor());
-                let draw_bound = Section::<DeviceContext>::new(
-                    v_sec.position - viewport.as_section().position,
-                    v_sec.area,
-                );
-                render_group.draw_section.section.replace(draw_bound);
+    }
+    for batch in [&mut renderer.coverage, &mut renderer.distance_field] {
+        if batch.glyph_cache.rebuilt {
+            for (entity, render_group) in renderer.render_groups.iter() {
+                if render_group.sdf.is_some()
+                    != (batch.glyph_cache.rasterization == GlyphRasterization::DistanceField)
+                {
+                    continue;
+                }
+                for (key, glyph_id) in render_group.keyed_glyph_ids.ids.iter() {
+                    let cached = batch.glyph_cache.get(*glyph_id).unwrap();
+                    let index = batch.indexer.get_index((*entity, *key)).unwrap();
+                    batch.glyph_tex_coords.queue_write(index, cached.coords);
+                }
             }
+            batch.glyph_cache.rebuilt = false;
         }
-        renderer.render_groups.insert(*entity, render_group);
+        batch.grow(&gfx_surface);
+        batch.write(&gfx_surface);
     }
     *extraction = Extraction::new();
 }
-pub(crate) fn resolve_draw_section_on_resize(
-    #[cfg(not(target_family = "wasm"))] mut renderer: ResMut<TextRenderer>,
-    #[cfg(target_family = "wasm")] mut renderer: NonSendMut<TextRenderer>,
-    mut event_reader: EventReader<WindowResize>,
-    #[cfg(not(target_family = "wasm"))] viewport: Res<Viewport>,
-    #[cfg(target_family = "wasm")] viewport: NonSend<Viewport>,
-    scale_factor: Res<ScaleFactor>,
-) {
-    for _event in event_reader.iter() {
-        for (_, render_group) in renderer.render_groups.iter_mut() {
-            if let Some(v_sec) = render_group.visible_section.section {
-                let v_sec = v_sec.to_device(scale_factor.factor());
-                let draw_bound = Section::<DeviceContext>::new(
-                    v_sec.position - viewport.as_section().position,
-                    v_sec.area,
-                );
-                render_group.draw_section.section.replace(draw_bound);
-            }
-        }
-    }
+
+#[cfg(test)]
+#[test]
+fn decoration_lines() {
+    use bevy_ecs::prelude::{IntoSystemConfigs, Schedule, World};
+    let mut world = World::new();
+    world.insert_resource(Extraction::new());
+    world.insert_resource(ScaleFactor::new(1f32));
+    let text = world
+        .spawn((
+            TextTag::new(),
+            TextValue("underlined".to_string()),
+            TextDecorations {
+                segments: vec![(Position::new(0f32, 10f32), Position::new(40f32, 10f32), 0)],
+                lines: vec![],
+            },
+            Position::<InterfaceContext>::new(5f32, 5f32),
+            Layer::new(3f32),
+            Color::from_rgb(1f32, 1f32, 1f32),
+            Visibility::shown(),
+        ))
+        .id();
+    let mut schedule = Schedule::default();
+    schedule.add_systems((decorate, manage).chain());
+    schedule.run(&mut world);
+    let mut lines = world.query_filtered::<(&Layer, &Path), With<LineTag>>();
+    let (layer, path) = lines.single(&world);
+    assert_eq!(layer.z, 3f32);
+    assert_eq!(path.points[1], Position::new(45f32, 15f32));
+    world.get_mut::<Layer>(text).unwrap().z = 1f32;
+    schedule.run(&mut world);
+    assert_eq!(lines.single(&world).0.z, 1f32);
+    world.despawn(text);
+    schedule.run(&mut world);
+    assert_eq!(lines.iter(&world).count(), 0);
+}
+
+#[cfg(test)]
+#[test]
+fn overflow_and_alignment() {
+    use crate::{TextHorizontalAlignment, TextVerticalAlignment};
+    use fontdue::layout::WrapStyle;
+    let fonts = FontRegistry::new();
+    let primary = fonts.index(FontRegistry::DEFAULT);
+    let (px, width) = (20f32, 200f32);
+    let text = "one two three four five six seven eight nine ten";
+    let layout = |alignment: TextAlignment| {
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        layout.reset(&LayoutSettings {
+            max_width: Some(width),
+            horizontal_align: alignment.horizontal_align(false),
+            wrap_style: WrapStyle::Word,
+            ..LayoutSettings::default()
+        });
+        layout.append(fonts.font_slice(), &TextStyle::new(text, px, primary));
+        layout
+    };
+    let centered = layout(TextAlignment::centered());
+    let lines = centered.lines().expect("lines").clone();
+    assert!(lines.len() > 2);
+    let one_line = lines[0].baseline_y - lines[0].min_descent;
+    let two_lines = TextOverflow::ellipsis().with_max_lines(2);
+    assert_eq!(kept_lines(&two_lines, &lines, f32::MAX, 0f32), 2);
+    assert_eq!(kept_lines(&two_lines, &lines, one_line, 0f32), 1);
+    assert_eq!(
+        kept_lines(&TextOverflow::clip(), &lines, one_line, 0f32),
+        lines.len()
+    );
+    let truncated = |layout: &Layout, horizontal_align: HorizontalAlign| {
+        let mut placement = layout
+            .glyphs()
+            .iter()
+            .enumerate()
+            .map(|(index, glyph)| (Key::new(index as u32), *glyph))
+            .collect::<Vec<(Key, GlyphPosition<()>)>>();
+        truncate_overflow(
+            TextOverflowMode::Ellipsis,
+            &mut placement,
+            &mut GlyphStyles::default(),
+            &lines[1],
+            layout.glyphs(),
+            &fonts,
+            primary,
+            px,
+            width,
+            horizontal_align,
+        );
+        let pen = |glyph: &GlyphPosition<()>| glyph.x - bidi::metrics_of(&fonts, glyph).bounds.xmin;
+        let ellipsis = placement.last().expect("ellipsis").1;
+        assert_eq!(ellipsis.parent, TextOverflow::ELLIPSIS);
+        assert!(placement.len() <= lines[1].glyph_end + 2);
+        let left = pen(&placement[lines[1].glyph_start].1);
+        let right = pen(&ellipsis) + fonts.advance(primary, TextOverflow::ELLIPSIS, px);
+        (left, width - right)
+    };
+    let (left, right) = truncated(&centered, HorizontalAlign::Center);
+    assert!((left - right).abs() <= 2f32);
+    let start = layout(TextAlignment::new(
+        TextHorizontalAlignment::Start,
+        TextVerticalAlignment::Top,
+    ));
+    let (left, _) = truncated(&start, HorizontalAlign::Left);
+    assert!(left.abs() <= 1f32);
 }
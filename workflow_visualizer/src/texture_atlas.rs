@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use bytemuck::{Pod, Zeroable};
-use wgpu::{BindGroupEntry, BindGroupLayoutEntry, Texture, TextureView};
+use wgpu::{BindGroupEntry, BindGroupLayoutEntry};
 
 use crate::{Area, GfxOptions, GfxSurface, NumericalContext, Position, Section};
 pub struct TextureSampler {
@@ -31,8 +31,8 @@ impl TextureSampler {
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
             mipmap_filter: wgpu::FilterMode::Linear,
-            lod_min_clamp: Default::default(),
-            lod_max_clamp: Default::default(),
+            lod_min_clamp: 0.0,
+            lod_max_clamp: 32.0,
             compare: None,
             anisotropy_clamp: 1,
             border_color: None,
@@ -67,12 +67,6 @@ impl TextureAtlas {
             free_locations: AtlasFreeLocations::new(dimension),
         }
     }
-    pub(crate) fn texture(&self) -> &Texture {
-        &self.texture.resource
-    }
-    pub(crate) fn view(&self) -> &TextureView {
-        &self.texture.view
-    }
     pub fn write<TexelData: Sized>(
         &self,
         location: AtlasLocation,
@@ -81,31 +75,8 @@ impl TextureAtlas {
         gfx: &GfxSurface,
     ) -> TextureCoordinates {
         let position = AtlasPosition::new(location, self.block).position;
-        let image_copy_texture = wgpu::ImageCopyTexture {
-            texture: self.texture(),
-            mip_level: 0,
-            origin: wgpu::Origin3d {
-                x: position.x as u32,
-                y: position.y as u32,
-                z: 0,
-            },
-            aspect: wgpu::TextureAspect::All,
-        };
-        let extent_w = extent_dim.width as u32;
-        let extent_h = extent_dim.height as u32;
-        let image_data_layout = wgpu::ImageDataLayout {
-            offset: 0,
-            bytes_per_row: Some(extent_w * std::mem::size_of::<TexelData>() as u32),
-            rows_per_image: Some(extent_h * std::mem::size_of::<TexelData>() as u32),
-        };
-        let size = wgpu::Extent3d {
-            width: extent_w,
-            height: extent_h,
-            depth_or_array_layers: 1,
-        };
-        gfx.queue
-            .write_texture(image_copy_texture, data, image_data_layout, size);
-        let position = AtlasPosition::new(location, self.block).position;
+        self.texture
+            .write::<TexelData>(position, data, extent_dim, gfx);
         TextureCoordinates::from_section(
             Section::new(position, extent_dim),
             self.texture_dimensions,
@@ -194,9 +165,19 @@ impl AtlasTexture {
         gfx_surface: &GfxSurface,
         texture_dimensions: AtlasTextureDimensions,
         texture_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self::with_mip_levels(gfx_surface, texture_dimensions, texture_format, 1)
+    }
+    /// texture with `mip_level_count` levels, each written by `write_level`
+    pub fn with_mip_levels(
+        gfx_surface: &GfxSurface,
+        texture_dimensions: AtlasTextureDimensions,
+        texture_format: wgpu::TextureFormat,
+        mip_level_count: u32,
     ) -> Self {
         let formats = [texture_format];
-        let descriptor = Self::texture_descriptor(texture_dimensions, &formats);
+        let mut descriptor = Self::texture_descriptor(texture_dimensions, &formats);
+        descriptor.mip_level_count = mip_level_count;
         let texture = gfx_surface.device.create_texture(&descriptor);
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         Self {
@@ -204,6 +185,50 @@ impl AtlasTexture {
             view,
         }
     }
+    /// copies `data` into the texels of the region at `position` sized `extent_dim`
+    pub fn write<TexelData: Sized>(
+        &self,
+        position: Position<NumericalContext>,
+        data: &[u8],
+        extent_dim: Area<NumericalContext>,
+        gfx: &GfxSurface,
+    ) {
+        self.write_level::<TexelData>(position, data, extent_dim, 0, gfx);
+    }
+    /// `write` into mip level `mip_level`; `position` and `extent_dim` are in its texels
+    pub fn write_level<TexelData: Sized>(
+        &self,
+        position: Position<NumericalContext>,
+        data: &[u8],
+        extent_dim: Area<NumericalContext>,
+        mip_level: u32,
+        gfx: &GfxSurface,
+    ) {
+        let image_copy_texture = wgpu::ImageCopyTexture {
+            texture: &self.resource,
+            mip_level,
+            origin: wgpu::Origin3d {
+                x: position.x as u32,
+                y: position.y as u32,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        };
+        let extent_w = extent_dim.width as u32;
+        let extent_h = extent_dim.height as u32;
+        let image_data_layout = wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(extent_w * std::mem::size_of::<TexelData>() as u32),
+            rows_per_image: Some(extent_h * std::mem::size_of::<TexelData>() as u32),
+        };
+        let size = wgpu::Extent3d {
+            width: extent_w,
+            height: extent_h,
+            depth_or_array_layers: 1,
+        };
+        gfx.queue
+            .write_texture(image_copy_texture, data, image_data_layout, size);
+    }
     fn hardware_max_check(texture_dimensions: AtlasTextureDimensions) {
         let hardware_max = GfxOptions::limited_environment()
             .limits
@@ -276,17 +301,6 @@ impl AtlasDimension {
     pub fn new(dimension: u32) -> Self {
         Self { dimension }
     }
-    pub(crate) fn from_unique_glyphs(unique_glyphs: u32) -> Self {
-        Self {
-            dimension: {
-                let mut dimension = (unique_glyphs as f32).sqrt() as u32;
-                while dimension.pow(2) < unique_glyphs {
-                    dimension += 1;
-                }
-                dimension.max(1)
-            },
-        }
-    }
 }
 
 #[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
@@ -331,6 +345,85 @@ impl AtlasFreeLocations {
         location
     }
 }
+/// Row of an `AtlasShelves` texture; regions are placed left to right
+#[derive(Copy, Clone, Debug)]
+struct AtlasShelf {
+    y: f32,
+    height: f32,
+    x: f32,
+}
+/// Shelf packer placing differently sized regions into one texture.
+/// Freed slots are reused by later regions that fit inside them.
+pub struct AtlasShelves {
+    pub dimensions: Area<NumericalContext>,
+    /// slot positions and sizes are multiples of this
+    pub alignment: f32,
+    shelves: Vec<AtlasShelf>,
+    freed: Vec<Section<NumericalContext>>,
+}
+
+impl AtlasShelves {
+    pub fn new<T: Into<Area<NumericalContext>>>(dimensions: T) -> Self {
+        Self::aligned(dimensions, 1.0)
+    }
+    /// slots keep `alignment` texel boundaries so they stay whole in mip levels
+    pub fn aligned<T: Into<Area<NumericalContext>>>(dimensions: T, alignment: f32) -> Self {
+        Self {
+            dimensions: dimensions.into(),
+            alignment,
+            shelves: vec![],
+            freed: vec![],
+        }
+    }
+    /// reserves a padded slot for a region of `area`; None when the texture is full
+    pub fn allocate(&mut self, area: Area<NumericalContext>) -> Option<Section<NumericalContext>> {
+        let align = |extent: f32| (extent / self.alignment).ceil() * self.alignment;
+        let padded = Area::<NumericalContext>::new(
+            align(area.width + TextureAtlas::ATLAS_PADDING),
+            align(area.height + TextureAtlas::ATLAS_PADDING),
+        );
+        let reused = self
+            .freed
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.width() >= padded.width && slot.height() >= padded.height)
+            .min_by(|(_, a), (_, b)| (a.width() * a.height()).total_cmp(&(b.width() * b.height())))
+            .map(|(index, _)| index);
+        if let Some(index) = reused {
+            return Some(self.freed.swap_remove(index));
+        }
+        let fitting = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| {
+                shelf.height >= padded.height && shelf.x + padded.width <= self.dimensions.width
+            })
+            .min_by(|a, b| a.height.total_cmp(&b.height));
+        if let Some(shelf) = fitting {
+            let slot = Section::new((shelf.x, shelf.y), (padded.width, shelf.height));
+            shelf.x += padded.width;
+            return Some(slot);
+        }
+        let y = self
+            .shelves
+            .last()
+            .map(|shelf| shelf.y + shelf.height)
+            .unwrap_or_default();
+        if y + padded.height > self.dimensions.height || padded.width > self.dimensions.width {
+            return None;
+        }
+        self.shelves.push(AtlasShelf {
+            y,
+            height: padded.height,
+            x: padded.width,
+        });
+        Some(Section::new((0.0, y), (padded.width, padded.height)))
+    }
+    /// returns a slot from `allocate` for reuse
+    pub fn free(&mut self, slot: Section<NumericalContext>) {
+        self.freed.push(slot);
+    }
+}
 pub struct AtlasPosition {
     pub position: Position<NumericalContext>,
 }
@@ -346,3 +439,25 @@ impl AtlasPosition {
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn shelf_packing() {
+    let mut shelves = AtlasShelves::new((64, 64));
+    let tall = shelves.allocate((20, 30).into()).unwrap();
+    let short = shelves.allocate((20, 10).into()).unwrap();
+    assert_eq!(short.position, Position::new(21.0, 0.0));
+    let wide = shelves.allocate((60, 10).into()).unwrap();
+    assert_eq!(wide.position, Position::new(0.0, 31.0));
+    assert!(shelves.allocate((64, 64).into()).is_none());
+    shelves.free(tall);
+    assert_eq!(shelves.allocate((16, 16).into()), Some(tall));
+    let mut aligned = AtlasShelves::aligned((64, 64), 16.0);
+    assert_eq!(
+        aligned.allocate((15, 20).into()).unwrap().area,
+        Area::new(16.0, 32.0)
+    );
+    let next = aligned.allocate((16, 16).into()).unwrap();
+    assert_eq!(next.position, Position::new(16.0, 0.0));
+    assert_eq!(next.area, Area::new(32.0, 32.0));
+}
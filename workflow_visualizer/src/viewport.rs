@@ -128,12 +128,6 @@ impl Viewport {
             offset_uniform,
         }
     }
-    pub(crate) fn as_section(&self) -> Section<DeviceContext> {
-        Section::new(
-            Position::<DeviceContext>::new(self.offset.offset[0], self.offset.offset[1]),
-            self.cpu.area,
-        )
-    }
     pub(crate) fn adjust_area(
         &mut self,
         gfx_surface: &GfxSurface,
@@ -178,7 +172,6 @@ fn depth_texture(
 
 #[derive(Resource)]
 pub(crate) struct CpuViewport {
-    pub(crate) area: Area<DeviceContext>,
     pub(crate) far_layer: Layer,
     pub(crate) orthographic: nalgebra::Matrix4<f32>,
 }
@@ -186,7 +179,6 @@ pub(crate) struct CpuViewport {
 impl CpuViewport {
     pub(crate) fn new(area: Area<DeviceContext>, far_layer: Layer) -> Self {
         Self {
-            area,
             far_layer,
             orthographic: matrix![2f32/area.width, 0.0, 0.0, -1.0;
                                     0.0, 2f32/-area.height, 0.0, 1.0;
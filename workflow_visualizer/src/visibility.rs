@@ -37,6 +37,10 @@ impl Visibility {
     pub fn visible(&self) -> bool {
         self.visible
     }
+    #[cfg(test)]
+    pub(crate) fn shown() -> Self {
+        Self { visible: true }
+    }
 }
 /// What part of the entity is visible
 #[derive(Component, Copy, Clone, Default)]
@@ -1,17 +1,39 @@
+use crate::path::Path;
 use crate::{
-    Area, Attach, Color, InterfaceContext, Layer, Line, Position, Section, SyncPoint, Visualizer,
+    Area, Attach, Color, GridDirection, GridUnit, InterfaceContext, KeyboardInput, Layer, Line,
+    Position, ResponsiveGridPoint, ResponsiveGridView, Section, SnapGrid, SnapGridConfig,
+    SyncPoint, Tag, Text, TextValue, TextWrapStyle, ViewportHandle, VisibleSection, Visualizer,
 };
+use bevy_ecs::change_detection::DetectChanges;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
-use bevy_ecs::prelude::IntoSystemConfigs;
+use bevy_ecs::event::EventReader;
+use bevy_ecs::prelude::{
+    IntoSystemConfigs, Local, RemovedComponents, Res, ResMut, Resource, With, Without,
+};
 use bevy_ecs::query::{Changed, Or};
 use bevy_ecs::system::{Commands, Query};
+use std::collections::{HashMap, HashSet};
+use winit::keyboard::{Key, NamedKey};
 
 #[derive(Component, Copy, Clone, Debug, Default)]
 pub struct SectionOutline(Option<Entity>);
+/// marks a `Line` drawing the `SectionOutline` of the entity it holds
+#[derive(Component, Copy, Clone)]
+pub(crate) struct SectionOutlineLine(Entity);
+fn outline_path(section: Section<InterfaceContext>) -> Vec<Position<InterfaceContext>> {
+    vec![
+        Position::new(section.left(), section.top()),
+        Position::new(section.left(), section.bottom()),
+        Position::new(section.right(), section.bottom()),
+        Position::new(section.right(), section.top()),
+        Position::new(section.left(), section.top()),
+    ]
+}
 pub(crate) fn section_outline(
     mut query: Query<
         (
+            Entity,
             &mut SectionOutline,
             &Position<InterfaceContext>,
             &Area<InterfaceContext>,
@@ -23,27 +45,304 @@ pub(crate) fn section_outline(
             Changed<Area<InterfaceContext>>,
         )>,
     >,
+    mut paths: Query<&mut Path>,
+    mut removed: RemovedComponents<SectionOutline>,
+    outline_lines: Query<(Entity, &SectionOutlineLine)>,
     mut cmd: Commands,
 ) {
-    for (mut outline, pos, area, layer) in query.iter_mut() {
-        let section = Section::new(*pos, *area);
-        let path = vec![
-            Position::new(section.left(), section.top()),
-            Position::new(section.left(), section.bottom()),
-            Position::new(section.right(), section.bottom()),
-            Position::new(section.right(), section.top()),
-            Position::new(section.left(), section.top()),
-        ];
+    let removed = removed.iter().collect::<HashSet<Entity>>();
+    if !removed.is_empty() {
+        for (line, outline_line) in outline_lines.iter() {
+            if removed.contains(&outline_line.0) {
+                cmd.entity(line).despawn();
+            }
+        }
+    }
+    for (entity, mut outline, pos, area, layer) in query.iter_mut() {
+        let path = outline_path(Section::new(*pos, *area));
+        if let Some(mut existing) = outline.0.and_then(|line| paths.get_mut(line).ok()) {
+            existing.points = path;
+            continue;
+        }
         let line = Line::new(path, *layer - 1.into(), Color::OFF_WHITE);
-        let id = cmd.spawn(line).id();
+        let id = cmd.spawn((line, SectionOutlineLine(entity))).id();
         outline.0.replace(id);
     }
 }
+/// Runtime overlay of the `SnapGrid` columns/rows and every entity's section.
+/// Toggled by pressing `DebugOverlay::SHORTCUT` or via `toggle`/`set_enabled`.
+#[derive(Resource, Copy, Clone, Debug, Default)]
+pub struct DebugOverlay {
+    enabled: bool,
+}
+impl DebugOverlay {
+    pub const SHORTCUT: NamedKey = NamedKey::F12;
+    pub const LABEL_SCALE: u32 = 12;
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
+/// Marks entities spawned by the overlay so they are not outlined themselves
+pub type DebugOverlayTag = Tag<DebugOverlay>;
+#[derive(Copy, Clone)]
+pub(crate) struct OverlayOutline {
+    section: Entity,
+    visible: Entity,
+    label: Option<Entity>,
+}
+#[derive(Default)]
+pub(crate) struct OverlayEntities {
+    columns: Option<Entity>,
+    rows: Option<Entity>,
+    outlines: HashMap<Entity, OverlayOutline>,
+}
+impl OverlayEntities {
+    fn drain(&mut self) -> Vec<Entity> {
+        let mut entities = vec![];
+        entities.extend(self.columns.take());
+        entities.extend(self.rows.take());
+        for (_, outline) in self.outlines.drain() {
+            entities.push(outline.section);
+            entities.push(outline.visible);
+            entities.extend(outline.label);
+        }
+        entities
+    }
+}
+pub(crate) fn toggle_debug_overlay(
+    mut events: EventReader<KeyboardInput>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    for event in events.iter() {
+        if event.pressed(Key::Named(DebugOverlay::SHORTCUT)) {
+            overlay.toggle();
+        }
+    }
+}
+fn grid_path(
+    grid: &SnapGrid,
+    direction: GridDirection,
+    segments: i32,
+    extent: f32,
+) -> Vec<Position<InterfaceContext>> {
+    let mut points = vec![];
+    for segment in 1..=segments {
+        let near = grid.location_coordinates(direction, segment.near());
+        let far = grid.location_coordinates(direction, segment.far());
+        let (start, end) = if segment % 2 == 1 {
+            (0f32, extent)
+        } else {
+            (extent, 0f32)
+        };
+        let edges = [(near, start), (near, end), (far, end), (far, start)];
+        for (along, across) in edges {
+            points.push(match direction {
+                GridDirection::Horizontal => Position::new(along, across),
+                GridDirection::Vertical => Position::new(across, along),
+            });
+        }
+    }
+    points
+}
+fn grid_label(
+    grid: &SnapGrid,
+    point: Option<&ResponsiveGridPoint>,
+    view: Option<&ResponsiveGridView>,
+) -> Option<String> {
+    if let Some(view) = view {
+        let view = view.current(grid.breakpoint, grid.vertical_breakpoint, grid.orientation);
+        return Some(format!(
+            "c{}-{} r{}-{}",
+            view.horizontal.begin.marker.0,
+            view.horizontal.end.marker.0,
+            view.vertical.begin.marker.0,
+            view.vertical.end.marker.0
+        ));
+    }
+    point.map(|point| {
+        let point = point.current(grid.breakpoint, grid.vertical_breakpoint, grid.orientation);
+        format!("c{} r{}", point.x.marker.0, point.y.marker.0)
+    })
+}
+pub(crate) fn debug_overlay(
+    overlay: Res<DebugOverlay>,
+    grid: Res<SnapGrid>,
+    config: Res<SnapGridConfig>,
+    viewport_handle: Res<ViewportHandle>,
+    outlined: Query<
+        (
+            Entity,
+            &Position<InterfaceContext>,
+            &Area<InterfaceContext>,
+            Option<&VisibleSection>,
+            Option<&ResponsiveGridPoint>,
+            Option<&ResponsiveGridView>,
+        ),
+        Without<DebugOverlayTag>,
+    >,
+    mut paths: Query<&mut Path, With<DebugOverlayTag>>,
+    mut labels: Query<(&mut TextValue, &mut Position<InterfaceContext>), With<DebugOverlayTag>>,
+    mut entities: Local<OverlayEntities>,
+    mut cmd: Commands,
+) {
+    if !overlay.enabled() {
+        if overlay.is_changed() {
+            for entity in entities.drain() {
+                cmd.entity(entity).despawn();
+            }
+        }
+        return;
+    }
+    let entities = &mut *entities;
+    let layer = Layer::new(0f32);
+    let area = viewport_handle.section.area;
+    let segments = config.descriptor(grid.breakpoint).columns;
+    let rows = config.vertical_descriptor(grid.vertical_breakpoint).rows;
+    let grid_paths = [
+        (
+            &mut entities.columns,
+            grid_path(&grid, GridDirection::Horizontal, segments, area.height),
+            Color::CYAN,
+        ),
+        (
+            &mut entities.rows,
+            grid_path(&grid, GridDirection::Vertical, rows, area.width),
+            Color::LIGHT_GREEN,
+        ),
+    ];
+    for (slot, path, color) in grid_paths {
+        match slot.and_then(|line| paths.get_mut(line).ok()) {
+            Some(mut existing) => {
+                if existing.points != path {
+                    existing.points = path;
+                }
+            }
+            None => {
+                let id = cmd
+                    .spawn(Line::new(path, layer, color))
+                    .insert(DebugOverlayTag::new())
+                    .id();
+                slot.replace(id);
+            }
+        }
+    }
+    let mut present = HashSet::new();
+    for (entity, pos, area, visible_section, point, view) in outlined.iter() {
+        present.insert(entity);
+        let section = Section::new(*pos, *area);
+        let visible = visible_section
+            .and_then(|visible| visible.section())
+            .unwrap_or(section);
+        let label = grid_label(&grid, point, view);
+        match entities.outlines.get_mut(&entity) {
+            Some(outline) => {
+                if let Ok(mut path) = paths.get_mut(outline.section) {
+                    let points = outline_path(section);
+                    if path.points != points {
+                        path.points = points;
+                    }
+                }
+                if let Ok(mut path) = paths.get_mut(outline.visible) {
+                    let points = outline_path(visible);
+                    if path.points != points {
+                        path.points = points;
+                    }
+                }
+                match (outline.label, label) {
+                    (Some(existing), Some(label)) => {
+                        if let Ok((mut value, mut label_pos)) = labels.get_mut(existing) {
+                            if value.0 != label {
+                                value.0 = label;
+                            }
+                            if *label_pos != section.position {
+                                *label_pos = section.position;
+                            }
+                        }
+                    }
+                    (Some(existing), None) => {
+                        cmd.entity(existing).despawn();
+                        outline.label.take();
+                    }
+                    (None, Some(label)) => {
+                        outline
+                            .label
+                            .replace(spawn_label(&mut cmd, label, section, layer));
+                    }
+                    (None, None) => {}
+                }
+            }
+            None => {
+                let outline = OverlayOutline {
+                    section: cmd
+                        .spawn(Line::new(outline_path(section), layer, Color::OFF_WHITE))
+                        .insert(DebugOverlayTag::new())
+                        .id(),
+                    visible: cmd
+                        .spawn(Line::new(outline_path(visible), layer, Color::RED_ORANGE))
+                        .insert(DebugOverlayTag::new())
+                        .id(),
+                    label: label.map(|label| spawn_label(&mut cmd, label, section, layer)),
+                };
+                entities.outlines.insert(entity, outline);
+            }
+        }
+    }
+    let stale = entities
+        .outlines
+        .keys()
+        .filter(|entity| !present.contains(entity))
+        .copied()
+        .collect::<Vec<Entity>>();
+    for entity in stale {
+        if let Some(outline) = entities.outlines.remove(&entity) {
+            cmd.entity(outline.section).despawn();
+            cmd.entity(outline.visible).despawn();
+            if let Some(label) = outline.label {
+                cmd.entity(label).despawn();
+            }
+        }
+    }
+}
+fn spawn_label(
+    cmd: &mut Commands,
+    label: String,
+    section: Section<InterfaceContext>,
+    layer: Layer,
+) -> Entity {
+    cmd.spawn(Text::new(
+        layer,
+        label,
+        DebugOverlay::LABEL_SCALE,
+        Color::OFF_WHITE,
+        TextWrapStyle::letter(),
+    ))
+    .insert((
+        section.position,
+        Area::<InterfaceContext>::new(
+            section.width().max(DebugOverlay::LABEL_SCALE as f32 * 8f32),
+            DebugOverlay::LABEL_SCALE as f32 * 2f32,
+        ),
+        DebugOverlayTag::new(),
+    ))
+    .id()
+}
 pub(crate) struct VisualDebugAttachment;
 impl Attach for VisualDebugAttachment {
     fn attach(visualizer: &mut Visualizer) {
+        visualizer.task(Visualizer::TASK_MAIN).add_systems((
+            section_outline.in_set(SyncPoint::PostSpawn),
+            toggle_debug_overlay.in_set(SyncPoint::Preparation),
+            debug_overlay.in_set(SyncPoint::Reconfigure),
+        ));
         visualizer
-            .task(Visualizer::TASK_MAIN)
-            .add_systems((section_outline.in_set(SyncPoint::PostSpawn),));
+            .job
+            .container
+            .insert_resource(DebugOverlay::default());
     }
 }
@@ -8,7 +8,8 @@ use bevy_ecs::prelude::{Bundle, Component, Event, Events, IntoSystemConfigs};
 use tracing::{info, trace};
 use wasm_bindgen::JsValue;
 use winit::dpi::{PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton};
+use winit::event::{ElementState, KeyEvent, MouseButton};
+use winit::keyboard::ModifiersState;
 use winit::window::Window;
 
 use crate::animate::{end_animations, pull_from_queue, start_animations, update_animations};
@@ -22,7 +23,9 @@ use crate::gfx::GfxSurfaceConfiguration;
 use crate::images::ImageAttachment;
 use crate::interaction::{InteractionAttachment, InteractionDevice, MouseAdapter};
 use crate::job::{attempt_to_idle, Task, TaskLabel};
+use crate::keyboard::KeyboardAttachment;
 use crate::line::LineAttachment;
+use crate::localization::LocalizationAttachment;
 use crate::orientation::OrientationAttachment;
 use crate::panel::PanelAttachment;
 use crate::render::{internal_render, invoke_render, Render, RenderPhase, RenderTaskManager};
@@ -37,8 +40,9 @@ use crate::visual_debug::VisualDebugAttachment;
 use crate::window::WindowAttachment;
 use crate::{
     Animate, Area, DeviceContext, GfxOptions, GfxSurface, InteractionEvent, InteractionPhase, Job,
-    JobSyncPoint, MsaaRenderAdapter, PrimaryInteraction, PrimaryMouseButton, ScaleFactor, Section,
-    SyncPoint, Theme, Viewport, ViewportHandle, WindowResize,
+    JobSyncPoint, KeyboardInput, KeyboardModifiers, MsaaRenderAdapter, PrimaryInteraction,
+    PrimaryMouseButton, ScaleFactor, Section, SyncPoint, Theme, Viewport, ViewportHandle,
+    WindowResize,
 };
 
 /// Used to hold queued attachments until ready to invoke attach to the Visualizer
@@ -167,10 +171,12 @@ impl Visualizer {
         self.invoke_attach::<VisibilityAttachment>();
         self.invoke_attach::<InteractionAttachment>();
         self.invoke_attach::<FocusAttachment>();
+        self.invoke_attach::<KeyboardAttachment>();
         self.invoke_attach::<OrientationAttachment>();
         self.invoke_attach::<TimerAttachment>();
         self.invoke_attach::<VirtualKeyboardAttachment>();
         self.invoke_attach::<TextAttachment>();
+        self.invoke_attach::<LocalizationAttachment>();
         self.invoke_attach::<ButtonAttachment>();
         self.invoke_attach::<ImageAttachment>();
         self.invoke_attach::<CoordinateAttachment>();
@@ -296,6 +302,25 @@ impl Visualizer {
     pub fn cancel_touches(&mut self) {
         // self.job.container.send_event(InteractionEvent::new());
     }
+    pub fn register_key_input(&mut self, event: KeyEvent) {
+        let modifiers = self
+            .job
+            .container
+            .get_resource::<KeyboardModifiers>()
+            .expect("keyboard modifiers")
+            .0;
+        self.job.container.send_event(KeyboardInput {
+            key: event.logical_key,
+            state: event.state,
+            repeat: event.repeat,
+            modifiers,
+        });
+    }
+    pub fn set_keyboard_modifiers(&mut self, modifiers: ModifiersState) {
+        self.job
+            .container
+            .insert_resource(KeyboardModifiers(modifiers));
+    }
     pub fn set_scale_factor(&mut self, scale_factor: f32) {
         self.job
             .container
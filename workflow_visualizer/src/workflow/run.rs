@@ -74,8 +74,12 @@ pub(crate) fn internal_loop<T: Workflow + 'static>(
             WindowEvent::HoveredFile(_) => {}
             WindowEvent::HoveredFileCancelled => {}
             WindowEvent::Focused(_) => {}
-            WindowEvent::KeyboardInput { .. } => {}
-            WindowEvent::ModifiersChanged(_) => {}
+            WindowEvent::KeyboardInput { event, .. } => {
+                visualizer.register_key_input(event);
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                visualizer.set_keyboard_modifiers(modifiers.state());
+            }
             WindowEvent::Ime(_) => {}
             WindowEvent::TouchpadMagnify { .. } => {}
             WindowEvent::SmartMagnify { .. } => {}
@@ -17,6 +17,8 @@ pub struct Runner {
     pub(crate) android_app: Option<()>,
     #[cfg(target_os = "android")]
     pub(crate) android_app: Option<AndroidApp>,
+    #[cfg(target_family = "wasm")]
+    pub(crate) image_worker_path: Option<String>,
 }
 
 impl Default for Runner {
@@ -31,6 +33,8 @@ impl Runner {
             attachment_queue: vec![],
             _desktop_dimensions: None,
             android_app: None,
+            #[cfg(target_family = "wasm")]
+            image_worker_path: None,
         }
     }
     /// insert the AndroidApp for interfacing with the Android OS
@@ -39,6 +43,13 @@ impl Runner {
         self.android_app.replace(android_app);
         self
     }
+    /// decode images in the web worker at `worker_path`, a script calling
+    /// `start_image_worker`; without one images decode on the UI thread
+    #[cfg(target_family = "wasm")]
+    pub fn with_image_worker<S: Into<String>>(mut self, worker_path: S) -> Self {
+        self.image_worker_path.replace(worker_path.into());
+        self
+    }
     /// set the fixed dimensions on the desktop platforms
     pub fn with_desktop_dimensions<A: Into<Area<DeviceContext>>>(mut self, dim: A) -> Self {
         self._desktop_dimensions.replace(dim.into());
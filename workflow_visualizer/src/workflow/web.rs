@@ -107,10 +107,13 @@ fn web_resizing(window: &Rc<Window>) {
 }
 #[cfg(target_family = "wasm")]
 pub(crate) async fn internal_web_run<T: Workflow + 'static + Default>(
-    _runner: Runner,
+    runner: Runner,
     mut visualizer: Visualizer,
     worker_path: String,
 ) {
+    if let Some(path) = runner.image_worker_path {
+        crate::images::decode::use_decode_worker(path);
+    }
     let event_loop = EventLoopBuilder::<T::Response>::with_user_event()
         .build()
         .expect("event-loop");